@@ -264,6 +264,8 @@ pub async fn new_wallet_context_from_cluster(
         }],
         active_address: Some(address),
         active_env: Some("localnet".to_string()),
+        aliases: Default::default(),
+        seed: None,
     }
     .persisted(&wallet_config_path)
     .save()
@@ -6,7 +6,7 @@ use crate::{
     genesis_config::{GenesisConfig, ValidatorGenesisInfo},
     p2p::P2pConfig,
     utils, ConsensusConfig, NetworkConfig, NodeConfig, ValidatorInfo, AUTHORITIES_DB_NAME,
-    CONSENSUS_DB_NAME,
+    CONSENSUS_DB_NAME, DEFAULT_COMMISSION_RATE,
 };
 use rand::rngs::OsRng;
 use std::{
@@ -217,10 +217,14 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                         stake,
                         delegation: 0, // no delegation yet at genesis
                         gas_price: validator.gas_price,
+                        commission_rate: DEFAULT_COMMISSION_RATE,
                         network_address,
                         narwhal_primary_address: validator.narwhal_primary_address.clone(),
                         narwhal_worker_address: validator.narwhal_worker_address.clone(),
                         narwhal_consensus_address: validator.narwhal_consensus_address.clone(),
+                        description: String::new(),
+                        image_url: String::new(),
+                        project_url: String::new(),
                     },
                     pop,
                 )
@@ -239,7 +243,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                 builder = builder.add_validator(validator, proof_of_possession);
             }
 
-            builder.build()
+            builder.build().unwrap()
         };
 
         let validator_configs = validators
@@ -281,8 +285,11 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     websocket_address: None,
                     consensus_config: Some(consensus_config),
                     enable_event_processing: false,
+                    commit_sink_dir: None,
+                    checkpoint_archive_dir: None,
                     enable_checkpoint: true,
                     enable_reconfig: false,
+                    sync_mode: Default::default(),
                     genesis: crate::node::Genesis::new(genesis.clone()),
                     grpc_load_shed: initial_accounts_config.grpc_load_shed,
                     grpc_concurrency_limit: initial_accounts_config.grpc_concurrency_limit,
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::ValidatorInfo;
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, ensure, Context, Result};
 use camino::Utf8Path;
 use fastcrypto::encoding::{Base64, Encoding};
 use move_binary_format::CompiledModule;
@@ -12,14 +12,14 @@ use move_vm_runtime::native_functions::NativeFunctionTable;
 use narwhal_crypto::NetworkPublicKey;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryInto;
 use std::{fs, path::Path};
 use sui_adapter::adapter;
 use sui_adapter::adapter::MoveVM;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::TransactionDigest;
-use sui_types::crypto::{AuthorityPublicKey, ToFromBytes};
+use sui_types::crypto::{verify_proof_of_possession, AuthorityPublicKey, ToFromBytes};
 use sui_types::crypto::{AuthorityPublicKeyBytes, AuthoritySignature};
 use sui_types::gas::SuiGasStatus;
 use sui_types::in_memory_storage::InMemoryStorage;
@@ -32,7 +32,7 @@ use sui_types::MOVE_STDLIB_ADDRESS;
 use sui_types::SUI_FRAMEWORK_ADDRESS;
 use sui_types::{
     base_types::{encode_bytes_hex, TxContext},
-    committee::{Committee, EpochId},
+    committee::{Committee, EpochId, StakeUnit},
     error::SuiResult,
     object::Object,
     sui_serde::AuthSignature,
@@ -142,7 +142,9 @@ impl Genesis {
     }
 
     pub fn get_default_genesis() -> Self {
-        Builder::new().build()
+        Builder::new()
+            .build()
+            .expect("A genesis without validators is always valid")
     }
 
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, anyhow::Error> {
@@ -292,7 +294,127 @@ impl Builder {
         self
     }
 
-    pub fn build(self) -> Genesis {
+    pub fn validators(&self) -> &BTreeMap<AuthorityPublicKeyBytes, GenesisValidatorInfo> {
+        &self.validators
+    }
+
+    pub fn objects(&self) -> &BTreeMap<ObjectID, Object> {
+        &self.objects
+    }
+
+    /// Check that the validators can form a working committee: that keys and addresses aren't
+    /// shared between validators, that every protocol key comes with a valid proof of
+    /// possession, and that the stake is sound. All the problems found are reported together.
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+
+        let mut names = BTreeSet::new();
+        let mut sui_addresses = BTreeSet::new();
+        let mut network_keys = BTreeSet::new();
+        let mut worker_keys = BTreeSet::new();
+        let mut multiaddrs = BTreeSet::new();
+        let mut total_stake: StakeUnit = 0;
+        for GenesisValidatorInfo {
+            info: validator,
+            proof_of_possession,
+        } in self.validators.values()
+        {
+            let name = validator.name();
+            if !names.insert(name) {
+                problems.push(format!("duplicate validator name {name}"));
+            }
+            if !sui_addresses.insert(validator.sui_address()) {
+                problems.push(format!(
+                    "validator {name} shares account address {} with another validator",
+                    validator.sui_address()
+                ));
+            }
+            if !network_keys.insert(validator.network_key()) {
+                problems.push(format!(
+                    "validator {name} shares its network key with another validator"
+                ));
+            }
+            if !worker_keys.insert(validator.worker_key()) {
+                problems.push(format!(
+                    "validator {name} shares its worker key with another validator"
+                ));
+            }
+            let addresses: BTreeSet<_> = [
+                validator.network_address(),
+                &validator.narwhal_primary_address,
+                &validator.narwhal_worker_address,
+                &validator.narwhal_consensus_address,
+            ]
+            .into_iter()
+            .collect();
+            for address in addresses {
+                if !multiaddrs.insert(address) {
+                    problems.push(format!(
+                        "validator {name} shares network address {address} with another validator"
+                    ));
+                }
+            }
+
+            match AuthorityPublicKey::try_from(validator.protocol_key()) {
+                Ok(protocol_key) => {
+                    if let Err(e) = verify_proof_of_possession(
+                        proof_of_possession,
+                        &protocol_key,
+                        validator.sui_address(),
+                    ) {
+                        problems.push(format!("validator {name}: {e}"));
+                    }
+                }
+                Err(e) => {
+                    problems.push(format!("validator {name} has an invalid protocol key: {e}"))
+                }
+            }
+
+            if validator.stake() == 0 {
+                problems.push(format!("validator {name} has no stake"));
+            }
+            if validator.commission_rate() > MAX_COMMISSION_RATE {
+                problems.push(format!(
+                    "validator {name} has a commission rate of {} basis points, above {MAX_COMMISSION_RATE}",
+                    validator.commission_rate()
+                ));
+            }
+            match total_stake
+                .checked_add(validator.stake())
+                .and_then(|total| total.checked_add(validator.delegation()))
+            {
+                Some(total) => total_stake = total,
+                None => problems.push("total stake overflows".to_owned()),
+            }
+        }
+
+        // A single validator must not be able to form a quorum on its own.
+        if self.validators.len() > 1 {
+            let quorum_threshold = 2 * u128::from(total_stake) / 3 + 1;
+            for GenesisValidatorInfo { info, .. } in self.validators.values() {
+                let voting_power = info.stake().saturating_add(info.delegation());
+                if u128::from(voting_power) >= quorum_threshold {
+                    problems.push(format!(
+                        "validator {} holds {voting_power} of the {total_stake} total stake, a quorum on its own",
+                        info.name()
+                    ));
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            bail!(
+                "invalid genesis validator set:\n  {}",
+                problems.join("\n  ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Build the genesis, after checking the validator set with [`Builder::validate`].
+    pub fn build(self) -> Result<Genesis> {
+        self.validate()?;
+
         let mut genesis_ctx = sui_adapter::genesis::get_genesis_context();
 
         // Get Move and Sui Framework
@@ -307,6 +429,7 @@ impl Builder {
             .into_iter()
             .map(|(_, v)| v)
             .collect::<Vec<_>>();
+
         let objects = create_genesis_objects(&mut genesis_ctx, &modules, &objects, &validators);
 
         let genesis = Genesis {
@@ -319,30 +442,56 @@ impl Builder {
 
         // Verify that all the validators were properly created onchain
         let system_object = genesis.sui_system_object();
-        assert_eq!(system_object.epoch, 0);
+        ensure!(
+            system_object.epoch == 0,
+            "genesis system object starts at epoch {}",
+            system_object.epoch
+        );
 
         for (validator, onchain_validator) in genesis
             .validator_set()
             .iter()
             .zip(system_object.validators.active_validators.iter())
         {
-            assert_eq!(validator.stake(), onchain_validator.stake_amount);
-            assert_eq!(
-                validator.sui_address().to_vec(),
-                onchain_validator.metadata.sui_address.to_vec(),
+            let metadata = &onchain_validator.metadata;
+            let name = validator.name();
+            ensure!(
+                validator.stake() == onchain_validator.stake_amount,
+                "validator {name} has the wrong stake onchain"
             );
-            assert_eq!(
-                validator.protocol_key().as_ref().to_vec(),
-                onchain_validator.metadata.pubkey_bytes,
+            ensure!(
+                validator.sui_address().to_vec() == metadata.sui_address.to_vec(),
+                "validator {name} has the wrong address onchain"
             );
-            assert_eq!(validator.name().as_bytes(), onchain_validator.metadata.name);
-            assert_eq!(
-                validator.network_address().to_vec(),
-                onchain_validator.metadata.net_address
+            ensure!(
+                validator.protocol_key().as_ref().to_vec() == metadata.pubkey_bytes,
+                "validator {name} has the wrong protocol key onchain"
+            );
+            ensure!(
+                name.as_bytes() == metadata.name,
+                "validator {name} has the wrong name onchain"
+            );
+            ensure!(
+                validator.network_address().to_vec() == metadata.net_address,
+                "validator {name} has the wrong network address onchain"
+            );
+            ensure!(
+                validator.gas_price() == metadata.next_epoch_gas_price,
+                "validator {name} has the wrong gas price onchain"
+            );
+            ensure!(
+                validator.commission_rate() == metadata.commission_rate,
+                "validator {name} has the wrong commission rate onchain"
+            );
+            ensure!(
+                validator.description().as_bytes() == metadata.description
+                    && validator.image_url().as_bytes() == metadata.image_url
+                    && validator.project_url().as_bytes() == metadata.project_url,
+                "validator {name} has the wrong description or URLs onchain"
             );
         }
 
-        genesis
+        Ok(genesis)
     }
 
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, anyhow::Error> {
@@ -540,8 +689,12 @@ pub fn generate_genesis_system_object(
     let mut sui_addresses = Vec::new();
     let mut network_addresses = Vec::new();
     let mut names = Vec::new();
+    let mut descriptions = Vec::new();
+    let mut image_urls = Vec::new();
+    let mut project_urls = Vec::new();
     let mut stakes = Vec::new();
     let mut gas_prices = Vec::new();
+    let mut commission_rates = Vec::new();
 
     for GenesisValidatorInfo {
         info: validator,
@@ -554,8 +707,12 @@ pub fn generate_genesis_system_object(
         sui_addresses.push(validator.sui_address());
         network_addresses.push(validator.network_address());
         names.push(validator.name().to_owned().into_bytes());
+        descriptions.push(validator.description().to_owned().into_bytes());
+        image_urls.push(validator.image_url().to_owned().into_bytes());
+        project_urls.push(validator.project_url().to_owned().into_bytes());
         stakes.push(validator.stake());
         gas_prices.push(validator.gas_price());
+        commission_rates.push(validator.commission_rate());
     }
 
     adapter::execute(
@@ -571,8 +728,12 @@ pub fn generate_genesis_system_object(
             CallArg::Pure(bcs::to_bytes(&sui_addresses).unwrap()),
             CallArg::Pure(bcs::to_bytes(&names).unwrap()),
             CallArg::Pure(bcs::to_bytes(&network_addresses).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&descriptions).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&image_urls).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&project_urls).unwrap()),
             CallArg::Pure(bcs::to_bytes(&stakes).unwrap()),
             CallArg::Pure(bcs::to_bytes(&gas_prices).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&commission_rates).unwrap()),
         ],
         SuiGasStatus::new_unmetered().create_move_gas_status(),
         genesis_ctx,
@@ -590,6 +751,9 @@ pub fn generate_genesis_system_object(
     Ok(())
 }
 
+/// Commission rates are in basis points, so at most 100%.
+const MAX_COMMISSION_RATE: u64 = 10_000;
+
 const GENESIS_BUILDER_OBJECT_DIR: &str = "objects";
 const GENESIS_BUILDER_COMMITTEE_DIR: &str = "committee";
 
@@ -605,7 +769,7 @@ mod test {
 
     #[test]
     fn roundtrip() {
-        let genesis = Builder::new().build();
+        let genesis = Builder::new().build().unwrap();
 
         let s = serde_yaml::to_string(&genesis).unwrap();
         let from_s = serde_yaml::from_str(&s).unwrap();
@@ -632,10 +796,14 @@ mod test {
             stake: 1,
             delegation: 0,
             gas_price: 1,
+            commission_rate: 0,
             network_address: utils::new_network_address(),
             narwhal_primary_address: utils::new_network_address(),
             narwhal_worker_address: utils::new_network_address(),
             narwhal_consensus_address: utils::new_network_address(),
+            description: String::new(),
+            image_url: String::new(),
+            project_url: String::new(),
         };
         let pop = generate_proof_of_possession(&key, account_key.public().into());
         let builder = Builder::new()
@@ -644,4 +812,101 @@ mod test {
         builder.save(dir.path()).unwrap();
         Builder::load(dir.path()).unwrap();
     }
+
+    #[test]
+    fn build_rejects_invalid_proof_of_possession() {
+        let key: AuthorityKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+        let worker_key: NetworkKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+        let account_key: AccountKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+        let network_key: NetworkKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+        let validator = ValidatorInfo {
+            name: "0".into(),
+            protocol_key: key.public().into(),
+            worker_key: worker_key.public().clone(),
+            account_key: account_key.public().clone().into(),
+            network_key: network_key.public().clone(),
+            stake: 1,
+            delegation: 0,
+            gas_price: 1,
+            commission_rate: 0,
+            network_address: utils::new_network_address(),
+            narwhal_primary_address: utils::new_network_address(),
+            narwhal_worker_address: utils::new_network_address(),
+            narwhal_consensus_address: utils::new_network_address(),
+            description: String::new(),
+            image_url: String::new(),
+            project_url: String::new(),
+        };
+        // A proof of possession of another protocol key
+        let other_key: AuthorityKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+        let pop = generate_proof_of_possession(&other_key, account_key.public().into());
+        let error = Builder::new()
+            .add_validator(validator, pop)
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid proof of possession"));
+    }
+
+    #[test]
+    fn validate() {
+        let validator = |name: &str, stake| {
+            let key: AuthorityKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+            let worker_key: NetworkKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+            let account_key: AccountKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+            let network_key: NetworkKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
+            let info = ValidatorInfo {
+                name: name.into(),
+                protocol_key: key.public().into(),
+                worker_key: worker_key.public().clone(),
+                account_key: account_key.public().clone().into(),
+                network_key: network_key.public().clone(),
+                stake,
+                delegation: 0,
+                gas_price: 1,
+                commission_rate: 0,
+                network_address: utils::new_network_address(),
+                narwhal_primary_address: utils::new_network_address(),
+                narwhal_worker_address: utils::new_network_address(),
+                narwhal_consensus_address: utils::new_network_address(),
+                description: String::new(),
+                image_url: String::new(),
+                project_url: String::new(),
+            };
+            let pop = generate_proof_of_possession(&key, account_key.public().into());
+            (info, pop, key)
+        };
+
+        let (info_0, pop_0, _) = validator("0", 1);
+        let (info_1, pop_1, _) = validator("1", 1);
+        let (info_2, pop_2, _) = validator("2", 1);
+        Builder::new()
+            .add_validator(info_0.clone(), pop_0.clone())
+            .add_validator(info_1.clone(), pop_1)
+            .add_validator(info_2.clone(), pop_2)
+            .validate()
+            .unwrap();
+
+        // A single validator with a quorum of the stake
+        let (whale, whale_pop, _) = validator("whale", 3);
+        let error = Builder::new()
+            .add_validator(info_0.clone(), pop_0.clone())
+            .add_validator(whale, whale_pop)
+            .validate()
+            .unwrap_err();
+        assert!(error.to_string().contains("a quorum on its own"));
+
+        // A reused address, no stake and a proof of possession bound to another address
+        let (mut copycat, _, copycat_key) = validator("copycat", 0);
+        copycat.network_address = info_0.network_address.clone();
+        let wrong_pop = generate_proof_of_possession(&copycat_key, info_0.sui_address());
+        let error = Builder::new()
+            .add_validator(info_0, pop_0)
+            .add_validator(copycat, wrong_pop)
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("shares network address"));
+        assert!(error.contains("has no stake"));
+        assert!(error.contains("Invalid proof of possession"));
+    }
 }
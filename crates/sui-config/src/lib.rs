@@ -19,7 +19,7 @@ pub mod p2p;
 mod swarm;
 pub mod utils;
 
-pub use node::{ConsensusConfig, NodeConfig, ValidatorInfo};
+pub use node::{ConsensusConfig, FullNodeSyncMode, NodeConfig, ValidatorInfo};
 pub use swarm::NetworkConfig;
 
 const SUI_DIR: &str = ".sui";
@@ -36,8 +36,9 @@ pub const AUTHORITIES_DB_NAME: &str = "authorities_db";
 pub const CONSENSUS_DB_NAME: &str = "consensus_db";
 pub const FULL_NODE_DB_PATH: &str = "full_node_db";
 
-const DEFAULT_STAKE: StakeUnit = 100000000000000;
-const DEFAULT_GAS_PRICE: u64 = 1;
+pub const DEFAULT_STAKE: StakeUnit = 100000000000000;
+pub const DEFAULT_GAS_PRICE: u64 = 1;
+pub const DEFAULT_COMMISSION_RATE: u64 = 0;
 
 pub fn sui_config_dir() -> Result<PathBuf, anyhow::Error> {
     match std::env::var_os("SUI_CONFIG_DIR") {
@@ -65,12 +65,26 @@ pub struct NodeConfig {
     #[serde(default)]
     pub enable_event_processing: bool,
 
+    /// If set, every committed transaction is written as JSON lines to files in this directory,
+    /// for consumption by external indexers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sink_dir: Option<PathBuf>,
+
+    /// If set, every certified checkpoint and its transactions are written to this directory,
+    /// unless the node is a fullnode syncing from it (`sync-mode: archive`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_archive_dir: Option<PathBuf>,
+
     #[serde(default = "bool_true")]
     pub enable_checkpoint: bool,
 
     #[serde(default)]
     pub enable_reconfig: bool,
 
+    /// How a fullnode keeps up with the validators. Ignored by validators.
+    #[serde(default)]
+    pub sync_mode: FullNodeSyncMode,
+
     #[serde(default)]
     pub grpc_load_shed: Option<bool>,
 
@@ -83,6 +97,20 @@ pub struct NodeConfig {
     pub genesis: Genesis,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FullNodeSyncMode {
+    /// Follow the batch stream of each validator, and execute transactions once enough
+    /// validators have voted for the same effects.
+    #[default]
+    Follower,
+    /// Download certified checkpoints and execute their transactions in checkpoint order.
+    Checkpoint,
+    /// Replay the checkpoints of the archive in `checkpoint_archive_dir` instead of talking to
+    /// validators.
+    Archive,
+}
+
 fn default_key_pair() -> Arc<AuthorityKeyPair> {
     Arc::new(sui_types::crypto::get_key_pair().1)
 }
@@ -207,12 +235,18 @@ pub struct ValidatorInfo {
     pub stake: StakeUnit,
     pub delegation: StakeUnit,
     pub gas_price: u64,
+    /// Commission charged on the rewards of delegators, in basis points.
+    pub commission_rate: u64,
     pub network_address: Multiaddr,
     pub narwhal_primary_address: Multiaddr,
 
     //TODO remove all of these as they shouldn't be needed to be encoded in genesis
     pub narwhal_worker_address: Multiaddr,
     pub narwhal_consensus_address: Multiaddr,
+
+    pub description: String,
+    pub image_url: String,
+    pub project_url: String,
 }
 
 impl ValidatorInfo {
@@ -252,10 +286,26 @@ impl ValidatorInfo {
         self.gas_price
     }
 
+    pub fn commission_rate(&self) -> u64 {
+        self.commission_rate
+    }
+
     pub fn network_address(&self) -> &Multiaddr {
         &self.network_address
     }
 
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn image_url(&self) -> &str {
+        &self.image_url
+    }
+
+    pub fn project_url(&self) -> &str {
+        &self.project_url
+    }
+
     pub fn voting_rights(validator_set: &[Self]) -> BTreeMap<AuthorityPublicKeyBytes, u64> {
         validator_set
             .iter()
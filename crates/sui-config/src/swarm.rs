@@ -120,8 +120,11 @@ impl NetworkConfig {
             },
             consensus_config: None,
             enable_event_processing,
+            commit_sink_dir: None,
+            checkpoint_archive_dir: None,
             enable_checkpoint: true,
             enable_reconfig: false,
+            sync_mode: Default::default(),
             genesis: validator_config.genesis.clone(),
             grpc_load_shed: None,
             grpc_concurrency_limit: None,
@@ -53,7 +53,7 @@ fn genesis_config_snapshot_matches() {
 // re-create the genesis blob.
 #[test]
 fn empty_genesis_snapshot_matches() {
-    let genesis = Builder::new().build();
+    let genesis = Builder::new().build().unwrap();
     assert_yaml_snapshot!(genesis);
 }
 
@@ -76,17 +76,22 @@ fn populated_genesis_snapshot_matches() {
         stake: 1,
         delegation: 0,
         gas_price: 1,
+        commission_rate: 0,
         network_address: Multiaddr::empty(),
         narwhal_primary_address: Multiaddr::empty(),
         narwhal_worker_address: Multiaddr::empty(),
         narwhal_consensus_address: Multiaddr::empty(),
+        description: String::new(),
+        image_url: String::new(),
+        project_url: String::new(),
     };
     let pop = generate_proof_of_possession(&key, account_key.public().into());
 
     let genesis = Builder::new()
         .add_objects(objects)
         .add_validator(validator, pop)
-        .build();
+        .build()
+        .unwrap();
     assert_yaml_snapshot!(genesis.validator_set());
     assert_yaml_snapshot!(genesis.committee().unwrap());
     assert_yaml_snapshot!(genesis.narwhal_committee());
@@ -6,8 +6,9 @@ use std::hash::Hash;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeSet, HashMap, VecDeque},
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -67,9 +68,9 @@ use sui_types::messages_checkpoint::{
     AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointFragmentMessage,
     CheckpointRequest, CheckpointRequestType, CheckpointResponse, CheckpointSequenceNumber,
 };
-use sui_types::object::{Owner, PastObjectRead};
-use sui_types::query::{EventQuery, TransactionQuery};
-use sui_types::sui_system_state::SuiSystemState;
+use sui_types::object::{Owner, OwnerChange, PastObjectRead};
+use sui_types::query::{EventQuery, OwnerChangeQuery, TransactionQuery};
+use sui_types::sui_system_state::{EpochStats, SuiSystemState};
 use sui_types::temporary_store::InnerTemporaryStore;
 pub use sui_types::temporary_store::TemporaryStore;
 use sui_types::{
@@ -96,6 +97,7 @@ use crate::scoped_counter;
 use crate::{
     authority_batch::{BroadcastReceiver, BroadcastSender},
     checkpoints::CheckpointStore,
+    commit_sink::{CommitSink, CommitSinkStore, CommittedTransaction},
     event_handler::EventHandler,
     execution_engine,
     metrics::start_timer,
@@ -127,6 +129,10 @@ mod authority_store;
 
 pub const MAX_ITEMS_LIMIT: u64 = 1_000;
 const BROADCAST_CAPACITY: usize = 10_000;
+const COMMIT_SINK_MIN_RETRY_DELAY: Duration = Duration::from_secs(1);
+const COMMIT_SINK_MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+/// The number of sequence numbers whose transactions are loaded at once for a commit sink.
+const COMMIT_SINK_BATCH_SIZE: TxSequenceNumber = 1000;
 
 pub(crate) const MAX_TX_RECOVERY_RETRY: u32 = 3;
 type CertTxGuard<'a> = DBTxGuard<'a, TrustedCertificate>;
@@ -1040,6 +1046,32 @@ impl AuthorityState {
         }
     }
 
+    /// Returns the packages a transaction interacted with: the packages it called or published,
+    /// and the packages whose modules emitted its Move events, which covers calls made from other
+    /// packages. The packages of the objects it touched are left out: every transaction pays for
+    /// gas with a `0x2::coin::Coin`, which would index all of them under the framework.
+    fn touched_packages(
+        cert: &VerifiedCertificate,
+        effects: &SignedTransactionEffects,
+    ) -> BTreeSet<ObjectID> {
+        let mut packages: BTreeSet<ObjectID> = cert
+            .signed_data
+            .data
+            .move_calls()
+            .iter()
+            .map(|mc| mc.package.0)
+            .collect();
+        for event in &effects.effects.events {
+            match event {
+                Event::MoveEvent { package_id, .. } | Event::Publish { package_id, .. } => {
+                    packages.insert(*package_id);
+                }
+                _ => {}
+            }
+        }
+        packages
+    }
+
     #[instrument(level = "debug", skip_all, fields(seq = ?seq, tx_digest =? digest), err)]
     fn index_tx(
         &self,
@@ -1066,6 +1098,12 @@ impl AuthorityState {
                 .move_calls()
                 .iter()
                 .map(|mc| (mc.package.0, mc.module.clone(), mc.function.clone())),
+            effects
+                .effects
+                .events
+                .iter()
+                .filter_map(|event| event.move_event_name()),
+            Self::touched_packages(cert, effects).into_iter(),
             seq,
             digest,
             timestamp_ms,
@@ -1108,6 +1146,18 @@ impl AuthorityState {
                 .index_tx(indexes.as_ref(), seq, digest, &cert, &effects, timestamp_ms)
                 .tap_ok(|_| self.metrics.post_processing_total_tx_indexed.inc())
                 .tap_err(|e| warn!(tx_digest=?digest, "Post processing - Couldn't index tx: {e}"));
+
+            // A synced checkpoint can be stored before its transactions are executed, in which
+            // case `post_process_checkpoint` has skipped this transaction.
+            let checkpoint = self
+                .checkpoints
+                .lock()
+                .get_transaction_checkpoint(&ExecutionDigests::new(*digest, *effects.digest()))?;
+            if let Some(checkpoint) = checkpoint {
+                let _ = indexes
+                    .index_checkpoint_tx(checkpoint, seq, digest)
+                    .tap_err(|e| warn!(tx_digest=?digest, ?checkpoint, "Post processing - Couldn't index tx in checkpoint: {e}"));
+            }
         }
 
         // Stream transaction
@@ -1189,6 +1239,223 @@ impl AuthorityState {
         Ok(())
     }
 
+    /// Indexes the transactions of a certified checkpoint by the checkpoint's sequence number.
+    /// Only the transactions already executed locally are indexed here: a synced checkpoint can
+    /// be stored before its transactions are executed, and those are added to the index by
+    /// `post_process_one_tx` when they are.
+    #[instrument(level = "debug", skip_all, fields(checkpoint=?checkpoint), err)]
+    async fn post_process_checkpoint(
+        &self,
+        indexes: &IndexStore,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<bool> {
+        let contents = {
+            let checkpoints = self.checkpoints.lock();
+            match checkpoints.get_checkpoint(checkpoint)? {
+                Some(AuthenticatedCheckpoint::Certified(_)) => {
+                    checkpoints.get_checkpoint_contents(checkpoint)?
+                }
+                _ => None,
+            }
+        };
+        let contents = match contents {
+            Some(contents) => contents,
+            None => return Ok(false),
+        };
+
+        let mut transactions = Vec::new();
+        for digests in contents.iter() {
+            // Transactions of a restored state snapshot were never executed locally.
+            if let Some(seq) = self.database.get_tx_sequence(digests.transaction).await? {
+                transactions.push((seq, digests.transaction));
+            }
+        }
+        indexes.index_checkpoint(checkpoint, transactions.into_iter())?;
+        Ok(true)
+    }
+
+    /// Indexes every checkpoint after the last indexed one, up to and including `last`, in order.
+    /// Stops at the first checkpoint that is not certified or whose contents are not stored yet,
+    /// which is retried on the next call.
+    async fn post_process_checkpoints_up_to(
+        &self,
+        indexes: &IndexStore,
+        last: CheckpointSequenceNumber,
+    ) -> SuiResult {
+        let mut next = indexes.last_indexed_checkpoint()?.map_or(0, |seq| seq + 1);
+        while next <= last {
+            if !self.post_process_checkpoint(indexes, next).await? {
+                debug!(checkpoint = ?next, "Post processing - checkpoint not certified yet");
+                break;
+            }
+            next += 1;
+        }
+        Ok(())
+    }
+
+    /// Indexes the checkpoints certified after the last indexed one, first those stored while the
+    /// process was not running and then the new ones as they are stored.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn run_checkpoint_post_processing_process(&self) -> SuiResult {
+        let indexes = match &self.indexes {
+            Some(indexes) => indexes.clone(),
+            None => return Ok(()),
+        };
+        // Subscribe before backfilling so that no checkpoint is missed in between.
+        let mut subscriber = self.checkpoints.lock().subscribe_to_checkpoints();
+        debug!("subscribed to checkpoints");
+
+        self.backfill_checkpoint_indexes(&indexes).await;
+        loop {
+            match subscriber.recv().await {
+                Ok(checkpoint) => {
+                    let sequence = *checkpoint.summary.sequence_number();
+                    if let Err(e) = self
+                        .post_process_checkpoints_up_to(&indexes, sequence)
+                        .await
+                    {
+                        warn!(
+                            ?sequence,
+                            "Post processing - Couldn't index checkpoint: {e}"
+                        );
+                    }
+                }
+                Err(RecvError::Closed) => {
+                    error!("run_checkpoint_post_processing_process receiver channel closed. If this happens there is a bug");
+                    break;
+                }
+                Err(RecvError::Lagged(number_skipped)) => {
+                    warn!(
+                        "run_checkpoint_post_processing_process too slow, skipped {} checkpoints, backfilling them",
+                        number_skipped
+                    );
+                    self.backfill_checkpoint_indexes(&indexes).await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Indexes every stored certified checkpoint after the last indexed one.
+    async fn backfill_checkpoint_indexes(&self, indexes: &IndexStore) {
+        let latest = self
+            .checkpoints
+            .lock()
+            .latest_stored_checkpoint()
+            .map(|checkpoint| checkpoint.sequence_number());
+        if let Some(latest) = latest {
+            if let Err(e) = self.post_process_checkpoints_up_to(indexes, latest).await {
+                warn!(
+                    ?latest,
+                    "Post processing - Couldn't backfill checkpoints: {e}"
+                );
+            }
+        }
+    }
+
+    fn make_committed_transaction(
+        &self,
+        seq: TxSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> SuiResult<CommittedTransaction> {
+        let certificate =
+            self.database
+                .read_certificate(digest)?
+                .ok_or(SuiError::CertificateNotfound {
+                    certificate_digest: *digest,
+                })?;
+        let effects = self.database.get_effects(digest)?;
+        let written_objects = effects
+            .all_mutated()
+            .filter_map(|(obj_ref, _, _)| {
+                self.database
+                    .get_object_by_key(&obj_ref.0, obj_ref.1)
+                    .transpose()
+            })
+            .collect::<SuiResult<Vec<_>>>()?;
+        let timestamp_ms = match &self.indexes {
+            Some(indexes) => indexes.get_timestamp_ms(digest)?,
+            None => None,
+        };
+        Ok(CommittedTransaction {
+            sequence_number: seq,
+            timestamp_ms,
+            certificate: certificate.into_inner(),
+            events: effects.events.clone(),
+            effects,
+            written_objects,
+        })
+    }
+
+    /// Delivers every committed transaction to `sink` in sequence number order, starting from
+    /// the cursor persisted for the sink in `store`, and advances the cursor after each
+    /// successful delivery. Failures of the sink or of the store are retried with exponential
+    /// backoff, so the process only stops with the node.
+    #[instrument(level = "debug", skip_all, fields(sink = sink.name()))]
+    pub async fn run_commit_sink_process(
+        &self,
+        sink: Arc<dyn CommitSink>,
+        store: Arc<CommitSinkStore>,
+    ) -> SuiResult {
+        let mut subscriber = self.subscribe_batch();
+        debug!("starting commit sink");
+
+        let mut delay = COMMIT_SINK_MIN_RETRY_DELAY;
+        loop {
+            // On failure the cursor still points at the first undelivered transaction, so
+            // delivery resumes from there once the sink or the store recovers.
+            if let Err(e) = self.deliver_commits(&*sink, &store).await {
+                error!(?delay, "Commit sink failed, retrying: {e}");
+                tokio::time::sleep(delay).await;
+                delay = std::cmp::min(delay * 2, COMMIT_SINK_MAX_RETRY_DELAY);
+                continue;
+            }
+            delay = COMMIT_SINK_MIN_RETRY_DELAY;
+
+            // Wait for more transactions to be committed. Lagging behind is fine, as the
+            // subscription is only used as a wake up signal.
+            match subscriber.recv().await {
+                Ok(_) | Err(RecvError::Lagged(_)) => (),
+                Err(RecvError::Closed) => {
+                    error!("run_commit_sink_process receiver channel closed. If this happens there is a bug");
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deliver to `sink` every transaction from its cursor up to the low watermark.
+    async fn deliver_commits(
+        &self,
+        sink: &dyn CommitSink,
+        store: &CommitSinkStore,
+    ) -> anyhow::Result<()> {
+        let mut next_seq = store.get_cursor(sink.name())?;
+        // All transactions below the low watermark have been fully committed, so stopping
+        // there guarantees that no transaction is skipped.
+        let low_watermark = self.batch_notifier.low_watermark();
+        // Load the range in batches, so that a sink far behind does not hold it all in memory.
+        while next_seq < low_watermark {
+            let batch_end = std::cmp::min(
+                next_seq.saturating_add(COMMIT_SINK_BATCH_SIZE),
+                low_watermark,
+            );
+            for (seq, digests) in self
+                .database
+                .transactions_in_seq_range(next_seq, batch_end)?
+            {
+                let commit = self.make_committed_transaction(seq, &digests.transaction)?;
+                sink.process_commit(&commit).await?;
+                store.set_cursor(sink.name(), seq + 1)?;
+            }
+            next_seq = batch_end;
+        }
+        Ok(())
+    }
+
     pub fn unixtime_now_ms() -> u64 {
         let ts_ms = Utc::now().timestamp_millis();
         u64::try_from(ts_ms).expect("Travelling in time machine")
@@ -1806,6 +2073,102 @@ impl AuthorityState {
         }
     }
 
+    /// Returns up to `limit` versions of the object starting at version `cursor` (or the first
+    /// known version), with the owner of each version if its data is still available, and the
+    /// transaction that produced it.
+    pub fn get_object_history(
+        &self,
+        object_id: &ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+    ) -> SuiResult<Vec<(ObjectRef, Option<Owner>, TransactionDigest)>> {
+        self.database
+            .get_object_history(*object_id, cursor.unwrap_or(SequenceNumber::MIN), limit)?
+            .into_iter()
+            .map(|(obj_ref, tx_digest)| {
+                let owner = if obj_ref.2.is_alive() {
+                    self.database
+                        .get_object_by_key(&obj_ref.0, obj_ref.1)?
+                        .map(|object| object.owner)
+                } else {
+                    None
+                };
+                Ok((obj_ref, owner, tx_digest))
+            })
+            .collect()
+    }
+
+    /// Returns up to `limit` epochs starting at `cursor`, each with its committee and the first
+    /// and last checkpoints it spans, when the node knows them.
+    pub fn get_epochs(
+        &self,
+        cursor: Option<EpochId>,
+        limit: usize,
+    ) -> SuiResult<
+        Vec<(
+            Committee,
+            Option<CheckpointSequenceNumber>,
+            Option<CheckpointSequenceNumber>,
+        )>,
+    > {
+        let committees = self
+            .committee_store
+            .get_committees(cursor.unwrap_or_default(), limit)?;
+        let checkpoints = self.checkpoints.lock();
+        committees
+            .into_iter()
+            .map(|committee| {
+                let first = match committee.epoch {
+                    0 => Some(0),
+                    epoch => checkpoints
+                        .get_epoch_change_checkpoint_sequence(epoch - 1)?
+                        .map(|seq| seq + 1),
+                };
+                let last = checkpoints.get_epoch_change_checkpoint_sequence(committee.epoch)?;
+                Ok((committee, first, last))
+            })
+            .collect()
+    }
+
+    /// Returns the statistics of up to `limit` finished epochs, starting at epoch `cursor`.
+    pub fn get_epoch_stats(
+        &self,
+        cursor: Option<EpochId>,
+        limit: usize,
+    ) -> SuiResult<Vec<EpochStats>> {
+        let mut stats = self
+            .database
+            .get_epoch_stats(cursor.unwrap_or_default(), limit)?;
+        let checkpoints = self.checkpoints.lock();
+        for stats in &mut stats {
+            stats.transaction_count = checkpoints.get_epoch_transaction_count(stats.epoch)?;
+        }
+        Ok(stats)
+    }
+
+    /// Returns up to `limit` ownership changes matching the query, starting at `cursor`.
+    pub fn get_owner_changes(
+        &self,
+        query: OwnerChangeQuery,
+        cursor: Option<(ObjectID, SequenceNumber)>,
+        limit: usize,
+    ) -> SuiResult<Vec<OwnerChange>> {
+        match query {
+            OwnerChangeQuery::Object(object_id) => self.database.get_owner_history_by_object(
+                object_id,
+                cursor
+                    .map(|(_, version)| version)
+                    .unwrap_or(SequenceNumber::MIN),
+                limit,
+            ),
+            OwnerChangeQuery::Address(address) => self.database.get_owner_history_by_owner(
+                Owner::AddressOwner(address),
+                cursor.unwrap_or((ObjectID::ZERO, SequenceNumber::MIN)),
+                limit,
+            ),
+        }
+    }
+
     pub fn get_owner_objects(&self, owner: Owner) -> SuiResult<Vec<ObjectInfo>> {
         self.database.get_owner_objects(owner)
     }
@@ -1852,6 +2215,7 @@ impl AuthorityState {
         limit: Option<usize>,
         reverse: bool,
     ) -> Result<Vec<TransactionDigest>, anyhow::Error> {
+        let cursor_digest = cursor;
         // Lookup TransactionDigest sequence number,
         // also default cursor to 0 or the current sequence number depends on ordering.
         let cursor = if let Some(cursor) = cursor {
@@ -1884,6 +2248,25 @@ impl AuthorityState {
             TransactionQuery::ToAddress(address) => self
                 .get_indexes()?
                 .get_transactions_to_addr(address, cursor, limit, reverse)?,
+            TransactionQuery::MoveEvent(event_type) => self
+                .get_indexes()?
+                .get_transactions_by_move_event(event_type, cursor, limit, reverse)?,
+            TransactionQuery::Package(package) => self
+                .get_indexes()?
+                .get_transactions_by_package(package, cursor, limit, reverse)?,
+            TransactionQuery::TimeRange {
+                start_time,
+                end_time,
+            } => self.get_indexes()?.get_transactions_in_time_range(
+                start_time,
+                end_time,
+                cursor_digest,
+                limit,
+                reverse,
+            )?,
+            TransactionQuery::Checkpoint(checkpoint) => self
+                .get_indexes()?
+                .get_transactions_by_checkpoint(checkpoint, cursor, limit, reverse)?,
             TransactionQuery::All => {
                 let iter = self.database.perpetual_tables.executed_sequence.iter();
                 if reverse {
@@ -2077,6 +2460,31 @@ impl AuthorityState {
 
         let seq = notifier_ticket.seq();
 
+        // Record the statistics of the ending epoch in the same batch as the epoch change.
+        let epoch_stats = match &certificate.signed_data.data.kind {
+            TransactionKind::Single(SingleTransactionKind::ChangeEpoch(change))
+                if signed_effects.effects.status.is_ok() =>
+            {
+                match inner_temporary_store.get_written_object(&SUI_SYSTEM_STATE_OBJECT_ID) {
+                    Some(next) => {
+                        let move_object = next
+                            .data
+                            .try_as_move()
+                            .expect("Sui System State object must be a Move object");
+                        let next = bcs::from_bytes::<SuiSystemState>(move_object.contents())
+                            .expect("Sui System State object deserialization cannot fail");
+                        Some(
+                            self.database
+                                .get_sui_system_state_object()?
+                                .epoch_stats(&next, change),
+                        )
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+
         let digest = certificate.digest();
         let effects_digest = &signed_effects.digest();
         let seq = self
@@ -2087,11 +2495,13 @@ impl AuthorityState {
                 seq,
                 signed_effects,
                 effects_digest,
+                epoch_stats.as_ref(),
             )
             .await
             .tap_ok(|_| {
                 debug!(?digest, ?effects_digest, ?self.name, "commit_certificate finished");
             })?;
+
         // We only notify i.e. update low watermark once database changes are committed
         notifier_ticket.notify();
         Ok(seq)
@@ -6,6 +6,7 @@ use super::{
     *,
 };
 use crate::authority::authority_store_tables::ExecutionIndicesWithHash;
+use crate::checkpoints::CheckpointStoreTables;
 use arc_swap::ArcSwap;
 use once_cell::sync::OnceCell;
 use rocksdb::Options;
@@ -23,8 +24,9 @@ use sui_storage::{
 };
 use sui_types::batch::TxSequenceNumber;
 use sui_types::crypto::{AuthoritySignInfo, EmptySignInfo};
-use sui_types::object::Owner;
+use sui_types::object::{Owner, OwnerChange};
 use sui_types::storage::{ChildObjectResolver, SingleTxContext, WriteKind};
+use sui_types::sui_system_state::EpochStats;
 use sui_types::{base_types::SequenceNumber, storage::ParentSync};
 use tokio::sync::Notify;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
@@ -607,6 +609,50 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(())
     }
 
+    /// Insert objects restored from a state snapshot into an empty store. Like
+    /// `insert_object_direct`, this only initializes locks for owned objects.
+    pub async fn insert_snapshot_objects(&self, objects: &[Object]) -> SuiResult {
+        let ref_and_objects: Vec<_> = objects
+            .iter()
+            .map(|o| (o.compute_object_reference(), o))
+            .collect();
+
+        self.perpetual_tables
+            .objects
+            .batch()
+            .insert_batch(
+                &self.perpetual_tables.objects,
+                ref_and_objects
+                    .iter()
+                    .map(|(oref, o)| (ObjectKey::from(oref), *o)),
+            )?
+            .insert_batch(
+                &self.perpetual_tables.owner_index,
+                ref_and_objects
+                    .iter()
+                    .filter(|(_, o)| o.get_single_owner().is_some())
+                    .map(|(oref, o)| ((o.owner, oref.0), ObjectInfo::new(oref, o))),
+            )?
+            .insert_batch(
+                &self.perpetual_tables.parent_sync,
+                ref_and_objects
+                    .iter()
+                    .map(|(oref, o)| (oref, o.previous_transaction)),
+            )?
+            .write()?;
+
+        let owned_refs: Vec<_> = ref_and_objects
+            .iter()
+            .filter(|(_, o)| o.get_single_owner().is_some())
+            .map(|(oref, _)| *oref)
+            .collect();
+        self.lock_service
+            .initialize_locks(&owned_refs, false /* is_force_reset */)
+            .await?;
+
+        Ok(())
+    }
+
     /// Acquires the transaction lock for a specific transaction, writing the transaction
     /// to the transaction column family if acquiring the lock succeeds.
     /// The lock service is used to atomically acquire locks.
@@ -647,7 +693,8 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     /// Updates the state resulting from the execution of a certificate.
     ///
     /// Internally it checks that all locks for active inputs are at the correct
-    /// version, and then writes locks, objects, certificates, parents atomically.
+    /// version, and then writes locks, objects, certificates, parents and the statistics of
+    /// the epoch ended by an epoch change atomically.
     pub async fn update_state(
         &self,
         inner_temporary_store: InnerTemporaryStore,
@@ -655,6 +702,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         proposed_seq: TxSequenceNumber,
         effects: &TransactionEffectsEnvelope<S>,
         effects_digest: &TransactionEffectsDigest,
+        epoch_stats: Option<&EpochStats>,
     ) -> SuiResult<TxSequenceNumber> {
         // Extract the new state from the execution
         // TODO: events are already stored in the TxDigest -> TransactionEffects store. Is that enough?
@@ -667,6 +715,12 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             iter::once((transaction_digest, certificate.serializable_ref())),
         )?;
 
+        // Store the statistics of the epoch ended by an epoch change transaction
+        write_batch = write_batch.insert_batch(
+            &self.perpetual_tables.epoch_stats,
+            epoch_stats.map(|stats| (stats.epoch, stats)),
+        )?;
+
         let seq = self
             .sequence_tx(
                 write_batch,
@@ -854,10 +908,51 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                     },
                 ));
 
+        // Record the ownership changes made by this transaction in the owner history.
+        let owner_changes: Vec<_> = written
+            .iter()
+            .filter_map(|(id, (object_ref, new_object, _))| {
+                let from = objects.get(id).map(|old_object| old_object.owner);
+                (from != Some(new_object.owner)).then(|| OwnerChange {
+                    object_id: *id,
+                    version: object_ref.1,
+                    from,
+                    to: Some(new_object.owner),
+                    transaction_digest,
+                })
+            })
+            .chain(deleted.iter().filter_map(|(id, (version, _))| {
+                objects.get(id).map(|old_object| OwnerChange {
+                    object_id: *id,
+                    version: *version,
+                    from: Some(old_object.owner),
+                    to: None,
+                    transaction_digest,
+                })
+            }))
+            .collect();
+
         // Delete the old owner index entries
         write_batch =
             write_batch.delete_batch(&self.perpetual_tables.owner_index, old_object_owners)?;
 
+        write_batch = write_batch.insert_batch(
+            &self.perpetual_tables.owner_history,
+            owner_changes
+                .iter()
+                .map(|change| ((change.object_id, change.version), change)),
+        )?;
+        write_batch = write_batch.insert_batch(
+            &self.perpetual_tables.owner_history_by_owner,
+            owner_changes.iter().flat_map(|change| {
+                change
+                    .from
+                    .iter()
+                    .chain(change.to.iter())
+                    .map(move |owner| ((*owner, change.object_id, change.version), change))
+            }),
+        )?;
+
         // Index the certificate by the objects mutated
         write_batch = write_batch.insert_batch(
             &self.perpetual_tables.parent_sync,
@@ -986,6 +1081,17 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     /// 4. owner_index table change is reverted.
     pub fn revert_state_update(&self, tx_digest: &TransactionDigest) -> SuiResult {
         let effects = self.get_effects(tx_digest)?;
+        self.revert_state_update_batch(tx_digest, &effects)?
+            .write()?;
+        Ok(())
+    }
+
+    /// The batch reverting the state update of `tx_digest` in the perpetual tables.
+    fn revert_state_update_batch(
+        &self,
+        tx_digest: &TransactionDigest,
+        effects: &TransactionEffects,
+    ) -> SuiResult<DBBatch> {
         let mut write_batch = self.perpetual_tables.certificates.batch();
         write_batch =
             write_batch.delete_batch(&self.perpetual_tables.certificates, iter::once(tx_digest))?;
@@ -1038,22 +1144,158 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                         .decrement()
                         .expect("version revert should never fail"),
                 )
-            });
+            })
+            .collect::<Vec<_>>();
         let old_objects = self
             .perpetual_tables
             .objects
-            .multi_get(mutated_objects)?
+            .multi_get(&mutated_objects)?
             .into_iter()
-            .map(|obj_opt| {
-                let obj = obj_opt.expect("Older object version not found");
-                (
+            .zip(&mutated_objects)
+            .map(|(obj_opt, key)| {
+                let obj = obj_opt.ok_or_else(|| {
+                    SuiError::GenericStorageError(format!(
+                        "Cannot revert {tx_digest:?}: older object version {key:?} not found"
+                    ))
+                })?;
+                Ok((
                     (obj.owner, obj.id()),
                     ObjectInfo::new(&obj.compute_object_reference(), &obj),
-                )
-            });
+                ))
+            })
+            .collect::<SuiResult<Vec<_>>>()?;
         write_batch = write_batch.insert_batch(&self.perpetual_tables.owner_index, old_objects)?;
 
-        write_batch.write()?;
+        // Remove the ownership changes recorded for this transaction.
+        let owner_history_keys: Vec<_> = effects
+            .mutated
+            .iter()
+            .chain(effects.created.iter())
+            .chain(effects.unwrapped.iter())
+            .map(|(r, _)| r)
+            .chain(effects.deleted.iter())
+            .chain(effects.wrapped.iter())
+            .map(|(id, version, _)| (*id, *version))
+            .collect();
+        let owner_changes = self
+            .perpetual_tables
+            .owner_history
+            .multi_get(&owner_history_keys)?;
+        write_batch = write_batch.delete_batch(
+            &self.perpetual_tables.owner_history_by_owner,
+            owner_changes.iter().flatten().flat_map(|change| {
+                change
+                    .from
+                    .iter()
+                    .chain(change.to.iter())
+                    .map(move |owner| (*owner, change.object_id, change.version))
+            }),
+        )?;
+        write_batch =
+            write_batch.delete_batch(&self.perpetual_tables.owner_history, owner_history_keys)?;
+
+        Ok(write_batch)
+    }
+
+    /// Rolls back an executed transaction for offline repair: its certificate, effects and
+    /// output objects are removed, the input objects become the latest versions again and their
+    /// locks are re-created. Fails if any output of the transaction has since been consumed by
+    /// a later transaction, or if the transaction touched shared objects, whose versions were
+    /// assigned by consensus and can't be handed out again. The transaction is also removed from
+    /// `checkpoints` if given.
+    ///
+    /// The lock service and the checkpoint store are separate databases, so they are reverted
+    /// first, and the perpetual tables last in a single batch: if the revert stops half way,
+    /// running it again completes it.
+    pub async fn revert_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+        checkpoints: Option<&CheckpointStoreTables>,
+    ) -> SuiResult {
+        let effects = self.get_effects(tx_digest)?;
+        if !effects.shared_objects.is_empty() {
+            return Err(SuiError::GenericStorageError(format!(
+                "Transaction {tx_digest:?} uses shared objects and cannot be reverted"
+            )));
+        }
+
+        let outputs: Vec<ObjectRef> = effects
+            .mutated
+            .iter()
+            .chain(effects.created.iter())
+            .chain(effects.unwrapped.iter())
+            .map(|(r, _)| *r)
+            .collect();
+        for object_ref in outputs
+            .iter()
+            .chain(effects.deleted.iter())
+            .chain(effects.wrapped.iter())
+        {
+            if let Some((latest, digest)) = self.get_latest_parent_entry(object_ref.0)? {
+                if latest != *object_ref {
+                    return Err(SuiError::GenericStorageError(format!(
+                        "Object {:?} was modified by later transaction {digest:?}, revert it first",
+                        object_ref.0
+                    )));
+                }
+            }
+        }
+
+        let input_keys: Vec<_> = effects
+            .mutated
+            .iter()
+            .map(|(r, _)| r)
+            .chain(effects.deleted.iter())
+            .chain(effects.wrapped.iter())
+            .map(|(id, version, _)| {
+                ObjectKey(
+                    *id,
+                    version
+                        .decrement()
+                        .expect("version revert should never fail"),
+                )
+            })
+            .collect();
+        let inputs: Vec<ObjectRef> = self
+            .perpetual_tables
+            .objects
+            .multi_get(&input_keys)?
+            .into_iter()
+            .flatten()
+            .filter(|obj| obj.is_owned_or_quasi_shared())
+            .map(|obj| obj.compute_object_reference())
+            .collect();
+        let seq = match self.get_tx_sequence(*tx_digest).await? {
+            Some(seq) => Some(seq),
+            // A previous attempt may have stopped after reverting the lock service.
+            None => self
+                .perpetual_tables
+                .executed_sequence
+                .iter()
+                .skip_to_last()
+                .reverse()
+                .find(|(_, digests)| digests.transaction == *tx_digest)
+                .map(|(seq, _)| seq),
+        };
+
+        self.lock_service
+            .revert_transaction(*tx_digest, inputs, outputs)
+            .await?;
+        if let Some(checkpoints) = checkpoints {
+            let digests = ExecutionDigests::new(*tx_digest, effects.digest());
+            checkpoints
+                .transactions_to_checkpoint
+                .batch()
+                .delete_batch(&checkpoints.transactions_to_checkpoint, [digests])?
+                .delete_batch(&checkpoints.extra_transactions, [digests])?
+                .write()?;
+        }
+
+        // Batches that were already signed keep listing the transaction; only new followers
+        // stop seeing it.
+        self.revert_state_update_batch(tx_digest, &effects)?
+            .delete_batch(&self.perpetual_tables.executed_sequence, seq)?
+            .write()?;
         Ok(())
     }
 
@@ -1075,6 +1317,76 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         self.perpetual_tables.get_latest_parent_entry(object_id)
     }
 
+    /// Returns the entries of the parents_sync index for this object in increasing version order,
+    /// starting at version `cursor`. Each entry links a version of the object to the transaction
+    /// that produced it, including the entries recording deletion or wrapping.
+    pub fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: SequenceNumber,
+        limit: usize,
+    ) -> Result<Vec<(ObjectRef, TransactionDigest)>, SuiError> {
+        Ok(self
+            .perpetual_tables
+            .parent_sync
+            .iter()
+            .skip_to(&(object_id, cursor, ObjectDigest::MIN))?
+            .take_while(|((id, _, _), _)| *id == object_id)
+            .take(limit)
+            .collect())
+    }
+
+    /// Returns the ownership changes of an object in increasing version order, starting at
+    /// version `cursor`.
+    pub fn get_owner_history_by_object(
+        &self,
+        object_id: ObjectID,
+        cursor: SequenceNumber,
+        limit: usize,
+    ) -> SuiResult<Vec<OwnerChange>> {
+        Ok(self
+            .perpetual_tables
+            .owner_history
+            .iter()
+            .skip_to(&(object_id, cursor))?
+            .take_while(|((id, _), _)| *id == object_id)
+            .take(limit)
+            .map(|(_, change)| change)
+            .collect())
+    }
+
+    /// Returns the ownership changes in which `owner` was either the previous or the new owner,
+    /// ordered by object ID and version, starting at `cursor`.
+    pub fn get_owner_history_by_owner(
+        &self,
+        owner: Owner,
+        cursor: (ObjectID, SequenceNumber),
+        limit: usize,
+    ) -> SuiResult<Vec<OwnerChange>> {
+        Ok(self
+            .perpetual_tables
+            .owner_history_by_owner
+            .iter()
+            .skip_to(&(owner, cursor.0, cursor.1))?
+            .take_while(|((o, _, _), _)| *o == owner)
+            .take(limit)
+            .map(|(_, change)| change)
+            .collect())
+    }
+
+    /// Returns the statistics of finished epochs in increasing epoch order, starting at epoch
+    /// `cursor`.
+    pub fn get_epoch_stats(&self, cursor: EpochId, limit: usize) -> SuiResult<Vec<EpochStats>> {
+        Ok(self
+            .perpetual_tables
+            .epoch_stats
+            .iter()
+            .skip_to(&cursor)?
+            .take(limit)
+            .map(|(_, stats)| stats)
+            .collect())
+    }
+
     /// Remove the shared objects locks.
     pub fn remove_shared_objects_locks(
         &self,
@@ -13,6 +13,8 @@ use sui_storage::default_db_options;
 use sui_types::base_types::{ExecutionDigests, SequenceNumber};
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
 use sui_types::messages::{TrustedCertificate, TrustedTransactionEnvelope};
+use sui_types::object::OwnerChange;
+use sui_types::sui_system_state::EpochStats;
 use typed_store::rocks::{DBMap, DBOptions};
 use typed_store::traits::TypedStoreDebug;
 
@@ -111,6 +113,15 @@ pub struct AuthorityPerpetualTables<S> {
     /// by a specific user, and their object reference.
     pub(crate) owner_index: DBMap<(Owner, ObjectID), ObjectInfo>,
 
+    /// A history of all ownership changes of objects, indexed by the object ID and the version
+    /// of the object written (or deleted) by the change. Unlike `owner_index`, entries are never
+    /// removed when ownership changes again.
+    pub(crate) owner_history: DBMap<(ObjectID, SequenceNumber), OwnerChange>,
+
+    /// The entries of `owner_history` indexed by each of the owners involved in the change, i.e.
+    /// both the owner before and the owner after the change.
+    pub(crate) owner_history_by_owner: DBMap<(Owner, ObjectID, SequenceNumber), OwnerChange>,
+
     /// This is a map between the transaction digest and the corresponding certificate for all
     /// certificates that have been successfully processed by this authority. This set of certificates
     /// along with the genesis allows the reconstruction of all other state, and a full sync to this
@@ -151,6 +162,10 @@ pub struct AuthorityPerpetualTables<S> {
 
     /// A sequence of batches indexing into the sequence of executed transactions.
     pub batches: DBMap<TxSequenceNumber, SignedBatch>,
+
+    /// Gas and validator reward statistics of each finished epoch, recorded when the epoch
+    /// change transaction of the epoch is committed.
+    pub(crate) epoch_stats: DBMap<EpochId, EpochStats>,
 }
 
 impl<S> AuthorityPerpetualTables<S>
@@ -251,6 +266,168 @@ where
             .next()
             .is_none())
     }
+
+    pub fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        Ok(self.objects.get(&ObjectKey(*object_id, version))?)
+    }
+
+    /// The versions of `object_id` held in the objects table, in increasing order.
+    pub fn get_object_versions(&self, object_id: &ObjectID) -> SuiResult<Vec<SequenceNumber>> {
+        Ok(self
+            .objects
+            .iter()
+            .skip_to(&ObjectKey(*object_id, SequenceNumber::MIN))?
+            .take_while(|(ObjectKey(id, _), _)| id == object_id)
+            .map(|(ObjectKey(_, version), _)| version)
+            .collect())
+    }
+
+    pub fn get_certificate(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TrustedCertificate>> {
+        Ok(self.certificates.get(digest)?)
+    }
+
+    pub fn get_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionEffectsEnvelope<S>>> {
+        Ok(self.effects.get(digest)?)
+    }
+
+    /// Return the `parent_sync` entries of all versions of an object, oldest first.
+    pub fn get_parent_entries(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiResult<Vec<(ObjectRef, TransactionDigest)>> {
+        Ok(self
+            .parent_sync
+            .iter()
+            .skip_to(&(object_id, SequenceNumber::MIN, ObjectDigest::MIN))?
+            .take_while(|((id, _, _), _)| *id == object_id)
+            .collect())
+    }
+
+    /// Cross-check the invariants between the tables, returning every violation found. This
+    /// reads every table in full, so it is meant to be run offline.
+    pub fn check_consistency(&self) -> SuiResult<Vec<PerpetualTablesInconsistency>> {
+        let mut issues = Vec::new();
+
+        for ((owner, object_id), info) in self.owner_index.iter() {
+            let object_ref = (info.object_id, info.version, info.digest);
+            let latest = self.get_latest_parent_entry(object_id)?.map(|(r, _)| r);
+            if latest != Some(object_ref) || !object_ref.2.is_alive() {
+                issues.push(PerpetualTablesInconsistency::OwnerIndexNotLatest {
+                    owner,
+                    object_ref,
+                    latest,
+                });
+            } else if !self
+                .objects
+                .contains_key(&ObjectKey(object_id, info.version))?
+            {
+                issues.push(PerpetualTablesInconsistency::OwnerIndexMissingObject {
+                    owner,
+                    object_ref,
+                });
+            }
+        }
+
+        for (digest, _) in self.certificates.iter() {
+            if !self.effects.contains_key(&digest)? {
+                issues.push(PerpetualTablesInconsistency::CertificateWithoutEffects(
+                    digest,
+                ));
+            }
+        }
+        for (digest, _) in self.effects.iter() {
+            if !self.certificates.contains_key(&digest)? {
+                issues.push(PerpetualTablesInconsistency::EffectsWithoutCertificate(
+                    digest,
+                ));
+            }
+        }
+
+        for (object_ref, _) in self.parent_sync.iter() {
+            if object_ref.2.is_alive()
+                && !self
+                    .objects
+                    .contains_key(&ObjectKey(object_ref.0, object_ref.1))?
+            {
+                issues.push(PerpetualTablesInconsistency::ParentSyncMissingObject(
+                    object_ref,
+                ));
+            }
+        }
+        for (_, object) in self.objects.iter() {
+            let object_ref = object.compute_object_reference();
+            if !self.parent_sync.contains_key(&object_ref)? {
+                issues.push(PerpetualTablesInconsistency::ObjectMissingParentSync(
+                    object_ref,
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// An invariant violation found by `AuthorityPerpetualTables::check_consistency`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PerpetualTablesInconsistency {
+    /// An `owner_index` entry does not point at the latest, live version of its object.
+    OwnerIndexNotLatest {
+        owner: Owner,
+        object_ref: ObjectRef,
+        latest: Option<ObjectRef>,
+    },
+    /// An `owner_index` entry points at a version missing from `objects`.
+    OwnerIndexMissingObject {
+        owner: Owner,
+        object_ref: ObjectRef,
+    },
+    CertificateWithoutEffects(TransactionDigest),
+    EffectsWithoutCertificate(TransactionDigest),
+    /// A live `parent_sync` entry has no matching version in `objects`.
+    ParentSyncMissingObject(ObjectRef),
+    /// An object version has no `parent_sync` entry with its digest.
+    ObjectMissingParentSync(ObjectRef),
+}
+
+impl std::fmt::Display for PerpetualTablesInconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OwnerIndexNotLatest {
+                owner,
+                object_ref,
+                latest,
+            } => write!(
+                f,
+                "owner_index entry of {owner} points at {object_ref:?}, but the latest parent_sync entry is {latest:?}"
+            ),
+            Self::OwnerIndexMissingObject { owner, object_ref } => write!(
+                f,
+                "owner_index entry of {owner} points at {object_ref:?}, which is missing from objects"
+            ),
+            Self::CertificateWithoutEffects(digest) => {
+                write!(f, "certificate {digest:?} has no effects")
+            }
+            Self::EffectsWithoutCertificate(digest) => {
+                write!(f, "effects of {digest:?} have no certificate")
+            }
+            Self::ParentSyncMissingObject(object_ref) => {
+                write!(f, "parent_sync entry {object_ref:?} is missing from objects")
+            }
+            Self::ObjectMissingParentSync(object_ref) => {
+                write!(f, "object {object_ref:?} has no parent_sync entry")
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
@@ -59,8 +59,11 @@ use gossip::{gossip_process, GossipMetrics};
 pub mod checkpoint_driver;
 use crate::authority_active::checkpoint_driver::CheckpointMetrics;
 use crate::authority_client::NetworkAuthorityClientMetrics;
+use crate::checkpoint_archive::{archive_sync_process, CheckpointArchive};
 use crate::epoch::reconfiguration::Reconfigurable;
-use checkpoint_driver::{checkpoint_process, get_latest_checkpoint_from_all, sync_to_checkpoint};
+use checkpoint_driver::{
+    checkpoint_process, checkpoint_sync_process, get_latest_checkpoint_from_all, sync_to_checkpoint,
+};
 
 pub mod execution_driver;
 
@@ -414,4 +417,25 @@ where
             metrics
         ))
     }
+
+    /// Start syncing certified checkpoints and executing their transactions, as an
+    /// alternative to the node sync process for fullnodes.
+    pub async fn spawn_checkpoint_sync_process(self: Arc<Self>) -> JoinHandle<()> {
+        let checkpoint_process_control = CheckpointProcessControl::default();
+        spawn_monitored_task!(checkpoint_sync_process(self, &checkpoint_process_control))
+    }
+
+    /// Start replaying the checkpoints of a local checkpoint archive, as an alternative to
+    /// syncing from validators for fullnodes.
+    pub async fn spawn_archive_sync_process(
+        self: Arc<Self>,
+        archive: CheckpointArchive,
+    ) -> JoinHandle<()> {
+        let checkpoint_process_control = CheckpointProcessControl::default();
+        spawn_monitored_task!(archive_sync_process(
+            self,
+            archive,
+            &checkpoint_process_control
+        ))
+    }
 }
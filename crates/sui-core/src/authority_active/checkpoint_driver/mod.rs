@@ -725,6 +725,68 @@ where
     Ok(())
 }
 
+/// Keep a fullnode in sync by downloading certified checkpoints from validators and executing
+/// their transactions in checkpoint order. Unlike the node sync process, this relies on the
+/// checkpoint certificates rather than on a majority of validators streaming the same effects.
+/// Checkpoints are verified against the committee of their epoch, which is taken from the
+/// checkpoint ending the previous epoch.
+pub async fn checkpoint_sync_process<A>(
+    active_authority: Arc<ActiveAuthority<A>>,
+    timing: &CheckpointProcessControl,
+) where
+    A: AuthorityAPI + Send + Sync + 'static + Clone + Reconfigurable,
+{
+    info!("Start checkpoint sync process.");
+    let checkpoint_db = active_authority.state.checkpoints.clone();
+
+    loop {
+        // Move to the next committee once the checkpoint ending the epoch is stored. This is
+        // checked on every iteration so that a failed attempt, or a restart, is resumed.
+        let latest = checkpoint_db.lock().latest_stored_checkpoint();
+        if let Some(AuthenticatedCheckpoint::Certified(latest)) = latest {
+            if latest.summary.next_epoch_committee.is_some()
+                && latest.summary.epoch == active_authority.net.load().committee.epoch
+            {
+                if let Err(err) = active_authority.reconfigure_from_checkpoint(&latest).await {
+                    error!(
+                        epoch = latest.summary.epoch,
+                        "Failed to move to the committee of the next epoch: {:?}", err
+                    );
+                    tokio::time::sleep(timing.delay_on_local_failure).await;
+                    continue;
+                }
+            }
+        }
+
+        let net = active_authority.net.load().clone();
+        let next_cp_seq = checkpoint_db.lock().next_checkpoint();
+        let authorities: BTreeSet<_> = net.committee.names().copied().collect();
+        let (checkpoint, contents) =
+            match get_one_checkpoint_with_contents(net.clone(), next_cp_seq, &authorities).await {
+                Ok(checkpoint) => checkpoint,
+                Err(err) => {
+                    // Most likely the checkpoint has not been certified yet.
+                    debug!(?next_cp_seq, "Cannot get the next checkpoint: {:?}", err);
+                    tokio::time::sleep(timing.delay_on_local_failure).await;
+                    continue;
+                }
+            };
+
+        if let Err(err) = process_new_checkpoint_certificate(
+            active_authority.clone(),
+            &checkpoint_db,
+            &net.committee,
+            &checkpoint,
+            &contents,
+        )
+        .await
+        {
+            warn!(?next_cp_seq, "Failed to sync checkpoint: {:?}", err);
+            tokio::time::sleep(timing.delay_on_local_failure).await;
+        }
+    }
+}
+
 async fn process_new_checkpoint_certificate<A>(
     active_authority: Arc<ActiveAuthority<A>>,
     checkpoint_db: &Arc<Mutex<CheckpointStore>>,
@@ -0,0 +1,567 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A checkpoint archive is a durable, replayable copy of the certified checkpoint history,
+//! kept outside of the node's databases.
+//!
+//! Every certified checkpoint is written as one immutable file, named after its zero-padded
+//! sequence number, holding the BCS encoded checkpoint certificate, its contents and the
+//! certificates and effects of all transactions it includes. Files are written to a temporary
+//! name and renamed into place, so a reader never sees a partial checkpoint. The directory can
+//! be synced as-is to an object store.
+//!
+//! Archiving resumes after the latest archived checkpoint, and an empty archive starts at the
+//! oldest checkpoint whose transactions the node holds: genesis, or the checkpoint after the one
+//! a node was restored at from a state snapshot. If an existing archive ends before that
+//! checkpoint, the missing checkpoints cannot be archived by this node: a warning is logged and
+//! the archive is left with a gap, which readers syncing from it will stop at.
+//!
+//! A fullnode can sync from an archive instead of from validators: each checkpoint is verified
+//! against the committee and its transactions are executed in order. The checkpoint ending an
+//! epoch carries the committee of the next one, which is used to verify the checkpoints that
+//! follow.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, ensure};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sui_types::committee::Committee;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages::{CertifiedTransaction, SignedTransactionEffects, VerifiedCertificate};
+use sui_types::messages_checkpoint::{
+    AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents,
+    CheckpointSequenceNumber,
+};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
+use typed_store::Map;
+
+use crate::authority::AuthorityState;
+use crate::authority_active::checkpoint_driver::CheckpointProcessControl;
+use crate::authority_active::ActiveAuthority;
+use crate::authority_client::AuthorityAPI;
+use crate::checkpoints::CheckpointStore;
+use crate::epoch::reconfiguration::Reconfigurable;
+
+pub const CHECKPOINT_ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+const CHECKPOINT_FILE_EXTENSION: &str = "chk";
+const ARCHIVE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchivedCheckpoint {
+    pub format_version: u32,
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    /// The certificates and effects of the transactions of `contents`, in the same order.
+    pub transactions: Vec<(CertifiedTransaction, SignedTransactionEffects)>,
+}
+
+impl ArchivedCheckpoint {
+    pub fn sequence_number(&self) -> CheckpointSequenceNumber {
+        *self.checkpoint.summary.sequence_number()
+    }
+
+    /// Verify the checkpoint certificate against `committee`, and check that the transactions
+    /// are exactly the ones the checkpoint commits to. Returns the verified certificates.
+    pub fn verify(
+        &self,
+        committee: &Committee,
+    ) -> anyhow::Result<Vec<(VerifiedCertificate, SignedTransactionEffects)>> {
+        ensure!(
+            self.format_version == CHECKPOINT_ARCHIVE_FORMAT_VERSION,
+            "Unsupported checkpoint archive format version {}, expected {}",
+            self.format_version,
+            CHECKPOINT_ARCHIVE_FORMAT_VERSION
+        );
+        self.checkpoint.verify(committee, Some(&self.contents))?;
+        ensure!(
+            self.transactions.len() == self.contents.size(),
+            "Checkpoint {} has {} transactions, but its contents list {}",
+            self.sequence_number(),
+            self.transactions.len(),
+            self.contents.size()
+        );
+
+        self.transactions
+            .iter()
+            .zip(self.contents.iter())
+            .map(|((certificate, effects), digests)| {
+                ensure!(
+                    *certificate.digest() == digests.transaction
+                        && effects.effects.transaction_digest == digests.transaction
+                        && *effects.digest() == digests.effects,
+                    "Transaction {:?} does not match the contents of checkpoint {}",
+                    digests.transaction,
+                    self.sequence_number()
+                );
+                Ok((certificate.clone().verify(committee)?, effects.clone()))
+            })
+            .collect()
+    }
+}
+
+/// A directory of archived checkpoints.
+#[derive(Clone)]
+pub struct CheckpointArchive {
+    dir: PathBuf,
+}
+
+impl CheckpointArchive {
+    pub fn new(dir: &Path) -> anyhow::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self { dir: dir.into() })
+    }
+
+    fn file_path(&self, seq: CheckpointSequenceNumber) -> PathBuf {
+        self.dir
+            .join(format!("{:020}.{}", seq, CHECKPOINT_FILE_EXTENSION))
+    }
+
+    /// The sequence number of the latest archived checkpoint, if any.
+    pub fn latest_sequence_number(&self) -> anyhow::Result<Option<CheckpointSequenceNumber>> {
+        let mut latest = None;
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(CHECKPOINT_FILE_EXTENSION) {
+                continue;
+            }
+            if let Some(seq) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.parse::<CheckpointSequenceNumber>().ok())
+            {
+                latest = latest.max(Some(seq));
+            }
+        }
+        Ok(latest)
+    }
+
+    pub fn read(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<Option<ArchivedCheckpoint>> {
+        let path = self.file_path(seq);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = fs::read(&path)?;
+        let checkpoint: ArchivedCheckpoint = bcs::from_bytes(&bytes)
+            .map_err(|e| anyhow!("Cannot decode archived checkpoint {:?}: {e}", path))?;
+        ensure!(
+            checkpoint.sequence_number() == seq,
+            "Archive file {:?} holds checkpoint {}",
+            path,
+            checkpoint.sequence_number()
+        );
+        Ok(Some(checkpoint))
+    }
+
+    /// Write `checkpoint` to the archive. Archived checkpoints are immutable, so this is a
+    /// no-op if the checkpoint was already written.
+    pub fn write(&self, checkpoint: &ArchivedCheckpoint) -> anyhow::Result<()> {
+        let path = self.file_path(checkpoint.sequence_number());
+        if path.exists() {
+            return Ok(());
+        }
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&bcs::to_bytes(checkpoint)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
+
+/// Build the archive entry of certified checkpoint `seq` from the local stores. Returns None
+/// if the checkpoint is not certified yet, or if some of its transactions are not executed
+/// locally yet.
+fn make_archived_checkpoint(
+    state: &AuthorityState,
+    seq: CheckpointSequenceNumber,
+) -> anyhow::Result<Option<ArchivedCheckpoint>> {
+    let (checkpoint, contents) = {
+        let checkpoints = state.checkpoints.lock();
+        let checkpoint = match checkpoints.get_checkpoint(seq)? {
+            Some(AuthenticatedCheckpoint::Certified(checkpoint)) => checkpoint,
+            _ => return Ok(None),
+        };
+        match checkpoints.tables.checkpoint_contents.get(&seq)? {
+            Some(contents) => (checkpoint, contents),
+            None => return Ok(None),
+        }
+    };
+
+    let mut transactions = Vec::with_capacity(contents.size());
+    for digests in contents.iter() {
+        let certificate = state
+            .database
+            .get_certified_transaction(&digests.transaction)?;
+        let effects = state
+            .database
+            .perpetual_tables
+            .effects
+            .get(&digests.transaction)?;
+        match (certificate, effects) {
+            (Some(certificate), Some(effects)) => {
+                transactions.push((certificate.into_inner(), effects))
+            }
+            _ => {
+                info!(?seq, tx = ?digests.transaction, "Checkpoint transaction not executed yet");
+                return Ok(None);
+            }
+        }
+    }
+
+    Ok(Some(ArchivedCheckpoint {
+        format_version: CHECKPOINT_ARCHIVE_FORMAT_VERSION,
+        checkpoint,
+        contents,
+        transactions,
+    }))
+}
+
+/// The first checkpoint to archive: the one after the latest archived checkpoint, or the oldest
+/// checkpoint whose transactions are held locally if the archive is empty or ends before it.
+fn first_checkpoint_to_archive(
+    state: &AuthorityState,
+    archive: &CheckpointArchive,
+) -> anyhow::Result<CheckpointSequenceNumber> {
+    let oldest_available = match state.checkpoints.lock().tables.checkpoints.iter().next() {
+        // A checkpoint store that doesn't start at genesis was restored from a state snapshot,
+        // and the snapshot checkpoint comes without its transactions.
+        Some((seq, _)) if seq > 0 => seq + 1,
+        _ => 0,
+    };
+    match archive.latest_sequence_number()? {
+        Some(seq) if seq + 1 >= oldest_available => Ok(seq + 1),
+        Some(seq) => {
+            warn!(
+                archived = ?seq,
+                ?oldest_available,
+                "Checkpoints after the archive are not available locally, the archive will have a gap"
+            );
+            Ok(oldest_available)
+        }
+        None => Ok(oldest_available),
+    }
+}
+
+/// Archive every checkpoint from `next_seq` on that is available locally, advancing `next_seq`
+/// past each archived checkpoint.
+fn archive_available_checkpoints(
+    state: &AuthorityState,
+    archive: &CheckpointArchive,
+    next_seq: &mut CheckpointSequenceNumber,
+) -> anyhow::Result<()> {
+    while let Some(checkpoint) = make_archived_checkpoint(state, *next_seq)? {
+        archive.write(&checkpoint)?;
+        info!(seq = ?next_seq, "Archived checkpoint");
+        *next_seq += 1;
+    }
+    Ok(())
+}
+
+/// Append every certified checkpoint to `archive`, in sequence order, as soon as it and all of
+/// its transactions are available locally. Errors are logged and retried, so the process only
+/// ends when the node stops producing checkpoints.
+pub async fn run_checkpoint_archive_process(
+    state: Arc<AuthorityState>,
+    archive: CheckpointArchive,
+) -> anyhow::Result<()> {
+    let mut subscriber = state.checkpoints.lock().subscribe_to_checkpoints();
+    let mut next_seq = loop {
+        // Archiving reads the stores and writes files, so it runs off the async runtime.
+        let (task_state, task_archive) = (state.clone(), archive.clone());
+        match tokio::task::spawn_blocking(move || {
+            first_checkpoint_to_archive(&task_state, &task_archive)
+        })
+        .await?
+        {
+            Ok(seq) => break seq,
+            Err(err) => {
+                error!("Cannot find the first checkpoint to archive: {:?}", err);
+                tokio::time::sleep(ARCHIVE_POLL_INTERVAL).await;
+            }
+        }
+    };
+    info!(?next_seq, "Starting checkpoint archive process");
+
+    loop {
+        let (task_state, task_archive) = (state.clone(), archive.clone());
+        let (archived_up_to, result) = tokio::task::spawn_blocking(move || {
+            let result = archive_available_checkpoints(&task_state, &task_archive, &mut next_seq);
+            (next_seq, result)
+        })
+        .await?;
+        next_seq = archived_up_to;
+        if let Err(err) = result {
+            error!(seq = ?next_seq, "Failed to archive checkpoint: {:?}", err);
+        }
+
+        // Transactions of a certified checkpoint may still be executing, so poll as well as
+        // waiting for new checkpoints. This also paces the retries after an error.
+        match tokio::time::timeout(ARCHIVE_POLL_INTERVAL, subscriber.recv()).await {
+            Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) | Err(_) => (),
+            Ok(Err(RecvError::Closed)) => {
+                warn!("Checkpoint notification channel closed, stopping the archive process");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Keep a fullnode in sync by replaying the checkpoints of `archive` instead of downloading
+/// them from validators.
+pub async fn archive_sync_process<A>(
+    active_authority: Arc<ActiveAuthority<A>>,
+    archive: CheckpointArchive,
+    timing: &CheckpointProcessControl,
+) where
+    A: AuthorityAPI + Send + Sync + 'static + Clone + Reconfigurable,
+{
+    info!("Start archive sync process.");
+    let checkpoint_db = active_authority.state.checkpoints.clone();
+
+    loop {
+        // Move to the next committee once the checkpoint ending the epoch is stored. This is
+        // checked on every iteration so that a failed attempt, or a restart, is resumed.
+        let latest = checkpoint_db.lock().latest_stored_checkpoint();
+        if let Some(AuthenticatedCheckpoint::Certified(latest)) = latest {
+            if latest.summary.next_epoch_committee.is_some()
+                && latest.summary.epoch == active_authority.net.load().committee.epoch
+            {
+                if let Err(err) = active_authority.reconfigure_from_checkpoint(&latest).await {
+                    error!(
+                        epoch = latest.summary.epoch,
+                        "Failed to move to the committee of the next epoch: {:?}", err
+                    );
+                    tokio::time::sleep(timing.delay_on_local_failure).await;
+                    continue;
+                }
+            }
+        }
+
+        let next_cp_seq = checkpoint_db.lock().next_checkpoint();
+        let reader = archive.clone();
+        let checkpoint = match tokio::task::spawn_blocking(move || reader.read(next_cp_seq))
+            .await
+            .unwrap_or_else(|e| Err(e.into()))
+        {
+            Ok(Some(checkpoint)) => checkpoint,
+            Ok(None) => {
+                tokio::time::sleep(timing.delay_on_local_failure).await;
+                continue;
+            }
+            Err(err) => {
+                warn!(?next_cp_seq, "Cannot read archived checkpoint: {:?}", err);
+                tokio::time::sleep(timing.delay_on_local_failure).await;
+                continue;
+            }
+        };
+
+        let committee = active_authority.net.load().committee.clone();
+        if let Err(err) = sync_archived_checkpoint(
+            &active_authority.state,
+            &checkpoint_db,
+            &committee,
+            &checkpoint,
+        )
+        .await
+        {
+            warn!(
+                ?next_cp_seq,
+                "Failed to sync archived checkpoint: {:?}", err
+            );
+            tokio::time::sleep(timing.delay_on_local_failure).await;
+        }
+    }
+}
+
+/// Execute the transactions of an archived checkpoint and store the checkpoint.
+pub async fn sync_archived_checkpoint(
+    state: &AuthorityState,
+    checkpoint_db: &Arc<Mutex<CheckpointStore>>,
+    committee: &Committee,
+    checkpoint: &ArchivedCheckpoint,
+) -> SuiResult {
+    let mut pending = checkpoint
+        .verify(committee)
+        .map_err(|e| SuiError::CheckpointingError {
+            error: e.to_string(),
+        })?;
+
+    // Transactions are listed in checkpoint order, which need not be causal order, so execute
+    // in rounds until every transaction whose dependencies are met has been executed.
+    while !pending.is_empty() {
+        let mut remaining = Vec::new();
+        let mut last_error = None;
+        for (certificate, effects) in pending.iter() {
+            if state.database.effects_exists(certificate.digest())? {
+                continue;
+            }
+            if let Err(e) = state
+                .handle_certificate_with_effects(certificate, effects)
+                .await
+            {
+                last_error = Some(e);
+                remaining.push((certificate.clone(), effects.clone()));
+            }
+        }
+        if remaining.len() == pending.len() {
+            return Err(last_error.expect("No progress implies an error"));
+        }
+        pending = remaining;
+    }
+
+    checkpoint_db.lock().process_synced_checkpoint_certificate(
+        &checkpoint.checkpoint,
+        &checkpoint.contents,
+        committee,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use sui_types::base_types::ExecutionDigests;
+    use sui_types::crypto::{
+        get_key_pair, AuthorityKeyPair, AuthorityPublicKeyBytes, KeypairTraits,
+    };
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::SignedCheckpointSummary;
+
+    use crate::authority_aggregator::authority_aggregator_tests::init_local_authorities;
+
+    #[test]
+    fn test_archive_write_and_read() {
+        let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+        let name: AuthorityPublicKeyBytes = key.public().into();
+        let committee = Committee::new(0, BTreeMap::from([(name, 1)])).unwrap();
+
+        let contents = CheckpointContents::new_with_causally_ordered_transactions(
+            Vec::<ExecutionDigests>::new().into_iter(),
+        );
+        let signed = SignedCheckpointSummary::new(
+            0,
+            0,
+            name,
+            &key,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            None,
+        );
+        let checkpoint = CertifiedCheckpointSummary::aggregate(vec![signed], &committee).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = CheckpointArchive::new(dir.path()).unwrap();
+        assert_eq!(archive.latest_sequence_number().unwrap(), None);
+        assert!(archive.read(0).unwrap().is_none());
+
+        let archived = ArchivedCheckpoint {
+            format_version: CHECKPOINT_ARCHIVE_FORMAT_VERSION,
+            checkpoint,
+            contents,
+            transactions: vec![],
+        };
+        archive.write(&archived).unwrap();
+        assert_eq!(archive.latest_sequence_number().unwrap(), Some(0));
+
+        let read = archive.read(0).unwrap().unwrap();
+        assert_eq!(read.sequence_number(), 0);
+        assert!(read.verify(&committee).unwrap().is_empty());
+
+        // A checkpoint claiming transactions that are not in its contents is rejected.
+        let mut tampered = read;
+        tampered.contents = CheckpointContents::new_with_causally_ordered_transactions(
+            [ExecutionDigests::random()].into_iter(),
+        );
+        assert!(tampered.verify(&committee).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_archive_sync_across_epoch_change() {
+        let (net, states, _) = init_local_authorities(1, vec![]).await;
+        let state = states[0].clone();
+        let genesis_committee = state.committee_store().get_latest_committee();
+        assert_eq!(genesis_committee.epoch, 0);
+
+        // The next epoch is run by a new validator.
+        let (_, next_key): (_, AuthorityKeyPair) = get_key_pair();
+        let next_name: AuthorityPublicKeyBytes = next_key.public().into();
+        let next_committee = Committee::new(1, BTreeMap::from([(next_name, 1)])).unwrap();
+
+        let contents = CheckpointContents::new_with_causally_ordered_transactions(
+            Vec::<ExecutionDigests>::new().into_iter(),
+        );
+        // The last checkpoint of epoch 0 carries the committee of epoch 1.
+        let signed = SignedCheckpointSummary::new(
+            0,
+            0,
+            state.name,
+            &*state.secret,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            Some(next_committee.clone()),
+        );
+        let epoch_change =
+            CertifiedCheckpointSummary::aggregate(vec![signed], &genesis_committee).unwrap();
+        // The first checkpoint of epoch 1 is only valid under the new committee.
+        let signed = SignedCheckpointSummary::new(
+            1,
+            1,
+            next_name,
+            &next_key,
+            &contents,
+            Some(epoch_change.summary.digest()),
+            GasCostSummary::default(),
+            None,
+        );
+        let next_epoch =
+            CertifiedCheckpointSummary::aggregate(vec![signed], &next_committee).unwrap();
+        assert!(next_epoch.verify(&genesis_committee, None).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = CheckpointArchive::new(dir.path()).unwrap();
+        for checkpoint in [epoch_change, next_epoch] {
+            archive
+                .write(&ArchivedCheckpoint {
+                    format_version: CHECKPOINT_ARCHIVE_FORMAT_VERSION,
+                    checkpoint,
+                    contents: contents.clone(),
+                    transactions: vec![],
+                })
+                .unwrap();
+        }
+
+        let active = Arc::new(
+            ActiveAuthority::new_with_ephemeral_storage_for_test(state.clone(), net).unwrap(),
+        );
+        let handle = active.clone().spawn_archive_sync_process(archive).await;
+
+        tokio::time::timeout(Duration::from_secs(30), async {
+            while state.checkpoints.lock().next_checkpoint() < 2 {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("Both archived checkpoints should be synced");
+        handle.abort();
+
+        assert_eq!(state.epoch(), 1);
+        assert_eq!(active.net.load().committee, next_committee);
+        assert_eq!(state.committee_store().get_latest_committee().epoch, 1);
+        assert!(matches!(
+            state.checkpoints.lock().latest_stored_checkpoint(),
+            Some(AuthenticatedCheckpoint::Certified(c)) if *c.summary.sequence_number() == 1
+        ));
+    }
+}
@@ -8,7 +8,6 @@ pub mod reconstruction;
 #[path = "./tests/checkpoint_tests.rs"]
 pub(crate) mod checkpoint_tests;
 
-use itertools::Itertools;
 use narwhal_executor::ExecutionIndices;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
@@ -130,6 +129,10 @@ pub struct CheckpointStoreTables {
     #[default_options_override_fn = "checkpoints_table_default_config"]
     pub checkpoints: DBMap<CheckpointSequenceNumber, AuthenticatedCheckpoint>,
 
+    /// The sequence number of the certified checkpoint ending each epoch, whose summary carries
+    /// the committee of the next epoch.
+    pub epoch_change_checkpoints: DBMap<EpochId, CheckpointSequenceNumber>,
+
     // --- Logic related to fragments on the way to making checkpoints
 
     // A list of own fragments indexed by the other node that the fragment connects
@@ -169,6 +172,58 @@ fn locals_table_default_config() -> DBOptions {
 }
 
 impl CheckpointStoreTables {
+    /// Seed an empty checkpoint store with a checkpoint restored from a state snapshot, so
+    /// that checkpoint sync continues from the checkpoint after it.
+    pub fn insert_restored_checkpoint(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+    ) -> SuiResult {
+        let seq = *checkpoint.summary.sequence_number();
+        let locals = CheckpointLocals {
+            next_checkpoint: seq + 1,
+            in_construction_checkpoint_seq: seq + 1,
+            ..Default::default()
+        };
+        self.checkpoints
+            .batch()
+            .insert_batch(
+                &self.checkpoints,
+                [(
+                    &seq,
+                    &AuthenticatedCheckpoint::Certified(checkpoint.clone()),
+                )],
+            )?
+            .insert_batch(&self.checkpoint_contents, [(&seq, contents)])?
+            .insert_batch(
+                &self.transactions_to_checkpoint,
+                contents.iter().map(|digests| (*digests, seq)),
+            )?
+            .insert_batch(
+                &self.epoch_change_checkpoints,
+                epoch_change_entry(checkpoint),
+            )?
+            .insert_batch(&self.locals, [(&LOCALS, &locals)])?
+            .write()?;
+        Ok(())
+    }
+
+    /// Returns the certified checkpoint ending `epoch`, whose summary carries the committee of
+    /// the next epoch, if it is known.
+    pub fn get_epoch_change_checkpoint(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<CertifiedCheckpointSummary>> {
+        let seq = match self.epoch_change_checkpoints.get(&epoch)? {
+            Some(seq) => seq,
+            None => return Ok(None),
+        };
+        match self.checkpoints.get(&seq)? {
+            Some(AuthenticatedCheckpoint::Certified(checkpoint)) => Ok(Some(checkpoint)),
+            _ => Ok(None),
+        }
+    }
+
     /// The checkpoint construction state in `locals` should be for the next checkpoint cert as
     /// much as possible. However we should also make sure that it's not ahead of consensus:
     /// the construction state does not advance to the next checkpoint if it hasn't received enough
@@ -214,6 +269,17 @@ impl CheckpointStoreTables {
     }
 }
 
+/// The `epoch_change_checkpoints` entry of `checkpoint`, if it ends its epoch.
+fn epoch_change_entry(
+    checkpoint: &CertifiedCheckpointSummary,
+) -> Option<(EpochId, CheckpointSequenceNumber)> {
+    checkpoint
+        .summary
+        .next_epoch_committee
+        .is_some()
+        .then(|| (checkpoint.summary.epoch, checkpoint.summary.sequence_number))
+}
+
 pub struct CheckpointStore {
     // Fixed size, static, identity of the authority
     /// The name of this authority.
@@ -243,13 +309,85 @@ impl CheckpointStore {
         Ok(self.tables.checkpoints.get(&seq)?)
     }
 
-    // TODO: there might be more efficient ways to implement this.
-    pub fn get_checkpoints_of_epoch(&self, epoch: EpochId) -> Vec<AuthenticatedCheckpoint> {
-        self.tables
+    /// Returns the stored checkpoints of `epoch`, in sequence order.
+    pub fn get_checkpoints_of_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Vec<AuthenticatedCheckpoint>> {
+        // The checkpoints of an epoch follow the one ending the previous epoch. If that one is
+        // not known, neither are the checkpoints before it, so start from the oldest one.
+        let first = match epoch.checked_sub(1) {
+            Some(previous) => self
+                .get_epoch_change_checkpoint_sequence(previous)?
+                .map_or(0, |seq| seq + 1),
+            None => 0,
+        };
+        Ok(self
+            .tables
             .checkpoints
-            .values()
-            .filter(|cp| cp.summary().epoch == epoch)
-            .collect_vec()
+            .iter()
+            .skip_to(&first)?
+            .map(|(_, checkpoint)| checkpoint)
+            .skip_while(|checkpoint| checkpoint.summary().epoch < epoch)
+            .take_while(|checkpoint| checkpoint.summary().epoch == epoch)
+            .collect())
+    }
+
+    pub fn get_checkpoint_contents(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointContents>, SuiError> {
+        Ok(self.tables.checkpoint_contents.get(&seq)?)
+    }
+
+    /// Returns the sequence number of the checkpoint that includes a transaction, if any.
+    pub fn get_transaction_checkpoint(
+        &self,
+        digests: &ExecutionDigests,
+    ) -> Result<Option<CheckpointSequenceNumber>, SuiError> {
+        Ok(self.tables.transactions_to_checkpoint.get(digests)?)
+    }
+
+    /// Returns the certified checkpoint ending `epoch`, whose summary carries the committee of
+    /// the next epoch, if it is known.
+    pub fn get_epoch_change_checkpoint(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<CertifiedCheckpointSummary>> {
+        self.tables.get_epoch_change_checkpoint(epoch)
+    }
+
+    /// Returns the sequence number of the certified checkpoint ending `epoch`, if it is known.
+    pub fn get_epoch_change_checkpoint_sequence(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self.tables.epoch_change_checkpoints.get(&epoch)?)
+    }
+
+    /// Returns the number of transactions checkpointed during `epoch`, if all the checkpoints of
+    /// the epoch, up to and including its epoch change checkpoint, are known.
+    pub fn get_epoch_transaction_count(&self, epoch: EpochId) -> SuiResult<Option<u64>> {
+        let last = match self.get_epoch_change_checkpoint_sequence(epoch)? {
+            Some(last) => last,
+            None => return Ok(None),
+        };
+        let first = match epoch.checked_sub(1) {
+            None => 0,
+            Some(previous) => match self.get_epoch_change_checkpoint_sequence(previous)? {
+                Some(previous_last) => previous_last + 1,
+                None => return Ok(None),
+            },
+        };
+
+        let mut count = 0;
+        for seq in first..=last {
+            match self.get_checkpoint_contents(seq)? {
+                Some(contents) => count += contents.size() as u64,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(count))
     }
 
     fn get_prev_checkpoint_digest(
@@ -502,11 +640,16 @@ impl CheckpointStore {
         let batch = self.tables.checkpoints.batch();
 
         // Last store the actual checkpoints.
+        let epoch_change = match checkpoint {
+            AuthenticatedCheckpoint::Certified(checkpoint) => epoch_change_entry(checkpoint),
+            _ => None,
+        };
         let batch = batch
             .insert_batch(
                 &self.tables.checkpoints,
                 [(&checkpoint_sequence_number, checkpoint)],
             )?
+            .insert_batch(&self.tables.epoch_change_checkpoints, epoch_change)?
             // Drop local fragments that are used to create proposals for old checkpoint.
             // Note that we don't drop fragments table here, instead they are handled in the call
             // to advance_checkpoint_construction_state.
@@ -833,7 +976,16 @@ impl CheckpointStore {
         let seq = checkpoint.summary.sequence_number();
         self.tables
             .checkpoints
-            .insert(seq, &AuthenticatedCheckpoint::Certified(checkpoint.clone()))?;
+            .batch()
+            .insert_batch(
+                &self.tables.checkpoints,
+                [(seq, &AuthenticatedCheckpoint::Certified(checkpoint.clone()))],
+            )?
+            .insert_batch(
+                &self.tables.epoch_change_checkpoints,
+                epoch_change_entry(checkpoint),
+            )?
+            .write()?;
         self.notify_new_checkpoint(checkpoint.clone());
 
         self.clear_proposal(*seq + 1, committee)?;
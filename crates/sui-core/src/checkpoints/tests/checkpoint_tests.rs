@@ -2102,6 +2102,65 @@ async fn test_no_more_fragments() {
         .is_ok());
 }
 
+#[tokio::test]
+async fn epoch_change_checkpoint_index() {
+    let (committee, keys, mut stores) = random_ckpoint_store();
+    let (_, mut cps) = stores.pop().unwrap();
+    let next_committee =
+        Committee::new(1, committee.voting_rights.iter().cloned().collect()).unwrap();
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+
+    // Checkpoint 0 ends epoch 0, checkpoints 1 and 2 are in epoch 1.
+    let mut previous = None;
+    for (seq, committee, next_epoch_committee) in [
+        (0, &committee, Some(next_committee.clone())),
+        (1, &next_committee, None),
+        (2, &next_committee, None),
+    ] {
+        let signed = keys
+            .iter()
+            .map(|key| {
+                SignedCheckpointSummary::new(
+                    committee.epoch,
+                    seq,
+                    key.public().into(),
+                    key,
+                    &contents,
+                    previous,
+                    sui_types::gas::GasCostSummary::default(),
+                    next_epoch_committee.clone(),
+                )
+            })
+            .collect();
+        let checkpoint = CertifiedCheckpointSummary::aggregate(signed, committee).unwrap();
+        previous = Some(checkpoint.summary.digest());
+        cps.process_synced_checkpoint_certificate(&checkpoint, &contents, committee)
+            .unwrap();
+    }
+
+    let epoch_change = cps.get_epoch_change_checkpoint(0).unwrap().unwrap();
+    assert_eq!(epoch_change.summary.sequence_number, 0);
+    assert!(cps.get_epoch_change_checkpoint(1).unwrap().is_none());
+    assert_eq!(
+        cps.get_epoch_change_checkpoint_sequence(0).unwrap(),
+        Some(0)
+    );
+
+    let sequence_numbers = |epoch| {
+        cps.get_checkpoints_of_epoch(epoch)
+            .unwrap()
+            .iter()
+            .map(|checkpoint| checkpoint.sequence_number())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(sequence_numbers(0), vec![0]);
+    assert_eq!(sequence_numbers(1), vec![1, 2]);
+
+    // The transactions of an epoch can only be counted once it has ended.
+    assert_eq!(cps.get_epoch_transaction_count(0).unwrap(), Some(0));
+    assert_eq!(cps.get_epoch_transaction_count(1).unwrap(), None);
+}
+
 fn create_random_tx_certs<'a>(
     cp_stores: impl Iterator<Item = &'a (PathBuf, CheckpointStore)> + Clone,
     committee: &Committee,
@@ -0,0 +1,368 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commit sinks receive every transaction committed to the local store, in execution order,
+//! so that external systems (e.g. an off-node indexer) can follow the node without polling
+//! JSON-RPC. Delivery is at-least-once: the sequence number of the next transaction to
+//! deliver is persisted per sink only after the sink has accepted a transaction, so after a
+//! crash a sink may see the last few transactions again and must tolerate duplicates.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::Serialize;
+use sui_storage::default_db_options;
+use sui_types::batch::TxSequenceNumber;
+use sui_types::error::SuiResult;
+use sui_types::event::Event;
+use sui_types::messages::{CertifiedTransaction, TransactionEffects};
+use sui_types::object::Object;
+use typed_store::rocks::{DBMap, DBOptions};
+use typed_store::traits::Map;
+use typed_store::traits::TypedStoreDebug;
+use typed_store_derive::DBMapUtils;
+
+/// Everything a sink receives about a committed transaction.
+#[derive(Clone, Debug, Serialize)]
+pub struct CommittedTransaction {
+    /// The sequence number of the transaction in the local execution order.
+    pub sequence_number: TxSequenceNumber,
+    /// The time the node first processed the transaction, if the node keeps indexes.
+    pub timestamp_ms: Option<u64>,
+    pub certificate: CertifiedTransaction,
+    pub effects: TransactionEffects,
+    pub events: Vec<Event>,
+    /// The objects created, mutated or unwrapped by the transaction, at their new version.
+    pub written_objects: Vec<Object>,
+}
+
+#[async_trait]
+pub trait CommitSink: Send + Sync + 'static {
+    /// A stable name identifying the sink, used as the key of its persisted cursor.
+    fn name(&self) -> &str;
+
+    /// Called once per committed transaction, in sequence number order. Returning an error
+    /// causes the same transaction to be delivered again.
+    async fn process_commit(&self, commit: &CommittedTransaction) -> anyhow::Result<()>;
+}
+
+/// Persisted delivery cursors of the commit sinks, keyed by sink name.
+#[derive(DBMapUtils)]
+pub struct CommitSinkStore {
+    /// The sequence number of the next transaction to deliver to each sink.
+    #[default_options_override_fn = "cursors_table_default_config"]
+    cursors: DBMap<String, TxSequenceNumber>,
+}
+
+fn cursors_table_default_config() -> DBOptions {
+    default_db_options(None, None).1
+}
+
+impl CommitSinkStore {
+    pub fn get_cursor(&self, sink: &str) -> SuiResult<TxSequenceNumber> {
+        Ok(self.cursors.get(&sink.to_string())?.unwrap_or_default())
+    }
+
+    pub fn set_cursor(&self, sink: &str, next: TxSequenceNumber) -> SuiResult {
+        Ok(self.cursors.insert(&sink.to_string(), &next)?)
+    }
+}
+
+/// A sink writing each committed transaction as one JSON object per line to files in a local
+/// directory. A new file is started every `max_lines_per_file` lines and on every restart;
+/// files are named after the sequence number of their first transaction, so they sort in
+/// commit order.
+pub struct JsonLinesCommitSink {
+    dir: PathBuf,
+    max_lines_per_file: usize,
+    current: Arc<Mutex<Option<(BufWriter<File>, usize)>>>,
+}
+
+impl JsonLinesCommitSink {
+    pub const NAME: &'static str = "json-lines";
+    const DEFAULT_MAX_LINES_PER_FILE: usize = 10_000;
+
+    pub fn new(dir: &Path) -> anyhow::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            max_lines_per_file: Self::DEFAULT_MAX_LINES_PER_FILE,
+            current: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    pub fn with_max_lines_per_file(mut self, max_lines_per_file: usize) -> Self {
+        self.max_lines_per_file = max_lines_per_file;
+        self
+    }
+
+    pub fn file_name(first: TxSequenceNumber) -> String {
+        format!("commits-{:020}.jsonl", first)
+    }
+
+    /// Append `line`, the encoding of transaction `seq`, to the current file, or to a new one
+    /// starting at `seq` if the current file is full.
+    fn write_line(
+        dir: &Path,
+        max_lines_per_file: usize,
+        current: &Mutex<Option<(BufWriter<File>, usize)>>,
+        seq: TxSequenceNumber,
+        line: &[u8],
+    ) -> anyhow::Result<()> {
+        let mut current = current.lock();
+        if matches!(&*current, Some((_, lines)) if *lines >= max_lines_per_file) {
+            *current = None;
+        }
+        if current.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(dir.join(Self::file_name(seq)))?;
+            *current = Some((BufWriter::new(file), 0));
+        }
+        let (writer, lines) = current.as_mut().unwrap();
+        writer.write_all(line)?;
+        writer.flush()?;
+        // The cursor is advanced once we return, so the line must be durable by then.
+        writer.get_ref().sync_data()?;
+        *lines += 1;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommitSink for JsonLinesCommitSink {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn process_commit(&self, commit: &CommittedTransaction) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(commit)?;
+        line.push(b'\n');
+        // Writing and syncing the file blocks, so it runs off the async runtime.
+        let dir = self.dir.clone();
+        let max_lines_per_file = self.max_lines_per_file;
+        let current = self.current.clone();
+        let seq = commit.sequence_number;
+        tokio::task::spawn_blocking(move || {
+            Self::write_line(&dir, max_lines_per_file, &current, seq, &line)
+        })
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::time::Duration;
+    use sui_types::base_types::{dbg_addr, ObjectID, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use test_utils::messages::{make_tx_certs_and_signed_effects, test_shared_object_transactions};
+
+    use crate::authority::authority_tests::{
+        init_state_with_ids, init_transfer_transaction, send_and_confirm_transaction,
+    };
+    use crate::authority::AuthorityState;
+
+    /// A sink recording the sequence numbers it accepts, failing once on `fail_at`.
+    struct RecordingSink {
+        delivered: Mutex<Vec<TxSequenceNumber>>,
+        fail_at: Mutex<Option<TxSequenceNumber>>,
+    }
+
+    impl RecordingSink {
+        fn new(fail_at: Option<TxSequenceNumber>) -> Arc<Self> {
+            Arc::new(Self {
+                delivered: Mutex::new(vec![]),
+                fail_at: Mutex::new(fail_at),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CommitSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn process_commit(&self, commit: &CommittedTransaction) -> anyhow::Result<()> {
+            let mut fail_at = self.fail_at.lock();
+            if *fail_at == Some(commit.sequence_number) {
+                *fail_at = None;
+                anyhow::bail!("Sink unavailable");
+            }
+            self.delivered.lock().push(commit.sequence_number);
+            Ok(())
+        }
+    }
+
+    async fn transfer_objects(
+        state: &AuthorityState,
+        sender: SuiAddress,
+        sender_key: &AccountKeyPair,
+        objects: &[(ObjectID, ObjectID)],
+    ) {
+        for (object_id, gas_object_id) in objects {
+            let object = state.get_object(object_id).await.unwrap().unwrap();
+            let gas_object = state.get_object(gas_object_id).await.unwrap().unwrap();
+            let transaction = init_transfer_transaction(
+                sender,
+                sender_key,
+                dbg_addr(2),
+                object.compute_object_reference(),
+                gas_object.compute_object_reference(),
+            );
+            send_and_confirm_transaction(state, transaction)
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Run the commit sink process until `sink` has received `count` transactions, then stop it.
+    async fn run_sink_until(
+        state: &Arc<AuthorityState>,
+        sink: &Arc<RecordingSink>,
+        store: &Arc<CommitSinkStore>,
+        count: usize,
+    ) {
+        let process_state = state.clone();
+        let (process_sink, process_store) = (sink.clone(), store.clone());
+        let handle = tokio::spawn(async move {
+            process_state
+                .run_commit_sink_process(process_sink, process_store)
+                .await
+        });
+        tokio::time::timeout(Duration::from_secs(30), async {
+            while sink.delivered.lock().len() < count {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("The sink should receive every committed transaction");
+        // Give the process time to deliver anything it should not have.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        handle.abort();
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn test_commit_sink_process() -> Result<(), anyhow::Error> {
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        let objects: Vec<_> = (0..5)
+            .map(|_| (ObjectID::random(), ObjectID::random()))
+            .collect();
+        let state = Arc::new(
+            init_state_with_ids(
+                objects
+                    .iter()
+                    .flat_map(|(object, gas)| [(sender, *object), (sender, *gas)]),
+            )
+            .await,
+        );
+        let store_dir = tempfile::tempdir()?;
+        let open_store = || {
+            Arc::new(CommitSinkStore::open_tables_read_write(
+                store_dir.path().to_path_buf(),
+                None,
+                None,
+            ))
+        };
+        let committed = |state: &AuthorityState| -> Vec<TxSequenceNumber> {
+            state
+                .database
+                .transactions_in_seq_range(0, TxSequenceNumber::MAX)
+                .unwrap()
+                .into_iter()
+                .map(|(seq, _)| seq)
+                .collect()
+        };
+
+        transfer_objects(&state, sender, &sender_key, &objects[..3]).await;
+        let first_run = committed(&state);
+        assert!(first_run.len() >= 3);
+
+        // A failed delivery is retried, and every transaction is delivered once, in order.
+        let store = open_store();
+        let sink = RecordingSink::new(Some(first_run[1]));
+        run_sink_until(&state, &sink, &store, first_run.len()).await;
+        assert_eq!(*sink.delivered.lock(), first_run);
+        assert!(sink.fail_at.lock().is_none());
+        assert_eq!(
+            store.get_cursor(sink.name())?,
+            first_run.last().unwrap() + 1
+        );
+        drop(store);
+
+        // After a restart, delivery resumes from the persisted cursor.
+        transfer_objects(&state, sender, &sender_key, &objects[3..]).await;
+        let second_run: Vec<_> = committed(&state)
+            .into_iter()
+            .filter(|seq| !first_run.contains(seq))
+            .collect();
+        assert_eq!(second_run.len(), 2);
+
+        let store = open_store();
+        let sink = RecordingSink::new(None);
+        run_sink_until(&state, &sink, &store, second_run.len()).await;
+        assert_eq!(*sink.delivered.lock(), second_run);
+        assert_eq!(
+            store.get_cursor(sink.name())?,
+            second_run.last().unwrap() + 1
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_sink() -> Result<(), anyhow::Error> {
+        let dir = tempfile::tempdir()?;
+        let sink = JsonLinesCommitSink::new(dir.path())?.with_max_lines_per_file(2);
+
+        let (certs, effects) = make_tx_certs_and_signed_effects(test_shared_object_transactions());
+        for (seq, (cert, effects)) in certs.into_iter().zip(effects).take(3).enumerate() {
+            let commit = CommittedTransaction {
+                sequence_number: seq as TxSequenceNumber,
+                timestamp_ms: None,
+                certificate: cert.into_inner(),
+                events: effects.effects.events.clone(),
+                effects: effects.effects,
+                written_objects: vec![],
+            };
+            sink.process_commit(&commit).await?;
+        }
+
+        let mut files: Vec<_> = fs::read_dir(dir.path())?
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                JsonLinesCommitSink::file_name(0),
+                JsonLinesCommitSink::file_name(2)
+            ]
+        );
+
+        let lines: Vec<serde_json::Value> = BufReader::new(File::open(dir.path().join(&files[0]))?)
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["sequence_number"], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cursor_store() -> Result<(), anyhow::Error> {
+        let dir = tempfile::tempdir()?;
+        let store = CommitSinkStore::open_tables_read_write(dir.path().to_path_buf(), None, None);
+        assert_eq!(store.get_cursor(JsonLinesCommitSink::NAME)?, 0);
+        store.set_cursor(JsonLinesCommitSink::NAME, 42)?;
+        assert_eq!(store.get_cursor(JsonLinesCommitSink::NAME)?, 42);
+        Ok(())
+    }
+}
@@ -68,6 +68,17 @@ impl CommitteeStore {
         Ok(self.committee_map.get(epoch_id)?)
     }
 
+    /// Returns up to `limit` committees, in epoch order, starting at epoch `start`.
+    pub fn get_committees(&self, start: EpochId, limit: usize) -> SuiResult<Vec<Committee>> {
+        Ok(self
+            .committee_map
+            .iter()
+            .skip_to(&start)?
+            .take(limit)
+            .map(|(_, committee)| committee)
+            .collect())
+    }
+
     pub fn get_latest_committee(&self) -> Committee {
         self.committee_map
             .iter()
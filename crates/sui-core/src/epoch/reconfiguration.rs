@@ -13,9 +13,11 @@ use std::sync::Arc;
 use std::time::Duration;
 use sui_network::{default_mysten_network_config, tonic};
 use sui_types::base_types::AuthorityName;
+use sui_types::committee::Committee;
 use sui_types::crypto::AuthorityPublicKeyBytes;
-use sui_types::error::SuiResult;
+use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::VerifiedSignedTransaction;
+use sui_types::messages_checkpoint::CertifiedCheckpointSummary;
 use sui_types::sui_system_state::SuiSystemState;
 use tracing::{debug, error, info, warn};
 use typed_store::Map;
@@ -93,7 +95,7 @@ where
                 Vec<u64>,
                 Vec<u64>,
             ) = checkpoints
-                .get_checkpoints_of_epoch(epoch)
+                .get_checkpoints_of_epoch(epoch)?
                 .iter()
                 .map(|cp| {
                     (
@@ -197,6 +199,46 @@ where
         Ok(())
     }
 
+    /// Move a fullnode that syncs from checkpoints to the committee carried by `checkpoint`, the
+    /// certified checkpoint ending the current epoch. Unlike a validator, the fullnode doesn't
+    /// take part in the epoch change: it only needs the new committee to verify the checkpoints
+    /// and certificates of the next epoch.
+    pub async fn reconfigure_from_checkpoint(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+    ) -> SuiResult {
+        let epoch = checkpoint.summary.epoch;
+        let voting_rights = checkpoint
+            .summary
+            .next_epoch_committee
+            .clone()
+            .ok_or_else(|| SuiError::from("Checkpoint does not end the epoch"))?;
+        let new_committee = Committee::new(epoch + 1, voting_rights.into_iter().collect())?;
+        info!(
+            ?epoch,
+            "Moving to the committee of the next epoch: {}", new_committee
+        );
+
+        self.state.database.reopen_epoch_db(new_committee.epoch);
+        self.state.update_committee(new_committee.clone())?;
+
+        let new_clients = if A::needs_network_recreation() {
+            self.recreate_network(self.state.get_sui_system_state_object().await?)?
+        } else {
+            self.net.load().clone_inner_clients()
+        };
+        let new_net = Arc::new(AuthorityAggregator::new(
+            new_committee,
+            self.state.committee_store().clone(),
+            new_clients,
+            self.net.load().metrics.clone(),
+            self.net.load().safe_client_metrics.clone(),
+            self.net.load().network_client_metrics.clone(),
+        ));
+        self.net.store(new_net);
+        Ok(())
+    }
+
     /// Recreates the network if the client is a type of client that has a network, and swap the new
     /// clients onto the authority aggregator with the new committee.
     pub fn recreate_network(
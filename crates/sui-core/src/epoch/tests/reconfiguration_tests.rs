@@ -11,7 +11,7 @@ use std::{
 };
 
 use sui_types::crypto::AuthoritySignInfo;
-use sui_types::messages::CertifiedTransaction;
+use sui_types::messages::{CertifiedTransaction, ExecutionDigests};
 use sui_types::messages_checkpoint::{
     AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents,
 };
@@ -23,6 +23,7 @@ use sui_types::{
     object::Object,
     SUI_SYSTEM_STATE_OBJECT_ID,
 };
+use typed_store::Map;
 
 use crate::authority::AuthorityState;
 use crate::checkpoints::causal_order_effects::TestEffectsStore;
@@ -218,6 +219,39 @@ async fn test_finish_epoch_change() {
         assert!(response.signed_effects.is_some());
         assert!(response.certified_transaction.is_some());
         assert!(response.signed_effects.is_some());
+
+        // Verify that the statistics of the ended epoch were recorded.
+        let stats = active.state.get_epoch_stats(None, 10).unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].epoch, 0);
+        assert_eq!(stats[0].validator_rewards.len(), 4);
+        // The checkpoints of the epoch were skipped, so their transactions cannot be counted.
+        assert_eq!(stats[0].transaction_count, None);
+
+        // Once all the checkpoints of the epoch are known, the transaction count is available.
+        {
+            let checkpoints = active.state.checkpoints.lock();
+            for seq in 0..=CHECKPOINT_COUNT_PER_EPOCH {
+                let contents = CheckpointContents::new_with_causally_ordered_transactions(
+                    [ExecutionDigests::random()].into_iter(),
+                );
+                checkpoints
+                    .tables
+                    .checkpoint_contents
+                    .insert(&seq, &contents)
+                    .unwrap();
+            }
+            checkpoints
+                .tables
+                .epoch_change_checkpoints
+                .insert(&0, &CHECKPOINT_COUNT_PER_EPOCH)
+                .unwrap();
+        }
+        let stats = active.state.get_epoch_stats(None, 10).unwrap();
+        assert_eq!(
+            stats[0].transaction_count,
+            Some(CHECKPOINT_COUNT_PER_EPOCH + 1)
+        );
     }
 }
 
@@ -7,7 +7,9 @@ pub mod authority_aggregator;
 pub mod authority_batch;
 pub mod authority_client;
 pub mod authority_server;
+pub mod checkpoint_archive;
 pub mod checkpoints;
+pub mod commit_sink;
 pub mod consensus_adapter;
 pub mod epoch;
 pub mod event_handler;
@@ -16,6 +18,7 @@ pub mod gateway_state;
 pub mod metrics;
 pub mod quorum_driver;
 pub mod safe_client;
+pub mod state_snapshot;
 pub mod streamer;
 pub mod transaction_input_checker;
 pub mod transaction_orchestrator;
@@ -0,0 +1,582 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! State snapshots let a new node start from the live object set at a recent certified
+//! checkpoint instead of replaying every transaction since genesis.
+//!
+//! A snapshot is a directory holding a `MANIFEST` and a sequence of chunk files. Each chunk is
+//! a BCS encoded `Vec<Object>`, and the manifest records the SHA3-256 digest of every chunk
+//! together with the certified checkpoint (and its contents) the snapshot was taken at, the
+//! certified checkpoints ending every earlier epoch, and the state digest of the object set.
+//! The manifest is written last, so a directory without one is an incomplete snapshot.
+//!
+//! On restore, the committee of every epoch is derived from the genesis committee through the
+//! epoch change checkpoints, each signed by the committee of its epoch, and the checkpoint of
+//! the snapshot is verified against the committee of its epoch. Checkpoints do not (yet) commit
+//! to a root of the object set, so nothing signed by the committee binds the objects to the
+//! checkpoint: the operator must supply the state digest of the checkpoint from a trusted
+//! source (e.g. by comparing the digests exported by several independent nodes), and restore
+//! fails unless the objects of the snapshot hash to it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, ensure};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use sui_types::base_types::{ExecutionDigests, ObjectID, ObjectRef, TransactionDigest};
+use sui_types::committee::Committee;
+use sui_types::messages_checkpoint::{
+    AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents,
+    CheckpointSequenceNumber,
+};
+use sui_types::object::Object;
+use tracing::info;
+use typed_store::Map;
+
+use crate::authority::AuthorityStore;
+use crate::checkpoints::CheckpointStoreTables;
+use crate::epoch::committee_store::CommitteeStore;
+
+pub const STATE_SNAPSHOT_FORMAT_VERSION: u32 = 1;
+pub const DEFAULT_OBJECTS_PER_CHUNK: usize = 100_000;
+
+const MANIFEST_FILE_NAME: &str = "MANIFEST";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateSnapshotManifest {
+    pub format_version: u32,
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    /// The certified checkpoints ending the epochs before the epoch of `checkpoint`, in epoch
+    /// order. Each one is signed by the committee of its epoch and carries the committee of
+    /// the next one, which links the genesis committee to the committee of `checkpoint`.
+    pub epoch_change_checkpoints: Vec<CertifiedCheckpointSummary>,
+    pub state_digest: StateDigest,
+    pub chunks: Vec<StateSnapshotChunk>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateSnapshotChunk {
+    pub file_name: String,
+    pub object_count: u64,
+    /// SHA3-256 digest of the chunk file.
+    pub digest: [u8; 32],
+}
+
+/// SHA3-256 digest of the digest of the checkpoint a snapshot was taken at followed by the
+/// reference of every live object, in object ID order.
+pub type StateDigest = [u8; 32];
+
+struct StateHasher {
+    hasher: Sha3_256,
+    last: Option<ObjectID>,
+}
+
+impl StateHasher {
+    fn new(checkpoint: &CertifiedCheckpointSummary) -> Self {
+        let mut hasher = Sha3_256::new();
+        hasher.update(checkpoint.summary.digest());
+        Self { hasher, last: None }
+    }
+
+    fn push(&mut self, object_ref: &ObjectRef) -> anyhow::Result<()> {
+        ensure!(
+            !matches!(self.last, Some(last) if last >= object_ref.0),
+            "Snapshot objects are not in object ID order at {:?}",
+            object_ref.0
+        );
+        self.last = Some(object_ref.0);
+        self.hasher.update(bcs::to_bytes(object_ref)?);
+        Ok(())
+    }
+
+    fn finish(self) -> StateDigest {
+        self.hasher.finalize().into()
+    }
+}
+
+impl StateSnapshotManifest {
+    pub fn read(dir: &Path) -> anyhow::Result<Self> {
+        let bytes = fs::read(dir.join(MANIFEST_FILE_NAME))
+            .map_err(|e| anyhow!("Cannot read snapshot manifest in {:?}: {e}", dir))?;
+        let manifest: Self = bcs::from_bytes(&bytes)?;
+        ensure!(
+            manifest.format_version == STATE_SNAPSHOT_FORMAT_VERSION,
+            "Unsupported snapshot format version {}, expected {}",
+            manifest.format_version,
+            STATE_SNAPSHOT_FORMAT_VERSION
+        );
+        Ok(manifest)
+    }
+
+    pub fn sequence_number(&self) -> CheckpointSequenceNumber {
+        *self.checkpoint.summary.sequence_number()
+    }
+
+    pub fn object_count(&self) -> u64 {
+        self.chunks.iter().map(|chunk| chunk.object_count).sum()
+    }
+
+    /// Verify the checkpoint of the snapshot, given the trusted genesis committee, and return
+    /// the committees of every epoch up to and including the epoch of the checkpoint.
+    pub fn verify(&self, genesis_committee: &Committee) -> anyhow::Result<Vec<Committee>> {
+        let mut committees = vec![genesis_committee.clone()];
+        for checkpoint in &self.epoch_change_checkpoints {
+            let committee = committees.last().unwrap();
+            ensure!(
+                checkpoint.summary.epoch == committee.epoch,
+                "Snapshot epoch change checkpoint {} is of epoch {}, expected epoch {}",
+                checkpoint.summary.sequence_number,
+                checkpoint.summary.epoch,
+                committee.epoch
+            );
+            ensure!(
+                checkpoint.summary.sequence_number < self.sequence_number(),
+                "Snapshot epoch change checkpoint {} is after the snapshot checkpoint",
+                checkpoint.summary.sequence_number
+            );
+            checkpoint.verify(committee, None)?;
+            let voting_rights =
+                checkpoint
+                    .summary
+                    .next_epoch_committee
+                    .clone()
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Snapshot checkpoint {} does not end epoch {}",
+                            checkpoint.summary.sequence_number,
+                            committee.epoch
+                        )
+                    })?;
+            let next = Committee::new(committee.epoch + 1, voting_rights.into_iter().collect())?;
+            committees.push(next);
+        }
+        self.checkpoint
+            .verify(committees.last().unwrap(), Some(&self.contents))?;
+        Ok(committees)
+    }
+}
+
+/// Write a snapshot of the live object set at `checkpoint` (or at the latest certified
+/// checkpoint if none is given) to `dir`. The store must not be written to concurrently, so
+/// this is meant to be run against the database of a stopped node.
+pub fn write_state_snapshot(
+    store: &AuthorityStore,
+    checkpoints: &CheckpointStoreTables,
+    checkpoint: Option<CheckpointSequenceNumber>,
+    dir: &Path,
+    objects_per_chunk: usize,
+) -> anyhow::Result<StateSnapshotManifest> {
+    let certified = match checkpoint {
+        Some(seq) => checkpoints.checkpoints.get(&seq)?,
+        None => checkpoints
+            .checkpoints
+            .iter()
+            .skip_to_last()
+            .reverse()
+            .map(|(_, checkpoint)| checkpoint)
+            .find(|checkpoint| matches!(checkpoint, AuthenticatedCheckpoint::Certified(_))),
+    };
+    let checkpoint = match certified {
+        Some(AuthenticatedCheckpoint::Certified(checkpoint)) => checkpoint,
+        _ => bail!("No certified checkpoint {:?} found", checkpoint),
+    };
+    let seq = *checkpoint.summary.sequence_number();
+    let contents = checkpoints
+        .checkpoint_contents
+        .get(&seq)?
+        .ok_or_else(|| anyhow!("Contents of checkpoint {seq} not found"))?;
+    for digests in contents.iter() {
+        ensure!(
+            store.effects_exists(&digests.transaction)?,
+            "Checkpoint {seq} is not fully executed locally, missing {:?}",
+            digests.transaction
+        );
+    }
+
+    let mut epoch_change_checkpoints = Vec::new();
+    for epoch in 0..checkpoint.summary.epoch {
+        epoch_change_checkpoints.push(
+            checkpoints
+                .get_epoch_change_checkpoint(epoch)?
+                .ok_or_else(|| anyhow!("Certified checkpoint ending epoch {epoch} not found"))?,
+        );
+    }
+
+    fs::create_dir_all(dir)?;
+    let mut writer = ChunkWriter::new(dir, objects_per_chunk);
+    let mut state = StateHasher::new(&checkpoint);
+
+    // parent_sync holds an entry for every version of every object, ordered by object ID and
+    // version, so the state of each object at the checkpoint is given by its last entry that
+    // was written by a checkpointed transaction.
+    let is_checkpointed = |digest: &TransactionDigest| -> anyhow::Result<bool> {
+        if *digest == TransactionDigest::genesis() {
+            return Ok(true);
+        }
+        let effects = match store.perpetual_tables.effects.get(digest)? {
+            Some(effects) => effects,
+            None => return Ok(false),
+        };
+        Ok(checkpoints
+            .transactions_to_checkpoint
+            .get(&ExecutionDigests::new(*digest, *effects.digest()))?
+            .map_or(false, |checkpoint| checkpoint <= seq))
+    };
+    let mut push_live = |object_ref: ObjectRef| -> anyhow::Result<()> {
+        // Deleted and wrapped objects are not part of the live object set.
+        if object_ref.2.is_alive() {
+            state.push(&object_ref)?;
+            writer.push(store, object_ref)?;
+        }
+        Ok(())
+    };
+    let mut latest: Option<ObjectRef> = None;
+    for (object_ref, digest) in store.perpetual_tables.parent_sync.iter() {
+        if matches!(latest, Some(prev) if prev.0 != object_ref.0) {
+            push_live(latest.take().unwrap())?;
+        }
+        if is_checkpointed(&digest)? {
+            latest = Some(object_ref);
+        }
+    }
+    if let Some(object_ref) = latest {
+        push_live(object_ref)?;
+    }
+
+    let manifest = StateSnapshotManifest {
+        format_version: STATE_SNAPSHOT_FORMAT_VERSION,
+        checkpoint,
+        contents,
+        epoch_change_checkpoints,
+        state_digest: state.finish(),
+        chunks: writer.finish()?,
+    };
+    fs::write(dir.join(MANIFEST_FILE_NAME), bcs::to_bytes(&manifest)?)?;
+    info!(
+        checkpoint = seq,
+        objects = manifest.object_count(),
+        chunks = manifest.chunks.len(),
+        "Wrote state snapshot to {:?}",
+        dir
+    );
+    Ok(manifest)
+}
+
+/// Restore the snapshot in `dir` into empty stores, provided that its objects hash to
+/// `expected_state_digest`. Every chunk is verified before any object is written.
+pub async fn restore_state_snapshot(
+    dir: &Path,
+    genesis_committee: &Committee,
+    expected_state_digest: &StateDigest,
+    store: &AuthorityStore,
+    checkpoints: &CheckpointStoreTables,
+    committees: &CommitteeStore,
+) -> anyhow::Result<StateSnapshotManifest> {
+    let manifest = StateSnapshotManifest::read(dir)?;
+    let epoch_committees = manifest.verify(genesis_committee)?;
+    ensure!(
+        manifest.state_digest == *expected_state_digest,
+        "Snapshot state digest does not match the expected state digest"
+    );
+    let mut state = StateHasher::new(&manifest.checkpoint);
+    for chunk in &manifest.chunks {
+        for object in read_chunk(dir, chunk)? {
+            state.push(&object.compute_object_reference())?;
+        }
+    }
+    ensure!(
+        state.finish() == manifest.state_digest,
+        "Snapshot objects do not match the state digest of the manifest"
+    );
+    ensure!(
+        store.database_is_empty()?,
+        "Cannot restore a snapshot into a non-empty store"
+    );
+    ensure!(
+        checkpoints.checkpoints.iter().next().is_none(),
+        "Cannot restore a snapshot into a non-empty checkpoint store"
+    );
+
+    for chunk in &manifest.chunks {
+        let objects = read_chunk(dir, chunk)?;
+        store.insert_snapshot_objects(&objects).await?;
+    }
+    checkpoints.insert_restored_checkpoint(&manifest.checkpoint, &manifest.contents)?;
+    for committee in &epoch_committees[1..] {
+        if committees.get_committee(&committee.epoch)?.is_none() {
+            committees.insert_new_committee(committee)?;
+        }
+    }
+
+    info!(
+        checkpoint = manifest.sequence_number(),
+        objects = manifest.object_count(),
+        "Restored state snapshot from {:?}",
+        dir
+    );
+    Ok(manifest)
+}
+
+/// Read a chunk of the snapshot in `dir`, checking it against its digest in the manifest.
+pub fn read_chunk(dir: &Path, chunk: &StateSnapshotChunk) -> anyhow::Result<Vec<Object>> {
+    let bytes = fs::read(dir.join(&chunk.file_name))?;
+    ensure!(
+        chunk_digest(&bytes) == chunk.digest,
+        "Snapshot chunk {} is corrupted: digest mismatch",
+        chunk.file_name
+    );
+    let objects: Vec<Object> = bcs::from_bytes(&bytes)?;
+    ensure!(
+        objects.len() as u64 == chunk.object_count,
+        "Snapshot chunk {} holds {} objects, expected {}",
+        chunk.file_name,
+        objects.len(),
+        chunk.object_count
+    );
+    Ok(objects)
+}
+
+fn chunk_digest(bytes: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(bytes).into()
+}
+
+struct ChunkWriter {
+    dir: PathBuf,
+    objects_per_chunk: usize,
+    pending: Vec<Object>,
+    chunks: Vec<StateSnapshotChunk>,
+}
+
+impl ChunkWriter {
+    fn new(dir: &Path, objects_per_chunk: usize) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            objects_per_chunk: objects_per_chunk.max(1),
+            pending: Vec::new(),
+            chunks: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, store: &AuthorityStore, object_ref: ObjectRef) -> anyhow::Result<()> {
+        let object = store
+            .get_object_by_key(&object_ref.0, object_ref.1)?
+            .ok_or_else(|| anyhow!("Object {:?} missing from the store", object_ref))?;
+        self.pending.push(object);
+        if self.pending.len() >= self.objects_per_chunk {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        let file_name = format!("chunk-{:06}.bcs", self.chunks.len());
+        let bytes = bcs::to_bytes(&self.pending)?;
+        fs::write(self.dir.join(&file_name), &bytes)?;
+        self.chunks.push(StateSnapshotChunk {
+            file_name,
+            object_count: self.pending.len() as u64,
+            digest: chunk_digest(&bytes),
+        });
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<Vec<StateSnapshotChunk>> {
+        if !self.pending.is_empty() {
+            self.flush()?;
+        }
+        Ok(self.chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::traits::KeyPair;
+    use rand::{rngs::StdRng, SeedableRng};
+    use sui_types::base_types::SuiAddress;
+    use sui_types::crypto::AuthorityKeyPair;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::SignedCheckpointSummary;
+    use sui_types::utils::make_committee_key;
+
+    fn certify(
+        keys: &[AuthorityKeyPair],
+        committee: &Committee,
+        sequence_number: CheckpointSequenceNumber,
+        contents: &CheckpointContents,
+        next_epoch_committee: Option<Committee>,
+    ) -> CertifiedCheckpointSummary {
+        let signed = keys
+            .iter()
+            .map(|key| {
+                SignedCheckpointSummary::new(
+                    committee.epoch,
+                    sequence_number,
+                    key.public().into(),
+                    key,
+                    contents,
+                    None,
+                    GasCostSummary::default(),
+                    next_epoch_committee.clone(),
+                )
+            })
+            .collect();
+        CertifiedCheckpointSummary::aggregate(signed, committee).unwrap()
+    }
+
+    /// A node DB holding `objects` at genesis and two certified checkpoints: the first ends
+    /// epoch 0, the second is in epoch 1.
+    async fn init_node_stores(
+        dir: &Path,
+        objects: &[Object],
+    ) -> (AuthorityStore, CheckpointStoreTables, Committee) {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (genesis_keys, genesis_committee) = make_committee_key(&mut rng);
+        let (next_keys, next_committee) = make_committee_key(&mut rng);
+        let next_committee =
+            Committee::new(1, next_committee.voting_rights.into_iter().collect()).unwrap();
+
+        let store = AuthorityStore::open(&dir.join("store"), None).unwrap();
+        for object in objects {
+            store.insert_genesis_object(object.clone()).await.unwrap();
+        }
+
+        let checkpoints =
+            CheckpointStoreTables::open_tables_read_write(dir.join("checkpoints"), None, None);
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+        let epoch_change = certify(
+            &genesis_keys,
+            &genesis_committee,
+            0,
+            &contents,
+            Some(next_committee.clone()),
+        );
+        let latest = certify(&next_keys, &next_committee, 1, &contents, None);
+        for checkpoint in [epoch_change, latest] {
+            let seq = checkpoint.summary.sequence_number;
+            checkpoints
+                .checkpoints
+                .insert(&seq, &AuthenticatedCheckpoint::Certified(checkpoint))
+                .unwrap();
+            checkpoints
+                .checkpoint_contents
+                .insert(&seq, &contents)
+                .unwrap();
+        }
+        checkpoints.epoch_change_checkpoints.insert(&0, &0).unwrap();
+        (store, checkpoints, genesis_committee)
+    }
+
+    #[tokio::test]
+    async fn test_state_snapshot_round_trip() -> Result<(), anyhow::Error> {
+        let objects: Vec<_> = (0..3)
+            .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), SuiAddress::default()))
+            .collect();
+        let node_dir = tempfile::tempdir()?;
+        let (store, checkpoints, genesis_committee) =
+            init_node_stores(node_dir.path(), &objects).await;
+
+        let snapshot_dir = tempfile::tempdir()?;
+        let manifest = write_state_snapshot(&store, &checkpoints, None, snapshot_dir.path(), 2)?;
+        assert_eq!(manifest.sequence_number(), 1);
+        assert_eq!(manifest.epoch_change_checkpoints.len(), 1);
+        assert_eq!(manifest.object_count(), 3);
+        assert_eq!(manifest.chunks.len(), 2);
+
+        let restored_dir = tempfile::tempdir()?;
+        let restored_store = AuthorityStore::open(&restored_dir.path().join("store"), None)?;
+        let restored_checkpoints = CheckpointStoreTables::open_tables_read_write(
+            restored_dir.path().join("checkpoints"),
+            None,
+            None,
+        );
+        let committees =
+            CommitteeStore::new(restored_dir.path().join("epochs"), &genesis_committee, None);
+
+        // The restore fails closed unless the object set is the trusted one.
+        assert!(restore_state_snapshot(
+            snapshot_dir.path(),
+            &genesis_committee,
+            &[0; 32],
+            &restored_store,
+            &restored_checkpoints,
+            &committees,
+        )
+        .await
+        .is_err());
+        assert!(restored_store.database_is_empty()?);
+
+        restore_state_snapshot(
+            snapshot_dir.path(),
+            &genesis_committee,
+            &manifest.state_digest,
+            &restored_store,
+            &restored_checkpoints,
+            &committees,
+        )
+        .await?;
+        for object in &objects {
+            assert_eq!(
+                restored_store.get_object(&object.id())?.as_ref(),
+                Some(object)
+            );
+        }
+        assert!(restored_checkpoints.checkpoints.get(&1)?.is_some());
+        assert_eq!(committees.get_committee(&1)?.unwrap().epoch, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_state_snapshot_rejects_tampered_committee() -> Result<(), anyhow::Error> {
+        let node_dir = tempfile::tempdir()?;
+        let (store, checkpoints, genesis_committee) = init_node_stores(node_dir.path(), &[]).await;
+        let snapshot_dir = tempfile::tempdir()?;
+        let manifest = write_state_snapshot(&store, &checkpoints, None, snapshot_dir.path(), 2)?;
+        assert_eq!(manifest.verify(&genesis_committee)?.len(), 2);
+
+        // A snapshot claiming another committee for epoch 1 must be signed by the genesis
+        // committee to be accepted.
+        let (forged_keys, forged_committee) = make_committee_key(&mut StdRng::from_seed([1; 32]));
+        let forged_committee =
+            Committee::new(1, forged_committee.voting_rights.into_iter().collect())?;
+        let mut tampered = manifest.clone();
+        tampered.epoch_change_checkpoints[0]
+            .summary
+            .next_epoch_committee = Some(forged_committee.voting_rights.clone());
+        assert!(tampered.verify(&genesis_committee).is_err());
+
+        // Nor can the epoch change checkpoint be dropped to pass off a checkpoint of epoch 1
+        // signed by the forged committee.
+        let mut tampered = manifest.clone();
+        tampered.epoch_change_checkpoints.clear();
+        assert!(tampered.verify(&genesis_committee).is_err());
+        tampered.checkpoint = certify(&forged_keys, &forged_committee, 1, &manifest.contents, None);
+        assert!(tampered.verify(&genesis_committee).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunks_are_checksummed() -> Result<(), anyhow::Error> {
+        let dir = tempfile::tempdir()?;
+        let mut writer = ChunkWriter::new(dir.path(), 2);
+        writer.pending = (0..3)
+            .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), SuiAddress::default()))
+            .collect();
+        writer.flush()?;
+        let chunks = writer.finish()?;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(read_chunk(dir.path(), &chunks[0])?.len(), 3);
+
+        let path = dir.path().join(&chunks[0].file_name);
+        let mut bytes = fs::read(&path)?;
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&path, bytes)?;
+        assert!(read_chunk(dir.path(), &chunks[0]).is_err());
+        Ok(())
+    }
+}
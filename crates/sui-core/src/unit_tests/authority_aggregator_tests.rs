@@ -108,16 +108,20 @@ pub async fn init_local_authorities(
             stake: 1,
             delegation: 0,
             gas_price: 1,
+            commission_rate: 0,
             network_address: sui_config::utils::new_network_address(),
             narwhal_primary_address: sui_config::utils::new_network_address(),
             narwhal_worker_address: sui_config::utils::new_network_address(),
             narwhal_consensus_address: sui_config::utils::new_network_address(),
+            description: String::new(),
+            image_url: String::new(),
+            project_url: String::new(),
         };
         let pop = generate_proof_of_possession(&key_pair, (&account_key_pair.public()).into());
         builder = builder.add_validator(validator_info, pop);
         key_pairs.push((authority_name, key_pair));
     }
-    let genesis = builder.build();
+    let genesis = builder.build().unwrap();
     let (aggregator, authorities) = init_local_authorities_with_genesis(&genesis, key_pairs).await;
     (aggregator, authorities, pkg_ref)
 }
@@ -3,8 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    authority::authority_store_tables::PerpetualTablesInconsistency,
     authority_client::{AuthorityAPI, NetworkAuthorityClient, NetworkAuthorityClientMetrics},
     authority_server::AuthorityServer,
+    checkpoints::CheckpointStoreTables,
     test_utils::to_sender_signed_transaction,
 };
 
@@ -1112,6 +1114,155 @@ async fn test_handle_confirmation_transaction_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_owner_changes_recorded() {
+    let recipient = dbg_addr(2);
+    let ExecutedTransfer {
+        authority_state,
+        sender,
+        object,
+        gas_object,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_state_with_executed_transfer(Some(recipient)).await;
+    let object_id = object.id();
+    let gas_object_id = gas_object.id();
+
+    let expected = OwnerChange {
+        object_id,
+        version: object.version().increment(),
+        from: Some(Owner::AddressOwner(sender)),
+        to: Some(Owner::AddressOwner(recipient)),
+        transaction_digest: *certified_transfer_transaction.digest(),
+    };
+    let changes = authority_state
+        .get_owner_changes(OwnerChangeQuery::Object(object_id), None, 10)
+        .unwrap();
+    assert_eq!(changes, vec![expected.clone()]);
+
+    // The change is visible from both the sender and the recipient.
+    for address in [sender, recipient] {
+        let changes = authority_state
+            .get_owner_changes(OwnerChangeQuery::Address(address), None, 10)
+            .unwrap();
+        assert_eq!(changes, vec![expected.clone()]);
+    }
+
+    // The gas object was mutated but its owner didn't change.
+    let changes = authority_state
+        .get_owner_changes(OwnerChangeQuery::Object(gas_object_id), None, 10)
+        .unwrap();
+    assert!(changes.is_empty());
+}
+
+#[tokio::test]
+async fn test_perpetual_tables_consistency() {
+    let ExecutedTransfer {
+        authority_state,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_state_with_executed_transfer(Some(dbg_addr(2))).await;
+
+    let tables = &authority_state.database.perpetual_tables;
+    assert_eq!(tables.check_consistency().unwrap(), vec![]);
+
+    // Dropping the effects of the transfer leaves its certificate dangling.
+    tables
+        .effects
+        .remove(certified_transfer_transaction.digest())
+        .unwrap();
+    assert_eq!(
+        tables.check_consistency().unwrap(),
+        vec![PerpetualTablesInconsistency::CertificateWithoutEffects(
+            *certified_transfer_transaction.digest()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_revert_transaction() {
+    let ExecutedTransfer {
+        authority_state,
+        sender,
+        sender_key,
+        object,
+        certificate: first_transfer,
+        effects,
+        ..
+    } = init_state_with_executed_transfer(None).await;
+    let object_id = object.id();
+
+    let new_object_ref = effects
+        .mutated
+        .iter()
+        .find(|((id, _, _), _)| *id == object_id)
+        .unwrap()
+        .0;
+    let second_transfer = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        new_object_ref,
+        effects.gas_object.0,
+        &authority_state,
+    );
+    let second_effects = authority_state
+        .handle_certificate(&second_transfer)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap();
+    let second_digests = ExecutionDigests::new(*second_transfer.digest(), *second_effects.digest());
+    let checkpoints = CheckpointStoreTables::open_tables_read_write(
+        tempfile::tempdir().unwrap().into_path(),
+        None,
+        None,
+    );
+    checkpoints
+        .extra_transactions
+        .insert(&second_digests, &0)
+        .unwrap();
+
+    // The first transfer can't be reverted while the second one consumes its outputs.
+    let store = &authority_state.database;
+    assert!(store
+        .revert_transaction(first_transfer.digest(), None)
+        .await
+        .is_err());
+
+    store
+        .revert_transaction(second_transfer.digest(), Some(&checkpoints))
+        .await
+        .unwrap();
+    assert!(checkpoints.extra_transactions.iter().next().is_none());
+    store
+        .revert_transaction(first_transfer.digest(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        authority_state
+            .get_object(&object_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .compute_object_reference(),
+        object.compute_object_reference()
+    );
+    assert!(store
+        .get_object_locking_transaction(&object.compute_object_reference())
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(store.perpetual_tables.check_consistency().unwrap(), vec![]);
+
+    // The transfer can be executed again once it has been reverted.
+    authority_state
+        .handle_certificate(&first_transfer)
+        .await
+        .unwrap();
+}
+
 struct LimitedPoll<F: Future> {
     inner: Pin<Box<F>>,
     count: u64,
@@ -2029,6 +2180,63 @@ pub async fn init_state_with_ids<I: IntoIterator<Item = (SuiAddress, ObjectID)>>
     state
 }
 
+/// A transfer executed by `init_state_with_executed_transfer`, with the objects as they were
+/// before the transfer.
+struct ExecutedTransfer {
+    authority_state: AuthorityState,
+    sender: SuiAddress,
+    sender_key: AccountKeyPair,
+    object: Object,
+    gas_object: Object,
+    certificate: VerifiedCertificate,
+    effects: TransactionEffects,
+}
+
+/// Creates an authority where a new sender owns an object and a gas object, and executes the
+/// transfer of the object to `recipient`, or back to the sender if there is none.
+async fn init_state_with_executed_transfer(recipient: Option<SuiAddress>) -> ExecutedTransfer {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient.unwrap_or(sender),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    let effects = authority_state
+        .handle_certificate(&certificate)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects;
+    ExecutedTransfer {
+        authority_state,
+        sender,
+        sender_key,
+        object,
+        gas_object,
+        certificate,
+        effects,
+    }
+}
+
 #[cfg(test)]
 pub async fn init_state_with_ids_and_object_basics<
     I: IntoIterator<Item = (SuiAddress, ObjectID)>,
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::ops::Mul;
 
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
@@ -52,6 +53,46 @@ pub struct GasStatus<'a> {
     cost_table: &'a CostTable,
     gas_left: InternalGas,
     charge: bool,
+    profiler: Option<GasProfiler>,
+}
+
+/// Attributes the gas charged by a `GasStatus` to the Move function being executed, to report
+/// where the gas of a call goes.
+#[derive(Debug, Default)]
+pub struct GasProfiler {
+    /// The functions being executed, innermost last.
+    frames: Vec<String>,
+    /// The gas charged in the body of each function, not including the functions it calls.
+    pub costs: BTreeMap<String, InternalGas>,
+}
+
+impl GasProfiler {
+    /// Create a profiler for a call to `function`, which the VM enters without charging a call.
+    pub fn new(function: String) -> Self {
+        Self {
+            frames: vec![function],
+            costs: BTreeMap::new(),
+        }
+    }
+
+    fn enter(&mut self, module_id: &ModuleId, func_name: &str) {
+        self.frames
+            .push(format!("{}::{}", module_id.short_str_lossless(), func_name))
+    }
+
+    fn exit(&mut self) {
+        self.frames.pop();
+    }
+
+    fn charge(&mut self, amount: InternalGas) {
+        if let Some(function) = self.frames.last() {
+            let cost = self
+                .costs
+                .entry(function.clone())
+                .or_insert_with(InternalGas::zero);
+            *cost = *cost + amount;
+        }
+    }
 }
 
 impl<'a> GasStatus<'a> {
@@ -64,6 +105,7 @@ impl<'a> GasStatus<'a> {
             gas_left: gas_left.to_unit(),
             cost_table,
             charge: true,
+            profiler: None,
         }
     }
 
@@ -76,6 +118,7 @@ impl<'a> GasStatus<'a> {
             gas_left: InternalGas::new(0),
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
+            profiler: None,
         }
     }
 
@@ -98,6 +141,9 @@ impl<'a> GasStatus<'a> {
         match self.gas_left.checked_sub(amount) {
             Some(gas_left) => {
                 self.gas_left = gas_left;
+                if let Some(profiler) = &mut self.profiler {
+                    profiler.charge(amount);
+                }
                 Ok(())
             }
             None => {
@@ -136,6 +182,16 @@ impl<'a> GasStatus<'a> {
     pub fn set_metering(&mut self, enabled: bool) {
         self.charge = enabled
     }
+
+    /// Attribute the gas charged from now on to the functions executing, see `GasProfiler`.
+    pub fn set_profiler(&mut self, profiler: GasProfiler) {
+        self.profiler = Some(profiler)
+    }
+
+    /// Stop profiling and return the profiler, if one was set.
+    pub fn take_profiler(&mut self) -> Option<GasProfiler> {
+        self.profiler.take()
+    }
 }
 
 fn get_simple_instruction_opcode(instr: SimpleInstruction) -> Opcodes {
@@ -203,25 +259,38 @@ fn get_simple_instruction_opcode(instr: SimpleInstruction) -> Opcodes {
 impl<'b> GasMeter for GasStatus<'b> {
     /// Charge an instruction and fail if not enough gas units are left.
     fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
-        self.charge_instr(get_simple_instruction_opcode(instr))
+        let is_ret = matches!(instr, SimpleInstruction::Ret);
+        let res = self.charge_instr(get_simple_instruction_opcode(instr));
+        if is_ret {
+            if let Some(profiler) = &mut self.profiler {
+                profiler.exit();
+            }
+        }
+        res
     }
 
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.enter(module_id, func_name);
+        }
         self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())
     }
 
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.enter(module_id, func_name);
+        }
         self.charge_instr_with_size(
             Opcodes::CALL_GENERIC,
             ((ty_args.len() + args.len() + 1) as u64).into(),
@@ -417,7 +486,12 @@ impl<'b> GasMeter for GasStatus<'b> {
     }
 
     fn charge_native_function(&mut self, amount: InternalGas) -> PartialVMResult<()> {
-        self.deduct_gas(amount)
+        let res = self.deduct_gas(amount);
+        // Native functions return without a `Ret` instruction.
+        if let Some(profiler) = &mut self.profiler {
+            profiler.exit();
+        }
+        res
     }
 }
 
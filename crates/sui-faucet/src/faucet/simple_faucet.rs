@@ -473,7 +473,7 @@ impl Faucet for SimpleFaucet {
 
 #[cfg(test)]
 mod tests {
-    use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
+    use sui::client_commands::{SuiClientCommandResult, SuiClientCommands, TxBuildOptions};
     use test_utils::network::TestClusterBuilder;
 
     use super::*;
@@ -559,8 +559,9 @@ mod tests {
         // Now we transfer one gas out
         let res = SuiClientCommands::PayAllSui {
             input_coins: vec![*bad_gas.id()],
-            recipient: SuiAddress::random_for_testing_only(),
+            recipient: SuiAddress::random_for_testing_only().into(),
             gas_budget: 50000,
+            tx_options: TxBuildOptions::default(),
         }
         .execute(faucet.wallet_mut())
         .await
@@ -614,6 +615,7 @@ mod tests {
             gas_budget: 50000,
             gas: None,
             count: None,
+            tx_options: TxBuildOptions::default(),
         }
         .execute(&mut context)
         .await
@@ -684,7 +686,7 @@ mod tests {
     async fn get_current_gases(address: SuiAddress, context: &mut WalletContext) -> Vec<GasCoin> {
         // Get the latest list of gas
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(context)
         .await
@@ -34,8 +34,12 @@ module sui::genesis {
         validator_sui_addresses: vector<address>,
         validator_names: vector<vector<u8>>,
         validator_net_addresses: vector<vector<u8>>,
+        validator_descriptions: vector<vector<u8>>,
+        validator_image_urls: vector<vector<u8>>,
+        validator_project_urls: vector<vector<u8>>,
         validator_stakes: vector<u64>,
         validator_gas_prices: vector<u64>,
+        validator_commission_rates: vector<u64>,
         ctx: &mut TxContext,
     ) {
         let sui_supply = sui::new(ctx);
@@ -47,7 +51,11 @@ module sui::genesis {
                 && vector::length(&validator_stakes) == count
                 && vector::length(&validator_names) == count
                 && vector::length(&validator_net_addresses) == count
-                && vector::length(&validator_gas_prices) == count,
+                && vector::length(&validator_descriptions) == count
+                && vector::length(&validator_image_urls) == count
+                && vector::length(&validator_project_urls) == count
+                && vector::length(&validator_gas_prices) == count
+                && vector::length(&validator_commission_rates) == count,
             1
         );
         let i = 0;
@@ -58,8 +66,12 @@ module sui::genesis {
             let proof_of_possession = *vector::borrow(&validator_proof_of_possessions, i);
             let name = *vector::borrow(&validator_names, i);
             let net_address = *vector::borrow(&validator_net_addresses, i);
+            let description = *vector::borrow(&validator_descriptions, i);
+            let image_url = *vector::borrow(&validator_image_urls, i);
+            let project_url = *vector::borrow(&validator_project_urls, i);
             let stake = *vector::borrow(&validator_stakes, i);
             let gas_price = *vector::borrow(&validator_gas_prices, i);
+            let commission_rate = *vector::borrow(&validator_commission_rates, i);
             vector::push_back(&mut validators, validator::new(
                 sui_address,
                 pubkey,
@@ -67,9 +79,13 @@ module sui::genesis {
                 proof_of_possession,
                 name,
                 net_address,
+                description,
+                image_url,
+                project_url,
                 balance::increase_supply(&mut sui_supply, stake),
                 option::none(),
                 gas_price,
+                commission_rate,
                 ctx
             ));
             i = i + 1;
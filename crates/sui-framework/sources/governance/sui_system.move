@@ -114,8 +114,12 @@ module sui::sui_system {
         proof_of_possession: vector<u8>,
         name: vector<u8>,
         net_address: vector<u8>,
+        description: vector<u8>,
+        image_url: vector<u8>,
+        project_url: vector<u8>,
         stake: Coin<SUI>,
         gas_price: u64,
+        commission_rate: u64,
         ctx: &mut TxContext,
     ) {
         assert!(
@@ -134,9 +138,13 @@ module sui::sui_system {
             proof_of_possession,
             name,
             net_address,
+            description,
+            image_url,
+            project_url,
             coin::into_balance(stake),
             option::none(),
             gas_price,
+            commission_rate,
             ctx
         );
 
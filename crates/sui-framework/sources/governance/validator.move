@@ -43,12 +43,20 @@ module sui::validator {
         name: vector<u8>,
         /// The network address of the validator (could also contain extra info such as port, DNS and etc.).
         net_address: vector<u8>,
+        /// A description of the validator, for display.
+        description: vector<u8>,
+        /// The URL of an image representing the validator.
+        image_url: vector<u8>,
+        /// The URL of the validator's project or operator.
+        project_url: vector<u8>,
         /// Total amount of validator stake that would be active in the next epoch.
         next_epoch_stake: u64,
         /// Total amount of delegated stake that would be active in the next epoch.
         next_epoch_delegation: u64,
         /// This validator's gas price quote for the next epoch.
         next_epoch_gas_price: u64,
+        /// Commission charged on the rewards of delegators, in basis points.
+        commission_rate: u64,
     }
 
     struct Validator has store {
@@ -69,6 +77,15 @@ module sui::validator {
 
     const PROOF_OF_POSSESSION_DOMAIN: vector<u8> = vector[107, 111, 115, 107];
 
+    /// The proof of possession does not prove that the account address holds the validator key.
+    const EINVALID_PROOF_OF_POSSESSION: u64 = 1;
+
+    /// The commission rate is above 100%.
+    const EINVALID_COMMISSION_RATE: u64 = 2;
+
+    /// Commission rates are in basis points, so at most 100%.
+    const MAX_COMMISSION_RATE: u64 = 10000;
+
     fun verify_proof_of_possession(
         proof_of_possession: vector<u8>,
         sui_address: address,
@@ -82,7 +99,7 @@ module sui::validator {
         vector::append(&mut signed_bytes, address_bytes);
         assert!(
             bls12381_min_sig_verify_with_domain(&proof_of_possession, &pubkey_bytes, signed_bytes, PROOF_OF_POSSESSION_DOMAIN) == true,
-            0
+            EINVALID_PROOF_OF_POSSESSION
         );
     }
 
@@ -93,9 +110,13 @@ module sui::validator {
         proof_of_possession: vector<u8>,
         name: vector<u8>,
         net_address: vector<u8>,
+        description: vector<u8>,
+        image_url: vector<u8>,
+        project_url: vector<u8>,
         stake: Balance<SUI>,
         coin_locked_until_epoch: Option<EpochTimeLock>,
         gas_price: u64,
+        commission_rate: u64,
         ctx: &mut TxContext
     ): Validator {
         assert!(
@@ -103,6 +124,7 @@ module sui::validator {
             vector::length(&net_address) <= 128 && vector::length(&name) <= 128 && vector::length(&pubkey_bytes) <= 128,
             0
         );
+        assert!(commission_rate <= MAX_COMMISSION_RATE, EINVALID_COMMISSION_RATE);
         verify_proof_of_possession(
             proof_of_possession,
             sui_address,
@@ -120,9 +142,13 @@ module sui::validator {
                 proof_of_possession,
                 name,
                 net_address,
+                description,
+                image_url,
+                project_url,
                 next_epoch_stake: stake_amount,
                 next_epoch_delegation: 0,
                 next_epoch_gas_price: gas_price,
+                commission_rate,
             },
             stake_amount,
             pending_stake: 0,
@@ -259,6 +285,10 @@ module sui::validator {
         self.gas_price
     }
 
+    public fun commission_rate(self: &Validator): u64 {
+        self.metadata.commission_rate
+    }
+
     public fun is_duplicate(self: &Validator, other: &Validator): bool {
          self.metadata.sui_address == other.metadata.sui_address
             || self.metadata.name == other.metadata.name
@@ -297,9 +327,13 @@ module sui::validator {
                 proof_of_possession,
                 name,
                 net_address,
+                description: vector::empty(),
+                image_url: vector::empty(),
+                project_url: vector::empty(),
                 next_epoch_stake: stake_amount,
                 next_epoch_delegation: 0,
                 next_epoch_gas_price: gas_price,
+                commission_rate: 0,
             },
             stake_amount,
             pending_stake: 0,
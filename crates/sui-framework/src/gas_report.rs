@@ -0,0 +1,284 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas costs of Move unit tests, charged as Sui charges transactions: computation with the Sui
+//! bytecode cost table and storage per byte of the objects written by the test's transactions.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    io::Write,
+    ops::Mul,
+    path::Path,
+};
+
+use anyhow::anyhow;
+use move_binary_format::{
+    access::ModuleAccess,
+    errors::{VMError, VMResult},
+    CompiledModule,
+};
+use move_cli::base::test::UnitTestResult;
+use move_compiler::{
+    compiled_unit::CompiledUnitWithSource,
+    shared::{NumberFormat, NumericalAddress},
+    unit_test::{ExpectedFailure, TestCase},
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_algebra::{InternalGas, NumBytes},
+    identifier::Identifier,
+    vm_status::StatusCode,
+};
+use move_package::BuildConfig as MoveBuildConfig;
+use move_unit_test::UnitTestingConfig;
+use move_vm_runtime::{move_vm::MoveVM, native_extensions::NativeContextExtensions};
+use serde::Serialize;
+use sui_cost_tables::{
+    bytecode_tables::{GasProfiler, GasStatus, INITIAL_COST_SCHEDULE},
+    non_execution_tables::MAX_TX_GAS,
+    units_types::Gas,
+};
+use sui_framework_build::compiled_package::BuildConfig;
+use sui_types::{
+    base_types::TransactionDigest, gas::INIT_SUI_COST_TABLE, in_memory_storage::InMemoryStorage,
+    messages::InputObjects, object::Object, temporary_store::TemporaryStore, MOVE_STDLIB_ADDRESS,
+    SUI_FRAMEWORK_ADDRESS,
+};
+
+use crate::{
+    natives::{self, object_runtime::ObjectRuntime},
+    new_testing_object_runtime, MAX_UNIT_TEST_INSTRUCTIONS,
+};
+
+/// The gas charged for running a unit test.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TestGasCost {
+    /// The test function, as `address::module::function`.
+    pub test: String,
+    /// Whether the test aborted, in which case no storage is charged.
+    pub aborted: bool,
+    pub computation_cost: u64,
+    /// The storage cost of the objects written by the test's transactions. It is charged when a
+    /// transaction ends, so it is not attributed to functions.
+    pub storage_cost: u64,
+    /// The computation cost charged in the body of each function the test calls, not including
+    /// the functions it calls in turn.
+    pub functions: BTreeMap<String, u64>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct GasReport {
+    pub tests: Vec<TestGasCost>,
+}
+
+/// Run the unit tests of the package at `path` in a Move VM metered with the Sui cost table,
+/// writing whether each test passed to `writer` followed by the gas report.
+/// The tests run once, sequentially, and are not bound by the instruction limit of `config`: a
+/// test fails if it runs out of the gas of a transaction instead.
+pub fn run_move_unit_tests_with_gas_report<W: Write + Send>(
+    path: &Path,
+    build_config: MoveBuildConfig,
+    config: Option<UnitTestingConfig>,
+    preloaded_objects: Vec<Object>,
+    writer: &mut W,
+) -> anyhow::Result<(UnitTestResult, GasReport)> {
+    let config = config
+        .unwrap_or_else(|| UnitTestingConfig::default_with_bound(Some(MAX_UNIT_TEST_INSTRUCTIONS)));
+    let package = BuildConfig {
+        config: MoveBuildConfig {
+            test_mode: true,
+            ..build_config
+        },
+        run_bytecode_verifier: true,
+        print_diags_to_stderr: false,
+    }
+    .build(path.to_path_buf())?;
+
+    // The test functions, their arguments and expected failures are only known to the compiler,
+    // so compile the sources of the package again to plan the tests.
+    let test_plan = UnitTestingConfig {
+        source_files: source_files(package.package.root_compiled_units.iter()),
+        dep_files: source_files(
+            package
+                .package
+                .deps_compiled_units
+                .iter()
+                .map(|(_, unit)| unit),
+        ),
+        named_address_values: package
+            .package
+            .compiled_package_info
+            .address_alias_instantiation
+            .iter()
+            .map(|(name, address)| {
+                (
+                    name.to_string(),
+                    NumericalAddress::new(address.into_bytes(), NumberFormat::Hex),
+                )
+            })
+            .collect(),
+        ..config.clone()
+    }
+    .build_test_plan()
+    .ok_or_else(|| anyhow!("Cannot find the unit tests of {}", path.display()))?;
+
+    let mut packages: BTreeMap<AccountAddress, Vec<CompiledModule>> = BTreeMap::new();
+    for module in package.get_modules_and_deps() {
+        packages
+            .entry(*module.self_id().address())
+            .or_default()
+            .push(module.clone());
+    }
+    let store = InMemoryStorage::new(
+        packages
+            .into_values()
+            .map(|modules| Object::new_package(modules, TransactionDigest::genesis()))
+            .collect(),
+    );
+    let state_view = TemporaryStore::new(
+        store,
+        InputObjects::new(vec![]),
+        TransactionDigest::genesis(),
+    );
+    let vm = MoveVM::new(natives::all_natives(
+        MOVE_STDLIB_ADDRESS,
+        SUI_FRAMEWORK_ADDRESS,
+    ))?;
+
+    let mut report = GasReport::default();
+    let mut failed = 0;
+    for (module_id, module_tests) in &test_plan.module_tests {
+        for (function, test_case) in &module_tests.tests {
+            let test = format!("{}::{}", module_id.short_str_lossless(), function);
+            if matches!(&config.filter, Some(filter) if !test.contains(filter.as_str())) {
+                continue;
+            }
+
+            let mut extensions = NativeContextExtensions::default();
+            new_testing_object_runtime(&mut extensions, &preloaded_objects);
+            let mut session = vm.new_session_with_extensions(&state_view, extensions);
+            let mut gas_status = GasStatus::new(&INITIAL_COST_SCHEDULE, Gas::new(MAX_TX_GAS));
+            gas_status.set_profiler(GasProfiler::new(test.clone()));
+            let arguments = test_case
+                .arguments
+                .iter()
+                .map(|argument| argument.simple_serialize().unwrap())
+                .collect::<Vec<_>>();
+            let result = session.execute_function_bypass_visibility(
+                module_id,
+                &Identifier::new(function.as_str())?,
+                vec![],
+                arguments,
+                &mut gas_status,
+            );
+            let (_, _, mut extensions) = session.finish_with_extensions()?;
+            let object_runtime: ObjectRuntime = extensions.remove();
+
+            if test_passed(&result, test_case) {
+                writeln!(writer, "[ PASS    ] {test}")?;
+            } else {
+                failed += 1;
+                match &result {
+                    Ok(_) => writeln!(writer, "[ FAIL    ] {test}: expected to abort")?,
+                    Err(e) => writeln!(writer, "[ FAIL    ] {test}: {}", describe(e))?,
+                }
+            }
+
+            let aborted = result.is_err();
+            let storage_cost = if aborted {
+                0
+            } else {
+                let written = NumBytes::new(object_runtime.test_inventories.written_bytes as u64);
+                to_gas_units(written.mul(*INIT_SUI_COST_TABLE.storage_per_byte_cost))
+            };
+            let functions = gas_status
+                .take_profiler()
+                .map(|profiler| profiler.costs)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(function, cost)| (function, to_gas_units(cost)))
+                .collect();
+            report.tests.push(TestGasCost {
+                test,
+                aborted,
+                computation_cost: MAX_TX_GAS - u64::from(gas_status.remaining_gas()),
+                storage_cost,
+                functions,
+            });
+        }
+    }
+
+    let total = report.tests.len();
+    writeln!(
+        writer,
+        "Test result: {}. Total tests: {total}; passed: {}; failed: {failed}",
+        if failed == 0 { "OK" } else { "FAILED" },
+        total - failed
+    )?;
+    writeln!(writer, "{report}")?;
+    let result = if failed == 0 {
+        UnitTestResult::Success
+    } else {
+        UnitTestResult::Failure
+    };
+    Ok((result, report))
+}
+
+fn source_files<'a>(units: impl Iterator<Item = &'a CompiledUnitWithSource>) -> Vec<String> {
+    units
+        .map(|unit| unit.source_path.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Whether a test that ended with `result` passes, given the failure it expects.
+fn test_passed<T>(result: &VMResult<T>, test_case: &TestCase) -> bool {
+    match (result, &test_case.expected_failure) {
+        (Ok(_), expected_failure) => expected_failure.is_none(),
+        (Err(e), _) if e.major_status() == StatusCode::OUT_OF_GAS => false,
+        (Err(_), None) => false,
+        (Err(_), Some(ExpectedFailure::Expected)) => true,
+        (Err(e), Some(ExpectedFailure::ExpectedWithCode(code))) => {
+            e.major_status() == StatusCode::ABORTED && e.sub_status() == Some(*code)
+        }
+    }
+}
+
+fn describe(error: &VMError) -> String {
+    match (error.major_status(), error.sub_status()) {
+        (StatusCode::OUT_OF_GAS, _) => "exceeds the gas limit of a transaction".to_string(),
+        (StatusCode::ABORTED, Some(code)) => format!("aborted with code {code}"),
+        (status, _) => format!("failed with {status:?}"),
+    }
+}
+
+fn to_gas_units(cost: InternalGas) -> u64 {
+    let units: Gas = cost.to_unit_round_down();
+    units.into()
+}
+
+impl Display for GasReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Gas report:")?;
+        writeln!(f, "{:<60} {:>12} {:>12}", "Test", "Computation", "Storage")?;
+        for test in &self.tests {
+            let name = if test.aborted {
+                format!("{} (aborted)", test.test)
+            } else {
+                test.test.clone()
+            };
+            writeln!(
+                f,
+                "{:<60} {:>12} {:>12}",
+                name, test.computation_cost, test.storage_cost
+            )?;
+            let mut functions: Vec<_> = test.functions.iter().collect();
+            functions.sort_by(|a, b| b.1.cmp(a.1));
+            for (function, cost) in functions {
+                writeln!(f, "  {:<58} {:>12}", function, cost)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -9,15 +9,25 @@ use move_unit_test::{extensions::set_extension_hook, UnitTestingConfig};
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 use natives::object_runtime::ObjectRuntime;
 use once_cell::sync::Lazy;
-use std::{collections::BTreeMap, path::Path};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::Path,
+    sync::{Mutex, PoisonError, RwLock},
+};
 use sui_framework_build::compiled_package::{BuildConfig, CompiledPackage};
 use sui_types::{
-    base_types::TransactionDigest, error::SuiResult, in_memory_storage::InMemoryStorage,
-    messages::InputObjects, temporary_store::TemporaryStore, MOVE_STDLIB_ADDRESS,
-    SUI_FRAMEWORK_ADDRESS,
+    base_types::TransactionDigest,
+    error::SuiResult,
+    in_memory_storage::InMemoryStorage,
+    messages::InputObjects,
+    object::{Data, Object, Owner},
+    temporary_store::TemporaryStore,
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
 };
 
 pub mod cost_calib;
+pub mod gas_report;
 pub mod natives;
 
 // Move unit tests will halt after executing this many steps. This is a protection to avoid divergence
@@ -68,17 +78,44 @@ static MOVE_STDLIB_TEST: Lazy<Vec<CompiledModule>> = Lazy::new(|| {
         .collect()
 });
 
-static SET_EXTENSION_HOOK: Lazy<()> =
-    Lazy::new(|| set_extension_hook(Box::new(new_testing_object_runtime)));
+/// The objects every test of the current unit test run starts with.
+static PRELOADED_OBJECTS: Lazy<RwLock<Vec<Object>>> = Lazy::new(Default::default);
+
+/// Held for the duration of a unit test run, as all runs share the extension hook and the objects
+/// it preloads.
+static UNIT_TEST_RUN: Mutex<()> = Mutex::new(());
 
-fn new_testing_object_runtime(ext: &mut NativeContextExtensions) {
-    let store = InMemoryStorage::new(vec![]);
+static SET_EXTENSION_HOOK: Lazy<()> = Lazy::new(|| {
+    set_extension_hook(Box::new(|ext: &mut NativeContextExtensions| {
+        let preloaded_objects = PRELOADED_OBJECTS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        new_testing_object_runtime(ext, &preloaded_objects)
+    }))
+});
+
+fn new_testing_object_runtime(ext: &mut NativeContextExtensions, preloaded_objects: &[Object]) {
+    // Child objects are read through the store, the other objects are added to the test
+    // inventories by `test_scenario` when a test first looks for their type.
+    let (child_objects, objects): (Vec<_>, Vec<_>) = preloaded_objects
+        .iter()
+        .cloned()
+        .partition(|object| matches!(object.owner, Owner::ObjectOwner(_)));
+    let store = InMemoryStorage::new(child_objects);
     let state_view = TemporaryStore::new(
         store,
         InputObjects::new(vec![]),
         TransactionDigest::random(),
     );
-    ext.add(ObjectRuntime::new(Box::new(state_view), BTreeMap::new()))
+    let mut object_runtime = ObjectRuntime::new(Box::new(state_view), BTreeMap::new());
+    object_runtime.test_inventories.preloaded = objects
+        .into_iter()
+        .filter_map(|object| match object.data {
+            Data::Move(move_object) => Some((object.owner, move_object)),
+            Data::Package(_) => None,
+        })
+        .collect();
+    ext.add(object_runtime)
 }
 
 pub fn get_sui_framework() -> Vec<CompiledModule> {
@@ -122,12 +159,19 @@ pub fn legacy_length_cost() -> InternalGas {
 
 /// This function returns a result of UnitTestResult. The outer result indicates whether it
 /// successfully started running the test, and the inner result indicatests whether all tests pass.
-pub fn run_move_unit_tests(
+/// Every test starts with `preloaded_objects` in its storage.
+pub fn run_move_unit_tests<W: Write + Send>(
     path: &Path,
     build_config: MoveBuildConfig,
     config: Option<UnitTestingConfig>,
     compute_coverage: bool,
+    preloaded_objects: Vec<Object>,
+    writer: &mut W,
 ) -> anyhow::Result<UnitTestResult> {
+    let _run = UNIT_TEST_RUN.lock().unwrap_or_else(PoisonError::into_inner);
+    *PRELOADED_OBJECTS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = preloaded_objects;
     // bind the extension hook if it has not yet been done
     Lazy::force(&SET_EXTENSION_HOOK);
 
@@ -143,7 +187,7 @@ pub fn run_move_unit_tests(
         },
         natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS),
         compute_coverage,
-        &mut std::io::stdout(),
+        writer,
     )
 }
 
@@ -206,9 +250,40 @@ mod tests {
         check_move_unit_tests(&path);
     }
 
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    fn run_examples_move_unit_tests_with_gas_report() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../sui_programmability/examples")
+            .join("basics");
+        let (result, report) = gas_report::run_move_unit_tests_with_gas_report(
+            &path,
+            MoveBuildConfig::default(),
+            None,
+            vec![],
+            &mut std::io::stdout(),
+        )
+        .unwrap();
+        assert_eq!(result, UnitTestResult::Success);
+        assert!(!report.tests.is_empty());
+        for test in report.tests {
+            assert!(!test.aborted);
+            assert!(test.computation_cost > 0);
+            assert!(test.functions.contains_key(&test.test));
+        }
+    }
+
     fn check_move_unit_tests(path: &Path) {
         assert_eq!(
-            run_move_unit_tests(path, MoveBuildConfig::default(), None, false).unwrap(),
+            run_move_unit_tests(
+                path,
+                MoveBuildConfig::default(),
+                None,
+                false,
+                vec![],
+                &mut std::io::stdout()
+            )
+            .unwrap(),
             UnitTestResult::Success
         );
     }
@@ -50,6 +50,10 @@ pub(crate) struct TestInventories {
     pub(crate) taken_immutable_values: BTreeMap<Type, BTreeMap<ObjectID, Value>>,
     // object has been taken from the inventory
     pub(crate) taken: BTreeMap<ObjectID, Owner>,
+    // objects loaded from a network, added to the inventories when their type is first used
+    pub(crate) preloaded: Vec<(Owner, MoveObject)>,
+    // total size of the objects written by the transactions ended so far
+    pub(crate) written_bytes: usize,
 }
 
 pub struct RuntimeResults {
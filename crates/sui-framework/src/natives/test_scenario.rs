@@ -13,7 +13,7 @@ use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::{
     account_address::AccountAddress,
     identifier::Identifier,
-    language_storage::StructTag,
+    language_storage::{StructTag, TypeTag},
     value::{MoveStruct, MoveValue},
     vm_status::StatusCode,
 };
@@ -181,6 +181,13 @@ pub fn end_transaction(
             .all_active_child_objects()
             .map(|(id, ty, value)| (id, ty, value)),
     );
+    let written_bytes: usize = new_object_values
+        .values()
+        .filter_map(|(ty, value)| {
+            let layout = context.type_to_type_layout(ty).ok()??;
+            value.simple_serialize(&layout).map(|bytes| bytes.len())
+        })
+        .sum();
     // mark as "incorrect" if a shared/imm object was wrapped or is a child object
     incorrect_shared_or_imm_handling = incorrect_shared_or_imm_handling
         || taken_shared_or_imm
@@ -201,6 +208,7 @@ pub fn end_transaction(
 
     // new input objects are remaining taken objects not written/deleted
     let object_runtime_ref: &mut ObjectRuntime = context.extensions_mut().get_mut();
+    object_runtime_ref.test_inventories.written_bytes += written_bytes;
     object_runtime_ref.state.input_objects = object_runtime_ref
         .test_inventories
         .taken
@@ -257,6 +265,7 @@ pub fn take_from_address_by_id(
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
     let specified_ty = get_specified_ty(ty_args);
+    load_preloaded_objects(context, &specified_ty)?;
     let id = pop_id(&mut args)?;
     let account: SuiAddress = pop_arg!(args, AccountAddress).into();
     pop_arg!(args, StructRef);
@@ -291,6 +300,7 @@ pub fn ids_for_address(
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
     let specified_ty = get_specified_ty(ty_args);
+    load_preloaded_objects(context, &specified_ty)?;
     let account: SuiAddress = pop_arg!(args, AccountAddress).into();
     assert!(args.is_empty());
     let object_runtime: &mut ObjectRuntime = context.extensions_mut().get_mut();
@@ -317,6 +327,7 @@ pub fn most_recent_id_for_address(
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
     let specified_ty = get_specified_ty(ty_args);
+    load_preloaded_objects(context, &specified_ty)?;
     let account: SuiAddress = pop_arg!(args, AccountAddress).into();
     assert!(args.is_empty());
     let object_runtime: &mut ObjectRuntime = context.extensions_mut().get_mut();
@@ -361,6 +372,7 @@ pub fn take_immutable_by_id(
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
     let specified_ty = get_specified_ty(ty_args);
+    load_preloaded_objects(context, &specified_ty)?;
     let id = pop_id(&mut args)?;
     pop_arg!(args, StructRef);
     assert!(args.is_empty());
@@ -400,6 +412,7 @@ pub fn most_recent_immutable_id(
     args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
     let specified_ty = get_specified_ty(ty_args);
+    load_preloaded_objects(context, &specified_ty)?;
     assert!(args.is_empty());
     let object_runtime: &mut ObjectRuntime = context.extensions_mut().get_mut();
     let inventories = &mut object_runtime.test_inventories;
@@ -443,6 +456,7 @@ pub fn take_shared_by_id(
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
     let specified_ty = get_specified_ty(ty_args);
+    load_preloaded_objects(context, &specified_ty)?;
     let id = pop_id(&mut args)?;
     pop_arg!(args, StructRef);
     assert!(args.is_empty());
@@ -475,6 +489,7 @@ pub fn most_recent_id_shared(
     args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
     let specified_ty = get_specified_ty(ty_args);
+    load_preloaded_objects(context, &specified_ty)?;
     assert!(args.is_empty());
     let object_runtime: &mut ObjectRuntime = context.extensions_mut().get_mut();
     let inventories = &mut object_runtime.test_inventories;
@@ -554,6 +569,53 @@ fn most_recent_at_ty_opt(
     Some(pack_id(*most_recent_id))
 }
 
+// Add the preloaded objects of type `ty` to the inventories, the first time a test looks for them
+fn load_preloaded_objects(context: &mut NativeContext, ty: &Type) -> PartialVMResult<()> {
+    let object_runtime: &ObjectRuntime = context.extensions().get();
+    if object_runtime.test_inventories.preloaded.is_empty() {
+        return Ok(());
+    }
+    let tag = match context.type_to_type_tag(ty)? {
+        TypeTag::Struct(s) => s,
+        _ => return Ok(()),
+    };
+    let layout = match context.type_to_type_layout(ty)? {
+        Some(layout) => layout,
+        None => return Ok(()),
+    };
+    let object_runtime: &mut ObjectRuntime = context.extensions_mut().get_mut();
+    let inventories = &mut object_runtime.test_inventories;
+    let (loaded, preloaded): (Vec<_>, Vec<_>) = std::mem::take(&mut inventories.preloaded)
+        .into_iter()
+        .partition(|(_, object)| object.type_ == tag);
+    inventories.preloaded = preloaded;
+    for (owner, object) in loaded {
+        let id = object.id();
+        let value = match Value::simple_deserialize(object.contents(), &layout) {
+            Some(value) => value,
+            None => {
+                return Err(
+                    PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE)
+                        .with_message(format!("Cannot deserialize preloaded object {id}")),
+                )
+            }
+        };
+        let inventory = match owner {
+            Owner::AddressOwner(a) => inventories.address_inventories.entry(a).or_default(),
+            Owner::Shared { .. } => &mut inventories.shared_inventory,
+            Owner::Immutable => &mut inventories.immutable_inventory,
+            // child objects are read through the object store
+            Owner::ObjectOwner(_) => continue,
+        };
+        inventory
+            .entry(ty.clone())
+            .or_insert_with(Set::new)
+            .insert(id, ());
+        inventories.objects.insert(id, value);
+    }
+    Ok(())
+}
+
 fn get_specified_ty(mut ty_args: Vec<Type>) -> Type {
     assert!(ty_args.len() == 1);
     ty_args.pop().unwrap()
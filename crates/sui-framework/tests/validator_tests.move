@@ -29,13 +29,18 @@ module sui::validator_tests {
                 vector[150, 32, 70, 34, 231, 29, 255, 62, 248, 219, 245, 72, 85, 77, 190, 195, 251, 255, 166, 250, 229, 133, 29, 117, 17, 182, 0, 164, 162, 59, 36, 250, 78, 129, 8, 46, 106, 112, 197, 152, 219, 114, 241, 121, 242, 189, 75, 204],
                 b"Validator1",
                 x"FFFF",
+                b"Validator One",
+                b"https://validator-1.example.com/logo.png",
+                b"https://validator-1.example.com",
                 init_stake,
                 option::none(),
                 1,
+                500,
                 ctx
             );
             assert!(validator::stake_amount(&validator) == 10, 0);
             assert!(validator::sui_address(&validator) == sender, 0);
+            assert!(validator::commission_rate(&validator) == 500, 0);
 
             validator::destroy(validator, ctx);
         };
@@ -50,6 +55,67 @@ module sui::validator_tests {
         test_scenario::end(scenario_val);
     }
 
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_proof_of_possession_of_another_address() {
+        // The proof of possession is bound to @0x8feebb589ffa14667ff721b7cfb186cfad6530fc.
+        let sender = @0x1;
+        let scenario_val = test_scenario::begin(sender);
+        let scenario = &mut scenario_val;
+        let ctx = test_scenario::ctx(scenario);
+        let init_stake = coin::into_balance(coin::mint_for_testing(10, ctx));
+
+        let validator = validator::new(
+            sender,
+            vector[131, 117, 151, 65, 106, 116, 161, 1, 125, 44, 138, 143, 162, 193, 244, 241, 19, 159, 175, 120, 76, 35, 83, 213, 49, 79, 36, 21, 121, 79, 86, 242, 16, 1, 185, 176, 31, 191, 121, 156, 221, 167, 20, 33, 126, 19, 4, 105, 15, 229, 33, 187, 35, 99, 208, 103, 214, 176, 193, 196, 168, 154, 172, 78, 102, 5, 52, 113, 233, 213, 195, 23, 172, 220, 90, 232, 23, 17, 97, 66, 153, 105, 253, 219, 145, 125, 216, 254, 125, 49, 227, 8, 6, 206, 88, 13],
+            vector[171, 2, 39, 3, 139, 105, 166, 171, 153, 151, 102, 197, 151, 186, 140, 116, 114, 90, 213, 225, 20, 167, 60, 69, 203, 12, 180, 198, 9, 217, 117, 38],
+            vector[150, 32, 70, 34, 231, 29, 255, 62, 248, 219, 245, 72, 85, 77, 190, 195, 251, 255, 166, 250, 229, 133, 29, 117, 17, 182, 0, 164, 162, 59, 36, 250, 78, 129, 8, 46, 106, 112, 197, 152, 219, 114, 241, 121, 242, 189, 75, 204],
+            b"Validator1",
+            x"FFFF",
+            b"Validator One",
+            b"https://validator-1.example.com/logo.png",
+            b"https://validator-1.example.com",
+            init_stake,
+            option::none(),
+            1,
+            500,
+            ctx
+        );
+
+        validator::destroy(validator, ctx);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun test_commission_rate_above_100_percent() {
+        let sender = @0x8feebb589ffa14667ff721b7cfb186cfad6530fc;
+        let scenario_val = test_scenario::begin(sender);
+        let scenario = &mut scenario_val;
+        let ctx = test_scenario::ctx(scenario);
+        let init_stake = coin::into_balance(coin::mint_for_testing(10, ctx));
+
+        let validator = validator::new(
+            sender,
+            vector[131, 117, 151, 65, 106, 116, 161, 1, 125, 44, 138, 143, 162, 193, 244, 241, 19, 159, 175, 120, 76, 35, 83, 213, 49, 79, 36, 21, 121, 79, 86, 242, 16, 1, 185, 176, 31, 191, 121, 156, 221, 167, 20, 33, 126, 19, 4, 105, 15, 229, 33, 187, 35, 99, 208, 103, 214, 176, 193, 196, 168, 154, 172, 78, 102, 5, 52, 113, 233, 213, 195, 23, 172, 220, 90, 232, 23, 17, 97, 66, 153, 105, 253, 219, 145, 125, 216, 254, 125, 49, 227, 8, 6, 206, 88, 13],
+            vector[171, 2, 39, 3, 139, 105, 166, 171, 153, 151, 102, 197, 151, 186, 140, 116, 114, 90, 213, 225, 20, 167, 60, 69, 203, 12, 180, 198, 9, 217, 117, 38],
+            vector[150, 32, 70, 34, 231, 29, 255, 62, 248, 219, 245, 72, 85, 77, 190, 195, 251, 255, 166, 250, 229, 133, 29, 117, 17, 182, 0, 164, 162, 59, 36, 250, 78, 129, 8, 46, 106, 112, 197, 152, 219, 114, 241, 121, 242, 189, 75, 204],
+            b"Validator1",
+            x"FFFF",
+            b"",
+            b"",
+            b"",
+            init_stake,
+            option::none(),
+            1,
+            10001,
+            ctx
+        );
+
+        validator::destroy(validator, ctx);
+        test_scenario::end(scenario_val);
+    }
+
     #[test]
     fun test_pending_validator_flow() {
         let sender = @0x8feebb589ffa14667ff721b7cfb186cfad6530fc;
@@ -65,9 +131,13 @@ module sui::validator_tests {
             vector[150, 32, 70, 34, 231, 29, 255, 62, 248, 219, 245, 72, 85, 77, 190, 195, 251, 255, 166, 250, 229, 133, 29, 117, 17, 182, 0, 164, 162, 59, 36, 250, 78, 129, 8, 46, 106, 112, 197, 152, 219, 114, 241, 121, 242, 189, 75, 204],
             b"Validator1",
             x"FFFF",
+            b"Validator One",
+            b"https://validator-1.example.com/logo.png",
+            b"https://validator-1.example.com",
             init_stake,
             option::none(),
             1,
+            500,
             ctx
         );
 
@@ -30,12 +30,13 @@ use serde_with::serde_as;
 use tracing::warn;
 
 use fastcrypto::encoding::{Base64, Encoding};
+use multiaddr::Multiaddr;
 use sui_json::SuiJsonValue;
 use sui_types::base_types::{
-    ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
-    TransactionEffectsDigest,
+    AuthorityName, ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress,
+    TransactionDigest, TransactionEffectsDigest,
 };
-use sui_types::committee::EpochId;
+use sui_types::committee::{EpochId, StakeUnit};
 use sui_types::crypto::{AuthorityStrongQuorumSignInfo, SignableBytes, Signature};
 use sui_types::error::SuiError;
 use sui_types::event::{BalanceChangeType, Event, EventID};
@@ -49,10 +50,17 @@ use sui_types::messages::{
     SingleTransactionKind, TransactionData, TransactionEffects, TransactionKind,
     VerifiedCertificate,
 };
-use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+};
 use sui_types::move_package::{disassemble_modules, MovePackage};
 use sui_types::object::{
-    Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, PastObjectRead,
+    Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, OwnerChange, PastObjectRead,
+};
+use sui_types::object_proof::ObjectProof;
+use sui_types::sui_system_state::{
+    EpochStats, PendingDelegationEntry, StakingPool, SuiSystemState, Validator,
+    ValidatorEpochRewards, ValidatorMetadata,
 };
 use sui_types::{parse_sui_struct_tag, parse_sui_type_tag};
 
@@ -65,6 +73,31 @@ pub type TransactionsPage = Page<TransactionDigest, TransactionDigest>;
 
 pub type EventPage = Page<SuiEventEnvelope, EventID>;
 
+pub type ObjectHistoryPage = Page<SuiObjectHistoryEntry, SequenceNumber>;
+
+pub type OwnerChangePage = Page<OwnerChange, OwnerChangeID>;
+
+pub type EpochPage = Page<SuiEpochInfo, EpochId>;
+
+pub type EpochStatsPage = Page<SuiEpochStats, EpochId>;
+
+/// Identifies an ownership change by the object and the version written by the change.
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerChangeID {
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+}
+
+impl From<&OwnerChange> for OwnerChangeID {
+    fn from(change: &OwnerChange) -> Self {
+        Self {
+            object_id: change.object_id,
+            version: change.version,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub enum SuiMoveAbility {
     Copy,
@@ -949,6 +982,343 @@ impl Display for SuiParsedPublishResponse {
     }
 }
 
+/// A certificate and its effects in their BCS encoding, so that clients can check the
+/// signatures of the validators themselves instead of trusting the node.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[serde(rename = "RawTransaction")]
+pub struct SuiRawTransaction {
+    /// The BCS serialized `CertifiedTransaction`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub certificate: Vec<u8>,
+    /// The BCS serialized `TransactionEffects`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub effects: Vec<u8>,
+    /// The certified checkpoint including the transaction, None if it is not checkpointed yet.
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+impl SuiRawTransaction {
+    pub fn new(
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            certificate: bcs::to_bytes(certificate)?,
+            effects: bcs::to_bytes(effects)?,
+            checkpoint,
+        })
+    }
+
+    pub fn certificate(&self) -> Result<CertifiedTransaction, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.certificate)?)
+    }
+
+    pub fn effects(&self) -> Result<TransactionEffects, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.effects)?)
+    }
+}
+
+/// A certified checkpoint summary and its contents in their BCS encoding.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[serde(rename = "RawCheckpoint")]
+pub struct SuiRawCheckpoint {
+    /// The BCS serialized `CertifiedCheckpointSummary`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub summary: Vec<u8>,
+    /// The BCS serialized `CheckpointContents`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub contents: Vec<u8>,
+}
+
+impl SuiRawCheckpoint {
+    pub fn new(
+        summary: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            summary: bcs::to_bytes(summary)?,
+            contents: bcs::to_bytes(contents)?,
+        })
+    }
+
+    pub fn summary(&self) -> Result<CertifiedCheckpointSummary, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.summary)?)
+    }
+
+    pub fn contents(&self) -> Result<CheckpointContents, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.contents)?)
+    }
+}
+
+/// A BCS serialized `ObjectProof`, proving the state of an object at a version to anyone
+/// trusting the committee of the epoch of the proof's checkpoint.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[serde(rename = "RawObjectProof")]
+pub struct SuiRawObjectProof {
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub proof: Vec<u8>,
+}
+
+impl SuiRawObjectProof {
+    pub fn new(proof: &ObjectProof) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            proof: bcs::to_bytes(proof)?,
+        })
+    }
+
+    pub fn proof(&self) -> Result<ObjectProof, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.proof)?)
+    }
+}
+
+/// An epoch, its committee and the range of checkpoints it spans.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "EpochInfo", rename_all = "camelCase")]
+pub struct SuiEpochInfo {
+    pub epoch: EpochId,
+    pub committee: Vec<(AuthorityName, StakeUnit)>,
+    /// The first checkpoint of the epoch, None if the node does not know where it starts.
+    pub first_checkpoint: Option<CheckpointSequenceNumber>,
+    /// The checkpoint ending the epoch, None if the epoch is ongoing or the node does not know
+    /// where it ends.
+    pub last_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// The validator set of the current epoch, decoded from the Sui system state object.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "ValidatorSet", rename_all = "camelCase")]
+pub struct SuiValidatorSet {
+    pub epoch: EpochId,
+    pub reference_gas_price: u64,
+    pub validator_stake: u64,
+    pub delegation_stake: u64,
+    pub quorum_stake_threshold: u64,
+    pub active_validators: Vec<SuiValidator>,
+    pub pending_validators: Vec<SuiValidator>,
+    /// Indices in `active_validators` of the validators leaving at the end of the epoch.
+    pub pending_removals: Vec<u64>,
+    pub next_epoch_validators: Vec<SuiValidatorMetadata>,
+}
+
+impl From<SuiSystemState> for SuiValidatorSet {
+    fn from(state: SuiSystemState) -> Self {
+        let validators = state.validators;
+        Self {
+            epoch: state.epoch,
+            reference_gas_price: state.reference_gas_price,
+            validator_stake: validators.validator_stake,
+            delegation_stake: validators.delegation_stake,
+            quorum_stake_threshold: validators.quorum_stake_threshold,
+            active_validators: validators
+                .active_validators
+                .into_iter()
+                .map(SuiValidator::from)
+                .collect(),
+            pending_validators: validators
+                .pending_validators
+                .into_iter()
+                .map(SuiValidator::from)
+                .collect(),
+            pending_removals: validators.pending_removals,
+            next_epoch_validators: validators
+                .next_epoch_validators
+                .into_iter()
+                .map(SuiValidatorMetadata::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "Validator", rename_all = "camelCase")]
+pub struct SuiValidator {
+    pub metadata: SuiValidatorMetadata,
+    pub stake_amount: u64,
+    pub pending_stake: u64,
+    pub pending_withdraw: u64,
+    pub gas_price: u64,
+    pub delegation_staking_pool: SuiStakingPool,
+}
+
+impl From<Validator> for SuiValidator {
+    fn from(validator: Validator) -> Self {
+        Self {
+            metadata: validator.metadata.into(),
+            stake_amount: validator.stake_amount,
+            pending_stake: validator.pending_stake,
+            pending_withdraw: validator.pending_withdraw,
+            gas_price: validator.gas_price,
+            delegation_staking_pool: validator.delegation_staking_pool.into(),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "ValidatorMetadata", rename_all = "camelCase")]
+pub struct SuiValidatorMetadata {
+    pub sui_address: SuiAddress,
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub pubkey_bytes: Vec<u8>,
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub network_pubkey_bytes: Vec<u8>,
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub proof_of_possession_bytes: Vec<u8>,
+    pub name: String,
+    /// The network address of the validator, as a multiaddr.
+    pub net_address: String,
+    pub description: String,
+    pub image_url: String,
+    pub project_url: String,
+    pub next_epoch_stake: u64,
+    pub next_epoch_delegation: u64,
+    pub next_epoch_gas_price: u64,
+    /// Commission charged on the rewards of delegators, in basis points.
+    pub commission_rate: u64,
+}
+
+impl From<ValidatorMetadata> for SuiValidatorMetadata {
+    fn from(metadata: ValidatorMetadata) -> Self {
+        let net_address = match Multiaddr::try_from(metadata.net_address) {
+            Ok(address) => address.to_string(),
+            Err(e) => format!("<invalid multiaddr: {e}>"),
+        };
+        Self {
+            sui_address: metadata.sui_address.into(),
+            pubkey_bytes: metadata.pubkey_bytes,
+            network_pubkey_bytes: metadata.network_pubkey_bytes,
+            proof_of_possession_bytes: metadata.proof_of_possession_bytes,
+            name: String::from_utf8_lossy(&metadata.name).into_owned(),
+            net_address,
+            description: String::from_utf8_lossy(&metadata.description).into_owned(),
+            image_url: String::from_utf8_lossy(&metadata.image_url).into_owned(),
+            project_url: String::from_utf8_lossy(&metadata.project_url).into_owned(),
+            next_epoch_stake: metadata.next_epoch_stake,
+            next_epoch_delegation: metadata.next_epoch_delegation,
+            next_epoch_gas_price: metadata.next_epoch_gas_price,
+            commission_rate: metadata.commission_rate,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "StakingPool", rename_all = "camelCase")]
+pub struct SuiStakingPool {
+    pub validator_address: SuiAddress,
+    pub starting_epoch: EpochId,
+    pub epoch_starting_sui_balance: u64,
+    pub epoch_starting_delegation_token_supply: u64,
+    pub sui_balance: u64,
+    pub rewards_pool: u64,
+    pub delegation_token_supply: u64,
+    pub pending_delegations: Vec<SuiPendingDelegation>,
+}
+
+impl From<StakingPool> for SuiStakingPool {
+    fn from(pool: StakingPool) -> Self {
+        Self {
+            validator_address: pool.validator_address.into(),
+            starting_epoch: pool.starting_epoch,
+            epoch_starting_sui_balance: pool.epoch_starting_sui_balance,
+            epoch_starting_delegation_token_supply: pool.epoch_starting_delegation_token_supply,
+            sui_balance: pool.sui_balance,
+            rewards_pool: pool.rewards_pool.value(),
+            delegation_token_supply: pool.delegation_token_supply.value,
+            pending_delegations: pool
+                .pending_delegations
+                .into_iter()
+                .map(SuiPendingDelegation::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "PendingDelegation", rename_all = "camelCase")]
+pub struct SuiPendingDelegation {
+    pub delegator: SuiAddress,
+    pub sui_amount: u64,
+}
+
+impl From<PendingDelegationEntry> for SuiPendingDelegation {
+    fn from(entry: PendingDelegationEntry) -> Self {
+        Self {
+            delegator: entry.delegator.into(),
+            sui_amount: entry.sui_amount,
+        }
+    }
+}
+
+/// Gas and reward statistics of a finished epoch.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "EpochStats", rename_all = "camelCase")]
+pub struct SuiEpochStats {
+    pub epoch: EpochId,
+    /// The number of transactions checkpointed during the epoch, None if the node does not have
+    /// all the checkpoints of the epoch.
+    pub transaction_count: Option<u64>,
+    pub storage_charge: u64,
+    pub computation_charge: u64,
+    pub storage_rebate: u64,
+    pub reference_gas_price: u64,
+    pub validator_rewards: Vec<SuiValidatorEpochRewards>,
+}
+
+impl From<EpochStats> for SuiEpochStats {
+    fn from(stats: EpochStats) -> Self {
+        Self {
+            epoch: stats.epoch,
+            transaction_count: stats.transaction_count,
+            storage_charge: stats.storage_charge,
+            computation_charge: stats.computation_charge,
+            storage_rebate: stats.storage_rebate,
+            reference_gas_price: stats.reference_gas_price,
+            validator_rewards: stats
+                .validator_rewards
+                .into_iter()
+                .map(SuiValidatorEpochRewards::from)
+                .collect(),
+        }
+    }
+}
+
+/// The rewards distributed to a validator and its delegators at the end of an epoch.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "ValidatorEpochRewards", rename_all = "camelCase")]
+pub struct SuiValidatorEpochRewards {
+    pub sui_address: SuiAddress,
+    pub stake_reward: u64,
+    pub delegation_reward: u64,
+    /// The exchange rate of the delegation staking pool at the start of the next epoch:
+    /// `poolSuiBalance` SUI for `poolTokenSupply` delegation tokens.
+    pub pool_sui_balance: u64,
+    pub pool_token_supply: u64,
+}
+
+impl From<ValidatorEpochRewards> for SuiValidatorEpochRewards {
+    fn from(rewards: ValidatorEpochRewards) -> Self {
+        Self {
+            sui_address: rewards.sui_address.into(),
+            stake_reward: rewards.stake_reward,
+            delegation_reward: rewards.delegation_reward,
+            pool_sui_balance: rewards.pool_sui_balance,
+            pool_token_supply: rewards.pool_token_supply,
+        }
+    }
+}
+
 pub type GetObjectDataResponse = SuiObjectRead<SuiParsedData>;
 pub type GetRawObjectDataResponse = SuiObjectRead<SuiRawData>;
 
@@ -1007,6 +1377,7 @@ impl<T: SuiData> TryFrom<ObjectRead> for SuiObjectRead<T> {
 }
 
 pub type GetPastObjectDataResponse = SuiPastObjectRead<SuiParsedData>;
+pub type GetRawPastObjectDataResponse = SuiPastObjectRead<SuiRawData>;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(tag = "status", content = "details", rename = "ObjectRead")]
@@ -2581,6 +2952,18 @@ impl From<ObjectInfo> for SuiObjectInfo {
     }
 }
 
+/// One version of an object, together with the transaction that produced it.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Debug)]
+#[serde(rename = "ObjectHistoryEntry", rename_all = "camelCase")]
+pub struct SuiObjectHistoryEntry {
+    pub object_ref: SuiObjectRef,
+    /// The owner of the object at this version. None if the object was deleted or wrapped by
+    /// this version, or if the object data of this version has been pruned.
+    pub owner: Option<Owner>,
+    /// The transaction that created, mutated, wrapped or deleted the object at this version.
+    pub previous_transaction: TransactionDigest,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectExistsResponse {
@@ -9,14 +9,15 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::{MoveStruct, MoveValue};
 
-use fastcrypto::encoding::Base64;
+use fastcrypto::encoding::{Base64, Encoding};
 use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::gas_coin::GasCoin;
 use sui_types::object::MoveObject;
+use sui_types::sui_system_state::ValidatorMetadata;
 use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 
-use crate::{SuiMoveStruct, SuiMoveValue};
+use crate::{SuiMoveStruct, SuiMoveValue, SuiValidatorMetadata};
 
 #[test]
 fn test_move_value_to_sui_bytearray() {
@@ -159,3 +160,36 @@ fn test_serde() {
         )
     }
 }
+
+#[test]
+fn test_validator_metadata() {
+    let address = SuiAddress::random_for_testing_only();
+    let net_address: multiaddr::Multiaddr = "/dns/localhost/tcp/8080/http".parse().unwrap();
+    let metadata = ValidatorMetadata {
+        sui_address: address.into(),
+        pubkey_bytes: vec![1; 96],
+        network_pubkey_bytes: vec![2; 32],
+        proof_of_possession_bytes: vec![3; 48],
+        name: b"validator-0".to_vec(),
+        net_address: net_address.to_vec(),
+        description: b"The first validator".to_vec(),
+        image_url: vec![],
+        project_url: b"https://validator-0.example.com".to_vec(),
+        next_epoch_stake: 1,
+        next_epoch_delegation: 2,
+        next_epoch_gas_price: 3,
+        commission_rate: 500,
+    };
+
+    let metadata = SuiValidatorMetadata::from(metadata);
+    assert_eq!(metadata.sui_address, address);
+    assert_eq!(metadata.name, "validator-0");
+    assert_eq!(metadata.net_address, "/dns/localhost/tcp/8080/http");
+    assert_eq!(metadata.description, "The first validator");
+    assert_eq!(metadata.project_url, "https://validator-0.example.com");
+
+    let json = serde_json::to_value(&metadata).unwrap();
+    assert_eq!(json["netAddress"], "/dns/localhost/tcp/8080/http");
+    assert_eq!(json["pubkeyBytes"], Base64::encode(vec![1; 96]));
+    assert_eq!(json["commissionRate"], 500);
+}
@@ -10,11 +10,13 @@ use jsonrpsee_proc_macros::rpc;
 use fastcrypto::encoding::Base64;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    EventPage, GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse,
-    MoveFunctionArgType, RPCTransactionRequestParams, SuiEventEnvelope, SuiEventFilter,
+    EpochPage, EpochStatsPage, EventPage, GetObjectDataResponse, GetPastObjectDataResponse,
+    GetRawObjectDataResponse, GetRawPastObjectDataResponse, MoveFunctionArgType, ObjectHistoryPage,
+    OwnerChangeID, OwnerChangePage, RPCTransactionRequestParams, SuiEventEnvelope, SuiEventFilter,
     SuiExecuteTransactionResponse, SuiGasCostSummary, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionEffects,
-    SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag, TransactionBytes, TransactionsPage,
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiRawCheckpoint,
+    SuiRawObjectProof, SuiRawTransaction, SuiTransactionEffects, SuiTransactionFilter,
+    SuiTransactionResponse, SuiTypeTag, SuiValidatorSet, TransactionBytes, TransactionsPage,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
@@ -24,7 +26,8 @@ use sui_types::crypto::SignatureScheme;
 use sui_types::event::EventID;
 use sui_types::messages::CommitteeInfoResponse;
 use sui_types::messages::ExecuteTransactionRequestType;
-use sui_types::query::{EventQuery, TransactionQuery};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::query::{EventQuery, OwnerChangeQuery, TransactionQuery};
 
 /// Maximum number of events returned in an event query.
 /// This is equivalent to EVENT_QUERY_MAX_LIMIT in `sui-storage` crate.
@@ -188,6 +191,34 @@ pub trait RpcFullNodeReadApi {
         version: SequenceNumber,
     ) -> RpcResult<GetPastObjectDataResponse>;
 
+    /// Return the known versions of an object in increasing version order, each with its digest,
+    /// owner and the transaction that produced it.
+    /// Note that the owner of a version may be unavailable if the node has pruned it.
+    #[method(name = "getObjectHistory")]
+    async fn get_object_history(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// Optional paging cursor, the first version to return
+        cursor: Option<SequenceNumber>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectHistoryPage>;
+
+    /// Return the ownership changes of an object, or the ownership changes in which an address
+    /// was the previous or the new owner. Changes of an object are ordered by version, changes
+    /// of an address are ordered by object ID and then version.
+    #[method(name = "getOwnerChanges")]
+    async fn get_owner_changes(
+        &self,
+        /// the ownership change query criteria.
+        query: OwnerChangeQuery,
+        /// Optional paging cursor
+        cursor: Option<OwnerChangeID>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+    ) -> RpcResult<OwnerChangePage>;
+
     /// Return the committee information for the asked epoch
     #[method(name = "getCommitteeInfo")]
     async fn get_committee_info(
@@ -195,6 +226,34 @@ pub trait RpcFullNodeReadApi {
         /// The epoch of interest. If None, default to the latest epoch
         epoch: Option<EpochId>,
     ) -> RpcResult<CommitteeInfoResponse>;
+
+    /// Return the epochs known to the node in ascending order, each with its committee and the
+    /// range of checkpoints it spans.
+    #[method(name = "getEpochs")]
+    async fn get_epochs(
+        &self,
+        /// Optional paging cursor, the first epoch to return
+        cursor: Option<EpochId>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+    ) -> RpcResult<EpochPage>;
+
+    /// Return the validator set of the current epoch: the metadata, stake, delegation, gas price
+    /// and staking pool of every validator.
+    #[method(name = "getValidatorSet")]
+    async fn get_validator_set(&self) -> RpcResult<SuiValidatorSet>;
+
+    /// Return the gas and reward statistics of finished epochs in ascending order: the gas
+    /// charged during the epoch and the rewards distributed to every validator and its staking
+    /// pool when the epoch ended.
+    #[method(name = "getEpochStats")]
+    async fn get_epoch_stats(
+        &self,
+        /// Optional paging cursor, the first epoch to return
+        cursor: Option<EpochId>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+    ) -> RpcResult<EpochStatsPage>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Builder API")]
@@ -407,6 +466,55 @@ pub trait RpcBcsApi {
         /// the id of the object
         object_id: ObjectID,
     ) -> RpcResult<GetRawObjectDataResponse>;
+
+    /// Return the raw BCS serialized move object bytes of an object at a past version.
+    /// As with `tryGetPastObject`, the version may have been pruned by the node.
+    #[method(name = "getRawPastObject")]
+    async fn get_raw_past_object(
+        &self,
+        /// the id of the object
+        object_id: ObjectID,
+        /// the version of the object
+        version: SequenceNumber,
+    ) -> RpcResult<GetRawPastObjectDataResponse>;
+
+    /// Return the BCS serialized certificate and effects of a transaction, and the checkpoint
+    /// including it, so that the validator signatures can be verified by the caller.
+    #[method(name = "getRawTransaction")]
+    async fn get_raw_transaction(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiRawTransaction>;
+
+    /// Return the BCS serialized certified checkpoint summary and contents at a sequence number.
+    #[method(name = "getRawCheckpoint")]
+    async fn get_raw_checkpoint(
+        &self,
+        /// the sequence number of the checkpoint
+        sequence_number: CheckpointSequenceNumber,
+    ) -> RpcResult<SuiRawCheckpoint>;
+
+    /// Return the BCS serialized last certified checkpoint of an epoch, whose summary carries
+    /// the committee of the next epoch.
+    #[method(name = "getRawEpochChangeCheckpoint")]
+    async fn get_raw_epoch_change_checkpoint(
+        &self,
+        /// the epoch ended by the checkpoint
+        epoch: EpochId,
+    ) -> RpcResult<SuiRawCheckpoint>;
+
+    /// Return a BCS serialized proof of the state of an object at a version: the object, the
+    /// certificate and effects of the transaction that wrote it, and the certified checkpoint
+    /// including that transaction.
+    #[method(name = "getRawObjectProof")]
+    async fn get_raw_object_proof(
+        &self,
+        /// the id of the object
+        object_id: ObjectID,
+        /// the version of the object to prove
+        version: SequenceNumber,
+    ) -> RpcResult<SuiRawObjectProof>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Subscription")]
@@ -11,9 +11,18 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 use sui_core::authority::AuthorityState;
 use sui_core::gateway_state::GatewayClient;
-use sui_json_rpc_types::GetRawObjectDataResponse;
+use sui_json_rpc_types::{
+    GetRawObjectDataResponse, GetRawPastObjectDataResponse, SuiRawCheckpoint, SuiRawObjectProof,
+    SuiRawTransaction,
+};
 use sui_open_rpc::Module;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ExecutionDigests, ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::committee::EpochId;
+use sui_types::messages_checkpoint::{
+    AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents,
+    CheckpointSequenceNumber,
+};
+use sui_types::object_proof::ObjectProof;
 
 pub struct BcsApiImpl {
     client: ClientStateAdaptor,
@@ -52,6 +61,104 @@ impl ClientStateAdaptor {
                 .try_into(),
         }
     }
+
+    fn full_node(&self) -> Result<&Arc<AuthorityState>, anyhow::Error> {
+        match self {
+            ClientStateAdaptor::FullNode(state) => Ok(state),
+            ClientStateAdaptor::Gateway(_) => Err(anyhow!(
+                "Past objects, raw transactions, checkpoints and object proofs are only served by full nodes"
+            )),
+        }
+    }
+
+    async fn get_raw_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<GetRawPastObjectDataResponse, anyhow::Error> {
+        self.full_node()?
+            .get_past_object_read(&object_id, version)
+            .await?
+            .try_into()
+    }
+
+    async fn get_raw_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<SuiRawTransaction, anyhow::Error> {
+        let state = self.full_node()?;
+        let (certificate, effects) = state.get_transaction(digest).await?;
+        let checkpoint = state
+            .checkpoints
+            .lock()
+            .get_transaction_checkpoint(&ExecutionDigests::new(digest, effects.digest()))?;
+        SuiRawTransaction::new(&certificate.into_inner(), &effects, checkpoint)
+    }
+
+    fn get_certified_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<(CertifiedCheckpointSummary, CheckpointContents), anyhow::Error> {
+        let checkpoints = self.full_node()?.checkpoints.lock();
+        let summary = match checkpoints.get_checkpoint(sequence_number)? {
+            Some(AuthenticatedCheckpoint::Certified(summary)) => summary,
+            _ => return Err(anyhow!("Certified checkpoint {sequence_number} not found")),
+        };
+        let contents = checkpoints
+            .get_checkpoint_contents(sequence_number)?
+            .ok_or_else(|| anyhow!("Contents of checkpoint {sequence_number} not found"))?;
+        Ok((summary, contents))
+    }
+
+    fn get_raw_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<SuiRawCheckpoint, anyhow::Error> {
+        let (summary, contents) = self.get_certified_checkpoint(sequence_number)?;
+        SuiRawCheckpoint::new(&summary, &contents)
+    }
+
+    fn get_raw_epoch_change_checkpoint(
+        &self,
+        epoch: EpochId,
+    ) -> Result<SuiRawCheckpoint, anyhow::Error> {
+        let sequence_number = self
+            .full_node()?
+            .checkpoints
+            .lock()
+            .get_epoch_change_checkpoint(epoch)?
+            .ok_or_else(|| anyhow!("Epoch change checkpoint of epoch {epoch} not found"))?
+            .summary
+            .sequence_number;
+        self.get_raw_checkpoint(sequence_number)
+    }
+
+    async fn get_raw_object_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<SuiRawObjectProof, anyhow::Error> {
+        let state = self.full_node()?;
+        let object = state
+            .get_past_object_read(&object_id, version)
+            .await?
+            .into_object()?;
+        let digest = object.previous_transaction;
+        let (certificate, effects) = state.get_transaction(digest).await?;
+        let sequence_number = state
+            .checkpoints
+            .lock()
+            .get_transaction_checkpoint(&ExecutionDigests::new(digest, effects.digest()))?
+            .ok_or_else(|| anyhow!("Transaction {digest:?} is not checkpointed yet"))?;
+        let (checkpoint, contents) = self.get_certified_checkpoint(sequence_number)?;
+        SuiRawObjectProof::new(&ObjectProof {
+            object,
+            certificate: certificate.into_inner(),
+            effects,
+            checkpoint,
+            contents,
+        })
+    }
 }
 
 #[async_trait]
@@ -59,6 +166,37 @@ impl RpcBcsApiServer for BcsApiImpl {
     async fn get_raw_object(&self, object_id: ObjectID) -> RpcResult<GetRawObjectDataResponse> {
         Ok(self.client.get_raw_object(object_id).await?)
     }
+
+    async fn get_raw_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<GetRawPastObjectDataResponse> {
+        Ok(self.client.get_raw_past_object(object_id, version).await?)
+    }
+
+    async fn get_raw_transaction(&self, digest: TransactionDigest) -> RpcResult<SuiRawTransaction> {
+        Ok(self.client.get_raw_transaction(digest).await?)
+    }
+
+    async fn get_raw_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> RpcResult<SuiRawCheckpoint> {
+        Ok(self.client.get_raw_checkpoint(sequence_number)?)
+    }
+
+    async fn get_raw_epoch_change_checkpoint(&self, epoch: EpochId) -> RpcResult<SuiRawCheckpoint> {
+        Ok(self.client.get_raw_epoch_change_checkpoint(epoch)?)
+    }
+
+    async fn get_raw_object_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<SuiRawObjectProof> {
+        Ok(self.client.get_raw_object_proof(object_id, version).await?)
+    }
 }
 
 impl SuiRpcModule for BcsApiImpl {
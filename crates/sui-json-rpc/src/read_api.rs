@@ -14,9 +14,11 @@ use tap::TapFallible;
 use fastcrypto::encoding::Base64;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, GetPastObjectDataResponse, MoveFunctionArgType, ObjectValueKind, Page,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
-    SuiTransactionEffects, SuiTransactionResponse, TransactionsPage,
+    EpochPage, EpochStatsPage, GetObjectDataResponse, GetPastObjectDataResponse,
+    MoveFunctionArgType, ObjectHistoryPage, ObjectValueKind, OwnerChangeID, OwnerChangePage, Page,
+    SuiEpochInfo, SuiEpochStats, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiMoveNormalizedStruct, SuiObjectHistoryEntry, SuiObjectInfo, SuiTransactionEffects,
+    SuiTransactionResponse, SuiValidatorSet, TransactionsPage,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SequenceNumber;
@@ -27,7 +29,7 @@ use sui_types::crypto::{sha3_hash, SignableBytes};
 use sui_types::messages::{CommitteeInfoRequest, CommitteeInfoResponse, TransactionData};
 use sui_types::move_package::normalize_modules;
 use sui_types::object::{Data, ObjectRead, Owner};
-use sui_types::query::TransactionQuery;
+use sui_types::query::{OwnerChangeQuery, TransactionQuery};
 
 use tracing::debug;
 
@@ -292,12 +294,121 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
             .try_into()?)
     }
 
+    async fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectHistoryPage> {
+        let limit = cap_page_limit(limit)?;
+        // Retrieve 1 extra item for next cursor
+        let mut data = self
+            .state
+            .get_object_history(&object_id, cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(
+                |(object_ref, owner, previous_transaction)| SuiObjectHistoryEntry {
+                    object_ref: object_ref.into(),
+                    owner,
+                    previous_transaction,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        // extract next cursor
+        let next_cursor = data.get(limit).map(|entry| entry.object_ref.version);
+        data.truncate(limit);
+        Ok(Page { data, next_cursor })
+    }
+
+    async fn get_owner_changes(
+        &self,
+        query: OwnerChangeQuery,
+        cursor: Option<OwnerChangeID>,
+        limit: Option<usize>,
+    ) -> RpcResult<OwnerChangePage> {
+        let limit = cap_page_limit(limit)?;
+        // Retrieve 1 extra item for next cursor
+        let mut data = self
+            .state
+            .get_owner_changes(
+                query,
+                cursor.map(|cursor| (cursor.object_id, cursor.version)),
+                limit + 1,
+            )
+            .map_err(|e| anyhow!("{e}"))?;
+
+        // extract next cursor
+        let next_cursor = data.get(limit).map(OwnerChangeID::from);
+        data.truncate(limit);
+        Ok(Page { data, next_cursor })
+    }
+
     async fn get_committee_info(&self, epoch: Option<EpochId>) -> RpcResult<CommitteeInfoResponse> {
         Ok(self
             .state
             .handle_committee_info_request(&CommitteeInfoRequest { epoch })
             .map_err(|e| anyhow!("{e}"))?)
     }
+
+    async fn get_epochs(
+        &self,
+        cursor: Option<EpochId>,
+        limit: Option<usize>,
+    ) -> RpcResult<EpochPage> {
+        let limit = cap_page_limit(limit)?;
+        // Retrieve 1 extra item for next cursor
+        let mut data = self
+            .state
+            .get_epochs(cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(
+                |(committee, first_checkpoint, last_checkpoint)| SuiEpochInfo {
+                    epoch: committee.epoch,
+                    committee: committee.voting_rights,
+                    first_checkpoint,
+                    last_checkpoint,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        // extract next cursor
+        let next_cursor = data.get(limit).map(|info| info.epoch);
+        data.truncate(limit);
+        Ok(Page { data, next_cursor })
+    }
+
+    async fn get_validator_set(&self) -> RpcResult<SuiValidatorSet> {
+        Ok(self
+            .state
+            .get_sui_system_state_object()
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .into())
+    }
+
+    async fn get_epoch_stats(
+        &self,
+        cursor: Option<EpochId>,
+        limit: Option<usize>,
+    ) -> RpcResult<EpochStatsPage> {
+        let limit = cap_page_limit(limit)?;
+        // Retrieve 1 extra item for next cursor
+        let mut data = self
+            .state
+            .get_epoch_stats(cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(SuiEpochStats::from)
+            .collect::<Vec<_>>();
+
+        // extract next cursor
+        let next_cursor = data.get(limit).map(|stats| stats.epoch);
+        data.truncate(limit);
+        Ok(Page { data, next_cursor })
+    }
 }
 
 impl SuiRpcModule for FullNodeApi {
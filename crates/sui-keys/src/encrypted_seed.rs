@@ -0,0 +1,89 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::anyhow;
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use fastcrypto::encoding::{Base64, Encoding};
+use hmac::Hmac;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{SignatureScheme, SuiKeyPair};
+
+use crate::key_derive::{derivation_path_for_index, derive_key_pair_from_path};
+
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+const KEY_LENGTH: usize = 32;
+const PBKDF2_ROUNDS: u32 = 100_000;
+
+/// A BIP-39 seed encrypted with a password, from which accounts are derived by index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedSeed {
+    /// Base64 encoded salt of the PBKDF2 derivation of the encryption key from the password.
+    salt: String,
+    /// Base64 encoded AES-256-GCM nonce.
+    nonce: String,
+    /// Base64 encoded seed, encrypted.
+    ciphertext: String,
+}
+
+impl EncryptedSeed {
+    /// Generate a new 12 words mnemonic and encrypt its seed, returning the mnemonic phrase so
+    /// it can be backed up.
+    pub fn generate(password: &str) -> Result<(Self, String), anyhow::Error> {
+        let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+        let seed = Self::encrypt(Seed::new(&mnemonic, "").as_bytes(), password)?;
+        Ok((seed, mnemonic.phrase().to_string()))
+    }
+
+    pub fn encrypt(seed: &[u8], password: &str) -> Result<Self, anyhow::Error> {
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = cipher(password, &salt)?
+            .encrypt(Nonce::from_slice(&nonce), seed)
+            .map_err(|_| anyhow!("Failed to encrypt the seed"))?;
+        Ok(Self {
+            salt: Base64::encode(salt),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, password: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let salt = Base64::decode(&self.salt).map_err(|e| anyhow!(e))?;
+        let nonce = Base64::decode(&self.nonce).map_err(|e| anyhow!(e))?;
+        let ciphertext = Base64::decode(&self.ciphertext).map_err(|e| anyhow!(e))?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(anyhow!("Invalid nonce length {}", nonce.len()));
+        }
+        cipher(password, &salt)?
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| anyhow!("Failed to decrypt the seed, wrong password?"))
+    }
+
+    /// Derive the keypair of the account at `index` with the default derivation path of
+    /// `key_scheme`.
+    pub fn derive_key_pair(
+        &self,
+        password: &str,
+        key_scheme: &SignatureScheme,
+        index: u32,
+    ) -> Result<(SuiAddress, SuiKeyPair), anyhow::Error> {
+        let seed = self.decrypt(password)?;
+        let path = derivation_path_for_index(key_scheme, index)?;
+        Ok(derive_key_pair_from_path(&seed, Some(path), key_scheme)?)
+    }
+}
+
+fn cipher(password: &str, salt: &[u8]) -> Result<Aes256Gcm, anyhow::Error> {
+    let mut key = [0u8; KEY_LENGTH];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    Aes256Gcm::new_from_slice(&key).map_err(|e| anyhow!(e))
+}
@@ -52,6 +52,34 @@ pub fn derive_key_pair_from_path(
     }
 }
 
+/// The default derivation path of the account at `index`, i.e. the path of the address at
+/// `index` for the first account: m/44'/784'/0'/0'/{index}' for Ed25519 and m/54'/784'/0'/0/{index}
+/// for Secp256k1.
+pub fn derivation_path_for_index(
+    key_scheme: &SignatureScheme,
+    index: u32,
+) -> Result<DerivationPath, SuiError> {
+    let path = match key_scheme {
+        SignatureScheme::ED25519 => {
+            format!(
+                "m/{DERVIATION_PATH_PURPOSE_ED25519}'/{DERIVATION_PATH_COIN_TYPE}'/0'/0'/{index}'"
+            )
+        }
+        SignatureScheme::Secp256k1 => {
+            format!(
+                "m/{DERVIATION_PATH_PURPOSE_SECP256K1}'/{DERIVATION_PATH_COIN_TYPE}'/0'/0/{index}"
+            )
+        }
+        SignatureScheme::BLS12381 => {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "BLS is not supported for user key derivation".to_string(),
+            })
+        }
+    };
+    path.parse()
+        .map_err(|e: bip32::Error| SuiError::SignatureKeyGenError(e.to_string()))
+}
+
 pub fn validate_path(
     key_scheme: &SignatureScheme,
     path: Option<DerivationPath>,
@@ -33,6 +33,7 @@ pub enum Keystore {
 pub trait AccountKeystore: Send + Sync {
     fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error>;
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error>;
+    fn remove_key(&mut self, address: &SuiAddress) -> Result<SuiKeyPair, anyhow::Error>;
     fn keys(&self) -> Vec<PublicKey>;
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error>;
     fn addresses(&self) -> Vec<SuiAddress> {
@@ -143,6 +144,15 @@ impl AccountKeystore for FileBasedKeystore {
         Ok(())
     }
 
+    fn remove_key(&mut self, address: &SuiAddress) -> Result<SuiKeyPair, anyhow::Error> {
+        let keypair = self
+            .keys
+            .remove(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))?;
+        self.save()?;
+        Ok(keypair)
+    }
+
     fn keys(&self) -> Vec<PublicKey> {
         self.keys.values().map(|key| key.public()).collect()
     }
@@ -223,6 +233,12 @@ impl AccountKeystore for InMemKeystore {
         Ok(())
     }
 
+    fn remove_key(&mut self, address: &SuiAddress) -> Result<SuiKeyPair, anyhow::Error> {
+        self.keys
+            .remove(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))
+    }
+
     fn keys(&self) -> Vec<PublicKey> {
         self.keys.values().map(|key| key.public()).collect()
     }
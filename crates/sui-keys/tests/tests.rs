@@ -5,7 +5,9 @@ use std::str::FromStr;
 use sha3::{Digest, Sha3_256};
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::encrypted_seed::EncryptedSeed;
+use sui_keys::key_derive::derivation_path_for_index;
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
@@ -29,6 +31,30 @@ fn mnemonic_test() {
     assert_eq!(address, imported_address);
 }
 
+#[test]
+fn encrypted_seed_test() -> Result<(), anyhow::Error> {
+    let (seed, phrase) = EncryptedSeed::generate("password")?;
+    assert!(seed.decrypt("wrong password").is_err());
+
+    // Accounts derived from the seed are the ones imported from its mnemonic at the same index.
+    let mut keystore = Keystore::from(InMemKeystore::new(0));
+    for scheme in ["ed25519", "secp256k1"] {
+        for index in 0..3 {
+            let scheme = SignatureScheme::from_str(scheme)?;
+            let (address, _) = seed.derive_key_pair("password", &scheme, index)?;
+            let path = derivation_path_for_index(&scheme, index)?;
+            let imported_address = keystore.import_from_mnemonic(&phrase, scheme, Some(path))?;
+            assert_eq!(address, imported_address);
+        }
+    }
+
+    // The encrypted seed survives a serialization roundtrip.
+    let seed: EncryptedSeed = serde_json::from_str(&serde_json::to_string(&seed)?)?;
+    let (address, _) = seed.derive_key_pair("password", &SignatureScheme::ED25519, 0)?;
+    assert!(keystore.addresses().contains(&address));
+    Ok(())
+}
+
 /// This test confirms rust's implementation of mnemonic is the same with the Sui Wallet
 #[test]
 fn sui_wallet_address_mnemonic_test() -> Result<(), anyhow::Error> {
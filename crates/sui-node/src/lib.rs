@@ -15,10 +15,12 @@ use prometheus::Registry;
 use std::option::Option::None;
 use std::time::Instant;
 use std::{sync::Arc, time::Duration};
-use sui_config::NodeConfig;
+use sui_config::{FullNodeSyncMode, NodeConfig};
 use sui_core::authority_active::checkpoint_driver::CheckpointMetrics;
 use sui_core::authority_aggregator::{AuthAggMetrics, AuthorityAggregator};
 use sui_core::authority_server::ValidatorService;
+use sui_core::checkpoint_archive::{run_checkpoint_archive_process, CheckpointArchive};
+use sui_core::commit_sink::{CommitSinkStore, JsonLinesCommitSink};
 use sui_core::safe_client::SafeClientMetrics;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_core::transaction_streamer::TransactionStreamer;
@@ -73,6 +75,9 @@ pub struct SuiNode {
     _ws_subscription_service: Option<WsServerHandle>,
     _batch_subsystem_handle: tokio::task::JoinHandle<()>,
     _post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    _checkpoint_post_processing_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    _commit_sink_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    _checkpoint_archive_handle: Option<tokio::task::JoinHandle<Result<()>>>,
     _gossip_handle: Option<tokio::task::JoinHandle<()>>,
     _execute_driver_handle: tokio::task::JoinHandle<()>,
     _checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
@@ -240,7 +245,69 @@ impl SuiNode {
                 None
             };
 
-        let gossip_handle = if is_full_node {
+        let checkpoint_post_processing_handle = if index_store.is_some() {
+            let indexing_state = state.clone();
+            Some(spawn_monitored_task!(async move {
+                indexing_state
+                    .run_checkpoint_post_processing_process()
+                    .await
+                    .map_err(Into::into)
+            }))
+        } else {
+            None
+        };
+
+        let commit_sink_handle = match &config.commit_sink_dir {
+            Some(dir) => {
+                let sink = Arc::new(JsonLinesCommitSink::new(dir)?);
+                let store = Arc::new(CommitSinkStore::open_tables_read_write(
+                    config.db_path().join("commit_sinks"),
+                    None,
+                    None,
+                ));
+                let sink_state = state.clone();
+                Some(spawn_monitored_task!(async move {
+                    sink_state
+                        .run_commit_sink_process(sink, store)
+                        .await
+                        .map_err(Into::into)
+                }))
+            }
+            None => None,
+        };
+
+        let archive_sync = is_full_node && config.sync_mode == FullNodeSyncMode::Archive;
+        let checkpoint_archive_handle = match &config.checkpoint_archive_dir {
+            Some(dir) if !archive_sync => {
+                let archive = CheckpointArchive::new(dir)?;
+                Some(spawn_monitored_task!(run_checkpoint_archive_process(
+                    state.clone(),
+                    archive
+                )))
+            }
+            _ => None,
+        };
+
+        let gossip_handle = if is_full_node && config.sync_mode == FullNodeSyncMode::Checkpoint {
+            info!("Starting full node checkpoint sync");
+            Some(
+                active_authority
+                    .clone()
+                    .spawn_checkpoint_sync_process()
+                    .await,
+            )
+        } else if archive_sync {
+            let dir = config.checkpoint_archive_dir.as_ref().ok_or_else(|| {
+                anyhow!("sync-mode archive requires checkpoint-archive-dir to be set")
+            })?;
+            info!(?dir, "Starting full node archive sync");
+            Some(
+                active_authority
+                    .clone()
+                    .spawn_archive_sync_process(CheckpointArchive::new(dir)?)
+                    .await,
+            )
+        } else if is_full_node {
             info!("Starting full node sync to latest checkpoint (this may take a while)");
             let now = Instant::now();
             if let Err(err) = active_authority.clone().sync_to_latest_checkpoint().await {
@@ -360,6 +427,9 @@ impl SuiNode {
             _checkpoint_process_handle: checkpoint_process_handle,
             _batch_subsystem_handle: batch_subsystem_handle,
             _post_processing_subsystem_handle: post_processing_subsystem_handle,
+            _checkpoint_post_processing_handle: checkpoint_post_processing_handle,
+            _commit_sink_handle: commit_sink_handle,
+            _checkpoint_archive_handle: checkpoint_archive_handle,
             state,
             active: active_authority,
             transaction_orchestrator,
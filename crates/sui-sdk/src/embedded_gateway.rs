@@ -5,23 +5,29 @@ use std::path::Path;
 // TODO: Remove this file when sim test supports jsonrpc/ws
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 
 use sui_config::gateway::GatewayConfig;
 use sui_config::{NetworkConfig, PersistedConfig, SUI_NETWORK_CONFIG};
 use sui_core::gateway_state::{GatewayClient, GatewayState, TxSeqNumber};
 use sui_json_rpc_types::{
-    EventPage, GetObjectDataResponse, GetRawObjectDataResponse, SuiObjectInfo,
-    SuiTransactionResponse,
+    EpochStatsPage, EventPage, GetObjectDataResponse, GetRawObjectDataResponse, Page,
+    SuiObjectInfo, SuiTransactionResponse, TransactionsPage,
 };
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
 use sui_types::event::EventID;
 use sui_types::messages::{ExecuteTransactionRequestType, VerifiedTransaction};
-use sui_types::query::EventQuery;
+use sui_types::query::{EventQuery, TransactionQuery};
 
 use crate::TransactionExecutionResult;
 
+/// The number of transactions read from the gateway at once, within the limit of its range
+/// queries.
+const TX_RANGE_SIZE: u64 = 1000;
+
 #[derive(Clone)]
 pub struct SuiClient {
     transaction_builder: TransactionBuilder,
@@ -143,6 +149,90 @@ impl ReadApi {
     ) -> anyhow::Result<SuiTransactionResponse> {
         Ok(self.api.get_transaction(digest).await?)
     }
+
+    /// The gateway does not index transactions: queries by sender or recipient are answered by
+    /// scanning the transactions executed through it.
+    pub async fn get_transactions(
+        &self,
+        query: TransactionQuery,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> anyhow::Result<TransactionsPage> {
+        let total = self.api.get_total_transaction_number()?;
+        let mut digests = Vec::new();
+        let mut start = 0;
+        while start < total {
+            let end = total.min(start + TX_RANGE_SIZE);
+            digests.extend(self.api.get_transactions_in_range(start, end)?);
+            start = end;
+        }
+        if descending_order.unwrap_or_default() {
+            digests.reverse();
+        }
+        if let Some(cursor) = cursor {
+            let position = digests
+                .iter()
+                .position(|digest| *digest == cursor)
+                .ok_or_else(|| anyhow!("Transaction {cursor} not found"))?;
+            digests.drain(..position);
+        }
+
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut data = Vec::new();
+        for digest in digests {
+            let matches = match &query {
+                TransactionQuery::All => true,
+                TransactionQuery::FromAddress(address) => {
+                    self.api
+                        .get_transaction(digest)
+                        .await?
+                        .certificate
+                        .data
+                        .sender
+                        == *address
+                }
+                TransactionQuery::ToAddress(address) => {
+                    let effects = self.api.get_transaction(digest).await?.effects;
+                    effects
+                        .created
+                        .iter()
+                        .chain(&effects.mutated)
+                        .chain(&effects.unwrapped)
+                        .any(|object| object.owner.get_owner_address().ok() == Some(*address))
+                }
+                _ => {
+                    return Err(anyhow!(
+                        "Only transaction queries by sender or recipient are supported by the \
+                         embedded gateway"
+                    ))
+                }
+            };
+            if matches {
+                if data.len() == limit {
+                    return Ok(Page {
+                        data,
+                        next_cursor: Some(digest),
+                    });
+                }
+                data.push(digest);
+            }
+        }
+        Ok(Page {
+            data,
+            next_cursor: None,
+        })
+    }
+
+    pub async fn get_epoch_stats(
+        &self,
+        _cursor: Option<EpochId>,
+        _limit: Option<usize>,
+    ) -> anyhow::Result<EpochStatsPage> {
+        Err(anyhow!(
+            "Epoch stats are not supported by the embedded gateway"
+        ))
+    }
 }
 
 #[derive(Clone)]
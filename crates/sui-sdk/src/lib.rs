@@ -16,6 +16,8 @@ use jsonrpsee::core::client::{ClientT, Subscription};
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 
+use light_client::LightClient;
+
 use rpc_types::{
     GetPastObjectDataResponse, SuiCertifiedTransaction, SuiExecuteTransactionResponse,
     SuiParsedTransactionResponse, SuiTransactionEffects,
@@ -32,15 +34,16 @@ use sui_json_rpc::api::RpcReadApiClient;
 use sui_json_rpc::api::TransactionExecutionApiClient;
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    EventPage, GetObjectDataResponse, GetRawObjectDataResponse, SuiEventEnvelope, SuiEventFilter,
-    SuiObjectInfo, SuiTransactionResponse, TransactionsPage,
+    EpochPage, EpochStatsPage, EventPage, GetObjectDataResponse, GetRawObjectDataResponse,
+    ObjectHistoryPage, OwnerChangeID, OwnerChangePage, SuiEventEnvelope, SuiEventFilter,
+    SuiObjectInfo, SuiTransactionResponse, SuiValidatorSet, TransactionsPage,
 };
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::event::EventID;
 use sui_types::messages::VerifiedTransaction;
-use sui_types::query::{EventQuery, TransactionQuery};
+use sui_types::query::{EventQuery, OwnerChangeQuery, TransactionQuery};
 use types::base_types::SequenceNumber;
 use types::committee::EpochId;
 use types::error::TRANSACTION_NOT_FOUND_MSG_PREFIX;
@@ -48,6 +51,7 @@ use types::messages::{CommitteeInfoResponse, ExecuteTransactionRequestType};
 
 #[cfg(msim)]
 pub mod embedded_gateway;
+pub mod light_client;
 
 const WAIT_FOR_TX_TIMEOUT_SEC: u64 = 10;
 
@@ -188,6 +192,11 @@ impl SuiClient {
         };
         Ok(())
     }
+
+    /// A client verifying the data returned by the full node, trusting only `genesis_committee`.
+    pub fn light_client(&self, genesis_committee: types::committee::Committee) -> LightClient {
+        LightClient::new(self.api.clone(), genesis_committee)
+    }
 }
 
 #[derive(Debug)]
@@ -229,6 +238,32 @@ impl ReadApi {
             .await?)
     }
 
+    pub async fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<ObjectHistoryPage> {
+        Ok(self
+            .api
+            .http
+            .get_object_history(object_id, cursor, limit)
+            .await?)
+    }
+
+    pub async fn get_owner_changes(
+        &self,
+        query: OwnerChangeQuery,
+        cursor: Option<OwnerChangeID>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<OwnerChangePage> {
+        Ok(self
+            .api
+            .http
+            .get_owner_changes(query, cursor, limit)
+            .await?)
+    }
+
     pub async fn get_object(
         &self,
         object_id: ObjectID,
@@ -262,6 +297,26 @@ impl ReadApi {
         Ok(self.api.http.get_committee_info(epoch).await?)
     }
 
+    pub async fn get_epochs(
+        &self,
+        cursor: Option<EpochId>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<EpochPage> {
+        Ok(self.api.http.get_epochs(cursor, limit).await?)
+    }
+
+    pub async fn get_validator_set(&self) -> anyhow::Result<SuiValidatorSet> {
+        Ok(self.api.http.get_validator_set().await?)
+    }
+
+    pub async fn get_epoch_stats(
+        &self,
+        cursor: Option<EpochId>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<EpochStatsPage> {
+        Ok(self.api.http.get_epoch_stats(cursor, limit).await?)
+    }
+
     pub async fn get_transactions(
         &self,
         query: TransactionQuery,
@@ -0,0 +1,272 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A light client verifies what a single, untrusted full node returns against the signatures of
+//! the validators, starting from a trusted genesis committee.
+//!
+//! The committee of every later epoch is learnt from the certified checkpoint ending the
+//! previous epoch, whose summary carries the next committee, and is checked against the
+//! `CommitteeInfo` reported by the node. Checkpoints are verified against the committee of
+//! their epoch, certificates against the committee that certified them, and effects are only
+//! accepted once a verified checkpoint commits to their digest.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, ensure};
+use sui_json_rpc::api::{RpcBcsApiClient, RpcFullNodeReadApiClient};
+use sui_types::base_types::{ExecutionDigests, ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::messages::{TransactionEffects, VerifiedCertificate};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+};
+use sui_types::object_proof::ObjectProof;
+
+use crate::RpcClient;
+
+/// A transaction whose certificate and effects have been verified.
+#[derive(Debug)]
+pub struct VerifiedTransactionResponse {
+    pub certificate: VerifiedCertificate,
+    pub effects: TransactionEffects,
+    /// The verified checkpoint committing to the effects.
+    pub checkpoint: CertifiedCheckpointSummary,
+}
+
+pub struct LightClient {
+    api: Arc<RpcClient>,
+    /// The verified committees, from genesis up to the latest epoch learnt so far.
+    committees: RwLock<BTreeMap<EpochId, Committee>>,
+}
+
+impl LightClient {
+    pub(crate) fn new(api: Arc<RpcClient>, genesis_committee: Committee) -> Self {
+        Self {
+            api,
+            committees: RwLock::new(BTreeMap::from([(
+                genesis_committee.epoch,
+                genesis_committee,
+            )])),
+        }
+    }
+
+    /// The latest epoch whose committee has been verified.
+    pub fn latest_known_epoch(&self) -> EpochId {
+        *self.committees.read().unwrap().keys().last().unwrap()
+    }
+
+    /// Returns the verified committee of `epoch`, following the epoch change checkpoints from
+    /// the latest known committee if needed.
+    pub async fn get_committee(&self, epoch: EpochId) -> anyhow::Result<Committee> {
+        loop {
+            let latest = {
+                let committees = self.committees.read().unwrap();
+                if let Some(committee) = committees.get(&epoch) {
+                    return Ok(committee.clone());
+                }
+                let (latest_epoch, latest) = committees.iter().last().unwrap();
+                ensure!(
+                    epoch > *latest_epoch,
+                    "Epoch {epoch} is before the trusted genesis committee"
+                );
+                latest.clone()
+            };
+            let next = self.verify_epoch_change(&latest).await?;
+            self.committees.write().unwrap().insert(next.epoch, next);
+        }
+    }
+
+    /// Verify the checkpoint ending the epoch of `committee`, and return the committee of the
+    /// next epoch it carries.
+    async fn verify_epoch_change(&self, committee: &Committee) -> anyhow::Result<Committee> {
+        let raw = self
+            .api
+            .http
+            .get_raw_epoch_change_checkpoint(committee.epoch)
+            .await?;
+        let next = verify_epoch_change_checkpoint(committee, raw.summary()?)?;
+
+        let info = self.api.http.get_committee_info(Some(next.epoch)).await?;
+        if let Some(reported) = info.committee_info {
+            let reported = Committee::new(next.epoch, reported.into_iter().collect())?;
+            ensure!(
+                reported.voting_rights == next.voting_rights,
+                "Committee of epoch {} reported by the node differs from the certified one",
+                next.epoch
+            );
+        }
+        Ok(next)
+    }
+
+    /// Fetch a checkpoint and its contents, verified against the committee of its epoch.
+    pub async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> anyhow::Result<(CertifiedCheckpointSummary, CheckpointContents)> {
+        let raw = self.api.http.get_raw_checkpoint(sequence_number).await?;
+        let (checkpoint, contents) = (raw.summary()?, raw.contents()?);
+        ensure!(
+            checkpoint.summary.sequence_number == sequence_number,
+            "Node returned checkpoint {} instead of {sequence_number}",
+            checkpoint.summary.sequence_number
+        );
+        let committee = self.get_committee(checkpoint.summary.epoch).await?;
+        checkpoint.verify(&committee, Some(&contents))?;
+        Ok((checkpoint, contents))
+    }
+
+    /// Fetch a transaction, verify its certificate and check that its effects are committed to
+    /// by a verified checkpoint. Fails for transactions that are not checkpointed yet.
+    pub async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<VerifiedTransactionResponse> {
+        let raw = self.api.http.get_raw_transaction(digest).await?;
+        let certificate = raw.certificate()?;
+        ensure!(
+            *certificate.digest() == digest,
+            "Node returned transaction {:?} instead of {:?}",
+            certificate.digest(),
+            digest
+        );
+        let committee = self.get_committee(certificate.epoch()).await?;
+        let certificate = certificate.verify(&committee)?;
+
+        let effects = raw.effects()?;
+        let sequence_number = raw.checkpoint.ok_or_else(|| {
+            anyhow!("Transaction {digest:?} is not checkpointed yet, its effects can't be verified")
+        })?;
+        let (checkpoint, contents) = self.get_checkpoint(sequence_number).await?;
+        let digests = ExecutionDigests::new(digest, effects.digest());
+        ensure!(
+            contents.iter().any(|d| *d == digests),
+            "Effects of {digest:?} are not included in checkpoint {sequence_number}"
+        );
+
+        Ok(VerifiedTransactionResponse {
+            certificate,
+            effects,
+            checkpoint,
+        })
+    }
+
+    /// Fetch and verify the proof of the state of object `object_id` at `version`. The proven
+    /// object is `proof.object`, and the proof can be handed to third parties trusting the same
+    /// committee.
+    pub async fn get_object_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> anyhow::Result<ObjectProof> {
+        let proof = self
+            .api
+            .http
+            .get_raw_object_proof(object_id, version)
+            .await?
+            .proof()?;
+        let committee = self.get_committee(proof.checkpoint.summary.epoch).await?;
+        let (id, proven_version, _) = proof.verify(&committee)?;
+        ensure!(
+            id == object_id && proven_version == version,
+            "Node returned a proof of {id:?} at version {proven_version} instead of {object_id:?} at {version}"
+        );
+        Ok(proof)
+    }
+}
+
+/// Verify that `checkpoint` is certified by `committee` and ends its epoch, and return the
+/// committee of the next epoch it carries.
+fn verify_epoch_change_checkpoint(
+    committee: &Committee,
+    checkpoint: CertifiedCheckpointSummary,
+) -> anyhow::Result<Committee> {
+    ensure!(
+        checkpoint.summary.epoch == committee.epoch,
+        "Node returned a checkpoint of epoch {} as the end of epoch {}",
+        checkpoint.summary.epoch,
+        committee.epoch
+    );
+    checkpoint.verify(committee, None)?;
+    let voting_rights = checkpoint
+        .summary
+        .next_epoch_committee
+        .ok_or_else(|| anyhow!("Checkpoint does not end epoch {}", committee.epoch))?;
+    Ok(Committee::new(
+        committee.epoch + 1,
+        voting_rights.into_iter().collect(),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use sui_types::crypto::{AuthorityKeyPair, KeypairTraits};
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::SignedCheckpointSummary;
+    use sui_types::utils::make_committee_key;
+
+    use super::*;
+
+    fn certify(
+        keys: &[AuthorityKeyPair],
+        committee: &Committee,
+        next_epoch_committee: Option<Committee>,
+    ) -> CertifiedCheckpointSummary {
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+        let signed = keys
+            .iter()
+            .map(|key| {
+                SignedCheckpointSummary::new(
+                    committee.epoch,
+                    0,
+                    key.public().into(),
+                    key,
+                    &contents,
+                    None,
+                    GasCostSummary::default(),
+                    next_epoch_committee.clone(),
+                )
+            })
+            .collect();
+        CertifiedCheckpointSummary::aggregate(signed, committee).unwrap()
+    }
+
+    #[test]
+    fn test_verify_epoch_change_checkpoint() {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (keys, committee) = make_committee_key(&mut rng);
+        let (_, next_committee) = make_committee_key(&mut rng);
+
+        let checkpoint = certify(&keys, &committee, Some(next_committee.clone()));
+        let next = verify_epoch_change_checkpoint(&committee, checkpoint).unwrap();
+        assert_eq!(next.epoch, 1);
+        assert_eq!(next.voting_rights, next_committee.voting_rights);
+
+        // A checkpoint that doesn't end the epoch carries no committee.
+        let checkpoint = certify(&keys, &committee, None);
+        assert!(verify_epoch_change_checkpoint(&committee, checkpoint).is_err());
+    }
+
+    #[test]
+    fn test_verify_epoch_change_checkpoint_rejects_forgeries() {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (keys, committee) = make_committee_key(&mut rng);
+        let (forged_keys, forged_committee) = make_committee_key(&mut rng);
+
+        // Certified by a committee other than the trusted one.
+        let checkpoint = certify(
+            &forged_keys,
+            &forged_committee,
+            Some(forged_committee.clone()),
+        );
+        assert!(verify_epoch_change_checkpoint(&committee, checkpoint).is_err());
+
+        // Certified by the keys of the trusted committee, but for a later epoch.
+        let later_committee =
+            Committee::new(1, committee.voting_rights.iter().cloned().collect()).unwrap();
+        let checkpoint = certify(&keys, &later_committee, Some(forged_committee));
+        assert!(verify_epoch_change_checkpoint(&committee, checkpoint).is_err());
+    }
+}
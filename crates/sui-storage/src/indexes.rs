@@ -15,11 +15,15 @@ use typed_store_derive::DBMapUtils;
 use sui_types::base_types::ObjectRef;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
-use sui_types::error::SuiResult;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 
 use crate::default_db_options;
 
+pub type IndexLabel = usize;
+const LAST_INDEXED_CHECKPOINT: IndexLabel = 0;
+
 #[derive(DBMapUtils)]
 pub struct IndexStore {
     /// Index from sui address to transactions initiated by that address.
@@ -43,6 +47,17 @@ pub struct IndexStore {
     transactions_by_move_function:
         DBMap<(ObjectID, String, String, TxSequenceNumber), TransactionDigest>,
 
+    /// Index from move event struct type (e.g. `0x2::devnet_nft::MintNFTEvent`, including type
+    /// params) to transactions that emitted an event of that type.
+    #[default_options_override_fn = "transactions_by_move_event_table_default_config"]
+    transactions_by_move_event: DBMap<(String, TxSequenceNumber), TransactionDigest>,
+
+    /// Index from package id to transactions that interacted with that package, either by
+    /// publishing it, calling into it directly, or through calls from other packages that emitted
+    /// events from its modules.
+    #[default_options_override_fn = "transactions_by_package_table_default_config"]
+    transactions_by_package: DBMap<(ObjectID, TxSequenceNumber), TransactionDigest>,
+
     /// This is a map between the transaction digest and its timestamp (UTC timestamp in
     /// **milliseconds** since epoch 1/1/1970). A transaction digest is subjectively time stamped
     /// on a node according to the local machine time, so it varies across nodes.
@@ -50,6 +65,22 @@ pub struct IndexStore {
     #[default_options_override_fn = "timestamps_table_default_config"]
     timestamps: DBMap<TransactionDigest, u64>,
 
+    /// Index from timestamp (UTC timestamp in **milliseconds**, as recorded in `timestamps`) to
+    /// transactions, used to answer time range queries.
+    #[default_options_override_fn = "transactions_by_timestamp_table_default_config"]
+    transactions_by_timestamp: DBMap<(u64, TxSequenceNumber), TransactionDigest>,
+
+    /// Index from checkpoint sequence number to the transactions contained in that checkpoint.
+    /// A transaction is added when its checkpoint is indexed if it has already been executed
+    /// locally, or when it is executed if its checkpoint is already known.
+    #[default_options_override_fn = "transactions_by_checkpoint_table_default_config"]
+    transactions_by_checkpoint:
+        DBMap<(CheckpointSequenceNumber, TxSequenceNumber), TransactionDigest>,
+
+    /// The sequence number of the last checkpoint indexed in `transactions_by_checkpoint`, under
+    /// the key `LAST_INDEXED_CHECKPOINT`. Every checkpoint up to it has been indexed.
+    last_indexed_checkpoint: DBMap<IndexLabel, CheckpointSequenceNumber>,
+
     /// Index from transaction digest to sequence number.
     #[default_options_override_fn = "transactions_seq_table_default_config"]
     transactions_seq: DBMap<TransactionDigest, TxSequenceNumber>,
@@ -74,9 +105,21 @@ fn transactions_by_mutated_object_id_table_default_config() -> DBOptions {
 fn transactions_by_move_function_table_default_config() -> DBOptions {
     default_db_options(None, Some(1_000_000)).0
 }
+fn transactions_by_move_event_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn transactions_by_package_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
 fn timestamps_table_default_config() -> DBOptions {
     default_db_options(None, Some(1_000_000)).1
 }
+fn transactions_by_timestamp_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn transactions_by_checkpoint_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
 
 impl IndexStore {
     pub fn index_tx(
@@ -85,6 +128,8 @@ impl IndexStore {
         active_inputs: impl Iterator<Item = ObjectID>,
         mutated_objects: impl Iterator<Item = (ObjectRef, Owner)> + Clone,
         move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)> + Clone,
+        move_events: impl Iterator<Item = String>,
+        packages: impl Iterator<Item = ObjectID>,
         sequence: TxSequenceNumber,
         digest: &TransactionDigest,
         timestamp_ms: u64,
@@ -121,6 +166,16 @@ impl IndexStore {
             }),
         )?;
 
+        let batch = batch.insert_batch(
+            &self.transactions_by_move_event,
+            move_events.map(|event_type| ((event_type, sequence), *digest)),
+        )?;
+
+        let batch = batch.insert_batch(
+            &self.transactions_by_package,
+            packages.map(|package| ((package, sequence), *digest)),
+        )?;
+
         let batch = batch.insert_batch(
             &self.transactions_to_addr,
             mutated_objects.filter_map(|(_, owner)| {
@@ -134,11 +189,116 @@ impl IndexStore {
         let batch =
             batch.insert_batch(&self.timestamps, std::iter::once((*digest, timestamp_ms)))?;
 
+        let batch = batch.insert_batch(
+            &self.transactions_by_timestamp,
+            std::iter::once(((timestamp_ms, sequence), *digest)),
+        )?;
+
         batch.write()?;
 
         Ok(())
     }
 
+    /// Records that the given (already indexed) transactions are part of checkpoint `checkpoint`,
+    /// and that every checkpoint up to `checkpoint` has now been indexed.
+    pub fn index_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        transactions: impl Iterator<Item = (TxSequenceNumber, TransactionDigest)>,
+    ) -> SuiResult {
+        let batch = self.transactions_by_checkpoint.batch();
+        let batch = batch.insert_batch(
+            &self.transactions_by_checkpoint,
+            transactions.map(|(seq, digest)| ((checkpoint, seq), digest)),
+        )?;
+        let batch = batch.insert_batch(
+            &self.last_indexed_checkpoint,
+            std::iter::once((LAST_INDEXED_CHECKPOINT, checkpoint)),
+        )?;
+        batch.write()?;
+        Ok(())
+    }
+
+    /// Records that a transaction executed after its checkpoint was indexed is part of it.
+    pub fn index_checkpoint_tx(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        sequence: TxSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> SuiResult {
+        self.transactions_by_checkpoint
+            .insert(&(checkpoint, sequence), digest)?;
+        Ok(())
+    }
+
+    /// Returns the sequence number of the last checkpoint indexed by `index_checkpoint`, if any.
+    pub fn last_indexed_checkpoint(&self) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self.last_indexed_checkpoint.get(&LAST_INDEXED_CHECKPOINT)?)
+    }
+
+    /// Removes every index entry of a transaction, e.g. after its execution has been reverted.
+    /// Entries keyed by object, address, function, etc. are found by scanning the whole index,
+    /// so this is only meant for offline repairs. Returns false if the transaction isn't indexed.
+    pub fn remove_tx(&self, digest: &TransactionDigest) -> SuiResult<bool> {
+        if self.transactions_seq.get(digest)?.is_none() {
+            return Ok(false);
+        }
+
+        fn entries_of<K: Serialize + DeserializeOwned>(
+            index: &DBMap<K, TransactionDigest>,
+            digest: &TransactionDigest,
+        ) -> Vec<K> {
+            index
+                .iter()
+                .filter(|(_, tx)| tx == digest)
+                .map(|(key, _)| key)
+                .collect()
+        }
+
+        let batch = self.transactions_seq.batch();
+        let batch = batch.delete_batch(&self.transactions_seq, std::iter::once(*digest))?;
+        let batch = batch.delete_batch(&self.timestamps, std::iter::once(*digest))?;
+        let batch = batch.delete_batch(
+            &self.transactions_from_addr,
+            entries_of(&self.transactions_from_addr, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_to_addr,
+            entries_of(&self.transactions_to_addr, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_by_input_object_id,
+            entries_of(&self.transactions_by_input_object_id, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_by_mutated_object_id,
+            entries_of(&self.transactions_by_mutated_object_id, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_by_move_function,
+            entries_of(&self.transactions_by_move_function, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_by_move_event,
+            entries_of(&self.transactions_by_move_event, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_by_package,
+            entries_of(&self.transactions_by_package, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_by_timestamp,
+            entries_of(&self.transactions_by_timestamp, digest),
+        )?;
+        let batch = batch.delete_batch(
+            &self.transactions_by_checkpoint,
+            entries_of(&self.transactions_by_checkpoint, digest),
+        )?;
+        batch.write()?;
+
+        Ok(true)
+    }
+
     /// Returns unix timestamp for a transaction if it exists
     pub fn get_timestamp_ms(
         &self,
@@ -277,6 +437,38 @@ impl IndexStore {
         })
     }
 
+    pub fn get_transactions_by_move_event(
+        &self,
+        event_type: String,
+        cursor: TxSequenceNumber,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        Self::get_transactions_from_index(
+            &self.transactions_by_move_event,
+            event_type,
+            cursor,
+            limit,
+            reverse,
+        )
+    }
+
+    pub fn get_transactions_by_package(
+        &self,
+        package: ObjectID,
+        cursor: TxSequenceNumber,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        Self::get_transactions_from_index(
+            &self.transactions_by_package,
+            package,
+            cursor,
+            limit,
+            reverse,
+        )
+    }
+
     pub fn get_transactions_to_addr(
         &self,
         addr: SuiAddress,
@@ -287,6 +479,77 @@ impl IndexStore {
         Self::get_transactions_from_index(&self.transactions_to_addr, addr, cursor, limit, reverse)
     }
 
+    pub fn get_transactions_by_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        cursor: TxSequenceNumber,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        Self::get_transactions_from_index(
+            &self.transactions_by_checkpoint,
+            checkpoint,
+            cursor,
+            limit,
+            reverse,
+        )
+    }
+
+    /// Returns transactions timestamped in the [start_time, end_time) interval, ordered by
+    /// timestamp. The cursor, if provided, must be a transaction with a recorded timestamp.
+    pub fn get_transactions_in_time_range(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        let cursor = match cursor {
+            Some(cursor) => {
+                let timestamp = self.timestamps.get(&cursor)?;
+                let seq = self.transactions_seq.get(&cursor)?;
+                match (timestamp, seq) {
+                    (Some(timestamp), Some(seq)) => Some((timestamp, seq)),
+                    _ => {
+                        return Err(SuiError::TransactionNotFound { digest: cursor });
+                    }
+                }
+            }
+            None => None,
+        };
+        let iter = self.transactions_by_timestamp.iter();
+        Ok(if reverse {
+            let key = cursor
+                .map(|(ts, seq)| (ts.min(end_time), seq))
+                .unwrap_or((end_time, TxSequenceNumber::MIN));
+            let iter = iter
+                .skip_prior_to(&key)?
+                .reverse()
+                .skip_while(|((ts, _), _)| *ts >= end_time)
+                .take_while(|((ts, _), _)| *ts >= start_time)
+                .map(|(_, digest)| digest);
+            if let Some(limit) = limit {
+                iter.take(limit).collect()
+            } else {
+                iter.collect()
+            }
+        } else {
+            let key = cursor
+                .map(|(ts, seq)| (ts.max(start_time), seq))
+                .unwrap_or((start_time, TxSequenceNumber::MIN));
+            let iter = iter
+                .skip_to(&key)?
+                .take_while(|((ts, _), _)| *ts < end_time)
+                .map(|(_, digest)| digest);
+            if let Some(limit) = limit {
+                iter.take(limit).collect()
+            } else {
+                iter.collect()
+            }
+        })
+    }
+
     pub fn get_transaction_seq(
         &self,
         digest: &TransactionDigest,
@@ -294,3 +557,72 @@ impl IndexStore {
         Ok(self.transactions_seq.get(digest)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::TransactionDigest;
+
+    use crate::IndexStore;
+
+    #[test]
+    fn test_transactions_by_checkpoint() {
+        let working_dir = tempfile::tempdir().unwrap();
+        let store =
+            IndexStore::open_tables_read_write(working_dir.path().to_path_buf(), None, None);
+
+        let first: Vec<_> = (0..3).map(|_| TransactionDigest::random()).collect();
+        let second = TransactionDigest::random();
+        assert_eq!(store.last_indexed_checkpoint().unwrap(), None);
+        store
+            .index_checkpoint(
+                0,
+                first
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .map(|(seq, d)| (seq as u64, d)),
+            )
+            .unwrap();
+        store
+            .index_checkpoint(1, std::iter::once((3, second)))
+            .unwrap();
+        assert_eq!(store.last_indexed_checkpoint().unwrap(), Some(1));
+
+        // A transaction executed after its checkpoint was indexed is added to it.
+        let late = TransactionDigest::random();
+        store.index_checkpoint_tx(1, 4, &late).unwrap();
+        assert_eq!(store.last_indexed_checkpoint().unwrap(), Some(1));
+
+        assert_eq!(
+            first,
+            store
+                .get_transactions_by_checkpoint(0, 0, None, false)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![second, late],
+            store
+                .get_transactions_by_checkpoint(1, 0, None, false)
+                .unwrap()
+        );
+        assert!(store
+            .get_transactions_by_checkpoint(2, 0, None, false)
+            .unwrap()
+            .is_empty());
+
+        // Paging within a checkpoint starts at the cursor sequence number.
+        assert_eq!(
+            first[1..2],
+            store
+                .get_transactions_by_checkpoint(0, 1, Some(1), false)
+                .unwrap()
+        );
+        let reversed: Vec<_> = first.iter().rev().copied().collect();
+        assert_eq!(
+            reversed,
+            store
+                .get_transactions_by_checkpoint(0, u64::MAX, None, true)
+                .unwrap()
+        );
+    }
+}
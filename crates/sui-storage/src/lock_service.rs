@@ -62,6 +62,12 @@ enum LockServiceCommands {
         objects: Vec<ObjectRef>,
         resp: oneshot::Sender<SuiResult>,
     },
+    RevertTransaction {
+        tx: TransactionDigest,
+        inputs: Vec<ObjectRef>,
+        outputs: Vec<ObjectRef>,
+        resp: oneshot::Sender<SuiResult>,
+    },
 }
 
 type SuiLockResult = Result<Option<Option<LockInfo>>, SuiError>;
@@ -238,6 +244,25 @@ impl LockServiceImpl {
         Ok(seq)
     }
 
+    /// Undo `sequence_transaction`: forget the sequence number of the tx, delete the locks of its
+    /// outputs and re-create empty locks for its inputs, atomically.
+    fn revert_transaction(
+        &self,
+        tx: TransactionDigest,
+        inputs: &[ObjectRef],
+        outputs: &[ObjectRef],
+    ) -> SuiResult {
+        debug!(tx_digest = ?tx, ?inputs, ?outputs, "revert_transaction");
+        let write_batch = self
+            .tx_sequence
+            .batch()
+            .delete_batch(&self.tx_sequence, std::iter::once(tx))?
+            .delete_batch(&self.transaction_lock, outputs)?;
+        let write_batch = self.initialize_locks_impl(write_batch, inputs, true)?;
+        write_batch.write()?;
+        Ok(())
+    }
+
     /// Acquires a lock for a transaction on the given objects if they have all been initialized previously
     /// to None state.  It is also OK if they have been set to the same transaction.
     /// The locks are all set to the given transaction digest.
@@ -405,6 +430,16 @@ impl LockServiceImpl {
                         warn!("Could not respond to sender!");
                     }
                 }
+                LockServiceCommands::RevertTransaction {
+                    tx,
+                    inputs,
+                    outputs,
+                    resp,
+                } => {
+                    if let Err(_e) = resp.send(self.revert_transaction(tx, &inputs, &outputs)) {
+                        warn!("Could not respond to sender!");
+                    }
+                }
             }
         }
         info!("LockService command loop stopped, the sender on other end hung up/dropped");
@@ -673,6 +708,34 @@ impl LockService {
         .await
     }
 
+    /// Reverts `sequence_transaction` for a transaction whose effects are being rolled back:
+    /// the tx is unsequenced, the locks of its `outputs` are deleted and empty locks are
+    /// re-created for its `inputs`.
+    pub async fn revert_transaction(
+        &self,
+        tx: TransactionDigest,
+        inputs: Vec<ObjectRef>,
+        outputs: Vec<ObjectRef>,
+    ) -> SuiResult {
+        block_on_future_in_sim(async move {
+            let (os_sender, os_receiver) = oneshot::channel::<SuiResult>();
+            self.inner
+                .sender()
+                .send(LockServiceCommands::RevertTransaction {
+                    tx,
+                    inputs,
+                    outputs,
+                    resp: os_sender,
+                })
+                .await
+                .expect("Could not send message to inner LockService");
+            os_receiver
+                .await
+                .expect("Response from lockservice was cancelled, should not happen!")
+        })
+        .await
+    }
+
     /// Checks multiple object locks exist.
     /// Returns Err(TransactionLockDoesNotExist) if at least one object lock is not initialized.
     pub async fn locks_exist(&self, objects: Vec<ObjectRef>) -> SuiResult {
@@ -819,6 +882,27 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_lockdb_revert_transaction() {
+        let ls = init_lockservice_db();
+
+        let input: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+        let output: ObjectRef = (input.0, 2.into(), ObjectDigest::random());
+        let tx1 = TransactionDigest::random();
+
+        ls.initialize_locks(&[input], false /* is_force_reset */)
+            .unwrap();
+        ls.acquire_locks(0, &[input], tx1).unwrap();
+        assert_eq!(ls.sequence_transaction(tx1, 7, &[input], &[output]), Ok(7));
+        assert_eq!(ls.get_lock(input), Ok(None));
+        assert_eq!(ls.get_lock(output), Ok(Some(None)));
+
+        ls.revert_transaction(tx1, &[input], &[output]).unwrap();
+        assert_eq!(ls.get_tx_sequence(tx1), Ok(None));
+        assert_eq!(ls.get_lock(input), Ok(Some(None)));
+        assert_eq!(ls.get_lock(output), Ok(None));
+    }
+
     #[tokio::test]
     async fn test_lockservice_conc_acquire_init() {
         telemetry_subscribers::init_for_testing();
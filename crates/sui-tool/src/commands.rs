@@ -5,11 +5,12 @@ use anyhow::Result;
 use futures::future::join_all;
 use std::cmp::min;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use sui_config::genesis::Genesis;
 use sui_network::default_mysten_network_config;
 use sui_tool::db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand};
+use sui_tool::replay::{replay_transaction, ReplaySource};
 
 use sui_core::authority_client::{
     AuthorityAPI, NetworkAuthorityClient, NetworkAuthorityClientMetrics,
@@ -20,7 +21,12 @@ use anyhow::anyhow;
 use futures::stream::StreamExt;
 
 use clap::*;
-use sui_core::authority::MAX_ITEMS_LIMIT;
+use sui_core::authority::{AuthorityStore, MAX_ITEMS_LIMIT};
+use sui_core::checkpoints::CheckpointStoreTables;
+use sui_core::epoch::committee_store::CommitteeStore;
+use sui_core::state_snapshot::{
+    restore_state_snapshot, write_state_snapshot, StateDigest, DEFAULT_OBJECTS_PER_CHUNK,
+};
 use sui_types::messages_checkpoint::{
     CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
 };
@@ -137,6 +143,59 @@ pub enum ToolCommand {
         )]
         sequence_number: Option<CheckpointSequenceNumber>,
     },
+
+    /// Write a snapshot of the live object set of a stopped node at a certified checkpoint.
+    #[clap(name = "export-state-snapshot")]
+    ExportStateSnapshot {
+        /// Path of the DB of the node to snapshot
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        /// Directory to write the snapshot to
+        #[clap(long = "output")]
+        output: PathBuf,
+        #[clap(
+            long,
+            help = "Snapshot at a specific checkpoint - if not specified, the latest certified checkpoint is used"
+        )]
+        checkpoint: Option<CheckpointSequenceNumber>,
+        #[clap(long, default_value_t = DEFAULT_OBJECTS_PER_CHUNK)]
+        objects_per_chunk: usize,
+    },
+
+    /// Seed the empty DB of a new node from a state snapshot.
+    #[clap(name = "restore-state-snapshot")]
+    RestoreStateSnapshot {
+        /// Path of the DB of the node to restore into
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        /// Directory of the snapshot to restore
+        #[clap(long = "snapshot")]
+        snapshot: PathBuf,
+        /// Hex encoded state digest of the snapshot checkpoint, obtained from a trusted source.
+        /// The restore fails unless the objects of the snapshot match it.
+        #[clap(long = "state-digest")]
+        state_digest: String,
+    },
+
+    /// Re-execute a transaction with the object versions it originally read, and compare the
+    /// resulting effects with the recorded ones.
+    #[clap(name = "replay")]
+    Replay {
+        /// The digest of the transaction to replay
+        digest: TransactionDigest,
+
+        /// Read the transaction and its inputs from the DB of a stopped node
+        #[clap(long = "db-path", required_unless_present = "rpc_url")]
+        db_path: Option<PathBuf>,
+
+        /// Fetch the transaction and its inputs from the JSON-RPC API of a full node instead
+        #[clap(long = "rpc-url", conflicts_with = "db_path")]
+        rpc_url: Option<String>,
+    },
 }
 
 fn make_clients(genesis: &Genesis) -> Result<BTreeMap<AuthorityName, NetworkAuthorityClient>> {
@@ -159,6 +218,18 @@ fn make_clients(genesis: &Genesis) -> Result<BTreeMap<AuthorityName, NetworkAuth
     Ok(authority_clients)
 }
 
+/// Open the stores of a node DB, laid out as by `SuiNode`.
+fn open_node_stores(
+    db_path: &Path,
+    genesis: &Genesis,
+) -> Result<(AuthorityStore, CheckpointStoreTables, CommitteeStore)> {
+    let store = AuthorityStore::open(&db_path.join("store"), None)?;
+    let checkpoints =
+        CheckpointStoreTables::open_tables_read_write(db_path.join("checkpoints"), None, None);
+    let committees = CommitteeStore::new(db_path.join("epochs"), &genesis.committee()?, None);
+    Ok((store, checkpoints, committees))
+}
+
 type ObjectVersionResponses = Vec<(Option<SequenceNumber>, Result<ObjectInfoResponse>)>;
 struct ObjectData {
     requested_id: ObjectID,
@@ -508,7 +579,7 @@ impl ToolCommand {
             ToolCommand::DbTool { db_path, cmd } => {
                 let path = PathBuf::from(db_path);
                 match cmd {
-                    Some(c) => execute_db_tool_command(path, c)?,
+                    Some(c) => execute_db_tool_command(path, c).await?,
                     None => print_db_all_tables(path)?,
                 }
             }
@@ -549,6 +620,90 @@ impl ToolCommand {
                     }
                 }
             }
+            ToolCommand::ExportStateSnapshot {
+                db_path,
+                genesis,
+                output,
+                checkpoint,
+                objects_per_chunk,
+            } => {
+                let genesis = Genesis::load(genesis)?;
+                let (store, checkpoints, _) = open_node_stores(&db_path, &genesis)?;
+                let manifest = write_state_snapshot(
+                    &store,
+                    &checkpoints,
+                    checkpoint,
+                    &output,
+                    objects_per_chunk,
+                )?;
+                println!(
+                    "Wrote {} objects in {} chunks at checkpoint {} to {:?}",
+                    manifest.object_count(),
+                    manifest.chunks.len(),
+                    manifest.sequence_number(),
+                    output
+                );
+                println!("State digest: {}", hex::encode(manifest.state_digest));
+            }
+            ToolCommand::RestoreStateSnapshot {
+                db_path,
+                genesis,
+                snapshot,
+                state_digest,
+            } => {
+                let state_digest: StateDigest = hex::decode(&state_digest)?
+                    .try_into()
+                    .map_err(|_| anyhow!("State digest must be 32 bytes"))?;
+                let genesis = Genesis::load(genesis)?;
+                let (store, checkpoints, committees) = open_node_stores(&db_path, &genesis)?;
+                let manifest = restore_state_snapshot(
+                    &snapshot,
+                    &genesis.committee()?,
+                    &state_digest,
+                    &store,
+                    &checkpoints,
+                    &committees,
+                )
+                .await?;
+                println!(
+                    "Restored {} objects at checkpoint {} into {:?}",
+                    manifest.object_count(),
+                    manifest.sequence_number(),
+                    db_path
+                );
+            }
+            ToolCommand::Replay {
+                digest,
+                db_path,
+                rpc_url,
+            } => {
+                let source = match (db_path, rpc_url) {
+                    (Some(db_path), _) => ReplaySource::open_db(&db_path.join("store")),
+                    (None, Some(rpc_url)) => ReplaySource::connect(&rpc_url)?,
+                    (None, None) => unreachable!("clap requires --rpc-url without --db-path"),
+                };
+
+                let result = replay_transaction(&source, digest).await?;
+                if let Some(error) = &result.execution_error {
+                    println!("Execution error: {}", error);
+                }
+                let diffs = result.diff();
+                if diffs.is_empty() {
+                    println!(
+                        "Replayed effects of {:?} match the recorded effects",
+                        digest
+                    );
+                } else {
+                    for diff in &diffs {
+                        println!("{}", diff);
+                    }
+                    anyhow::bail!(
+                        "Replayed effects of {:?} differ from the recorded effects in {} fields",
+                        digest,
+                        diffs.len()
+                    );
+                }
+            }
         };
         Ok(())
     }
@@ -18,7 +18,7 @@ use sui_types::{
     crypto::{AuthoritySignInfo, EmptySignInfo},
 };
 
-#[derive(EnumString, Parser, Debug)]
+#[derive(EnumString, Parser, Debug, Clone, Copy)]
 pub enum StoreName {
     Validator,
     Gateway,
@@ -0,0 +1,247 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail};
+use rocksdb::MultiThreaded;
+use std::path::PathBuf;
+use std::str::FromStr;
+use sui_core::authority::authority_store_tables::{
+    AuthorityPerpetualTables, PerpetualTablesInconsistency,
+};
+use sui_core::checkpoints::CheckpointStoreTables;
+use sui_core::epoch::committee_store::CommitteeStore;
+use sui_storage::{default_db_options, IndexStore};
+use sui_types::{
+    base_types::{EpochId, ObjectID, SequenceNumber, TransactionDigest},
+    crypto::{AuthoritySignInfo, EmptySignInfo},
+};
+use typed_store::Map;
+
+use super::db_dump::{list_tables, StoreName};
+
+/// Row count, on-disk size and key range of a column family.
+pub struct TableStats {
+    pub name: String,
+    pub rows: u64,
+    pub sst_bytes: u64,
+    pub first_key: Option<Vec<u8>>,
+    pub last_key: Option<Vec<u8>>,
+}
+
+/// Look up a single entry by its typed key. Keys are an object ID (optionally followed by
+/// `:<version>`), a transaction digest, a checkpoint sequence number or an epoch, depending on
+/// the table.
+pub fn get_entry(
+    store_name: StoreName,
+    db_path: PathBuf,
+    table_name: &str,
+    key: &str,
+) -> anyhow::Result<Option<String>> {
+    match store_name {
+        StoreName::Validator => {
+            get_perpetual_entry::<AuthoritySignInfo>(store_name, db_path, table_name, key)
+        }
+        StoreName::Gateway => {
+            get_perpetual_entry::<EmptySignInfo>(store_name, db_path, table_name, key)
+        }
+        StoreName::Index => {
+            let store = IndexStore::open_tables_read_write(db_path, None, None);
+            match table_name {
+                "timestamps" => Ok(store
+                    .get_timestamp_ms(&TransactionDigest::from_str(key)?)?
+                    .map(|t| t.to_string())),
+                "transactions_seq" => Ok(store
+                    .get_transaction_seq(&TransactionDigest::from_str(key)?)?
+                    .map(|s| s.to_string())),
+                _ => unsupported(store_name, table_name, "timestamps, transactions_seq"),
+            }
+        }
+        StoreName::Checkpoints => {
+            let store = CheckpointStoreTables::open_tables_read_write(db_path, None, None);
+            match table_name {
+                "checkpoints" => Ok(store
+                    .checkpoints
+                    .get(&u64::from_str(key)?)?
+                    .map(|c| format!("{:?}", c))),
+                "checkpoint_contents" => Ok(store
+                    .checkpoint_contents
+                    .get(&u64::from_str(key)?)?
+                    .map(|c| format!("{:?}", c))),
+                "epoch_change_checkpoints" => Ok(store
+                    .epoch_change_checkpoints
+                    .get(&EpochId::from_str(key)?)?
+                    .map(|s| s.to_string())),
+                _ => unsupported(
+                    store_name,
+                    table_name,
+                    "checkpoints, checkpoint_contents, epoch_change_checkpoints",
+                ),
+            }
+        }
+        StoreName::Epoch => {
+            let store = CommitteeStore::open_tables_read_write(db_path, None, None);
+            match table_name {
+                "committee_map" => Ok(store
+                    .get_committee(&EpochId::from_str(key)?)?
+                    .map(|c| format!("{:?}", c))),
+                _ => unsupported(store_name, table_name, "committee_map"),
+            }
+        }
+        StoreName::LocksService | StoreName::NodeSync | StoreName::Wal => {
+            bail!("Point lookups in store {store_name} are not supported")
+        }
+    }
+}
+
+fn get_perpetual_entry<S>(
+    store_name: StoreName,
+    db_path: PathBuf,
+    table_name: &str,
+    key: &str,
+) -> anyhow::Result<Option<String>>
+where
+    S: std::fmt::Debug + serde::Serialize + for<'de> serde::Deserialize<'de>,
+{
+    let tables = AuthorityPerpetualTables::<S>::open(&db_path, None);
+    match table_name {
+        "objects" => {
+            let (object_id, version) = parse_object_key(key)?;
+            let object = match version {
+                Some(version) => tables.get_object_by_key(&object_id, version)?,
+                None => tables.get_object(&object_id)?,
+            };
+            Ok(object.map(|o| format!("{:?}", o)))
+        }
+        "parent_sync" => {
+            let (object_id, version) = parse_object_key(key)?;
+            let entries: Vec<_> = tables
+                .get_parent_entries(object_id)?
+                .into_iter()
+                .filter(|((_, v, _), _)| version.map_or(true, |version| *v == version))
+                .map(|(object_ref, digest)| format!("{:?}: {:?}", object_ref, digest))
+                .collect();
+            Ok((!entries.is_empty()).then(|| entries.join("\n")))
+        }
+        "certificates" => Ok(tables
+            .get_certificate(&TransactionDigest::from_str(key)?)?
+            .map(|c| format!("{:?}", c))),
+        "effects" => Ok(tables
+            .get_effects(&TransactionDigest::from_str(key)?)?
+            .map(|e| format!("{:?}", e))),
+        _ => unsupported(
+            store_name,
+            table_name,
+            "objects, parent_sync, certificates, effects",
+        ),
+    }
+}
+
+fn parse_object_key(key: &str) -> anyhow::Result<(ObjectID, Option<SequenceNumber>)> {
+    match key.split_once(':') {
+        Some((id, version)) => Ok((
+            ObjectID::from_str(id)?,
+            Some(SequenceNumber::from_u64(u64::from_str(version)?)),
+        )),
+        None => Ok((ObjectID::from_str(key)?, None)),
+    }
+}
+
+fn unsupported<T>(store_name: StoreName, table_name: &str, supported: &str) -> anyhow::Result<T> {
+    Err(anyhow!(
+        "Point lookups in table {table_name} of store {store_name} are not supported, supported tables are: {supported}"
+    ))
+}
+
+/// Collect statistics on every column family of the RocksDB database at `db_path`. Row counts
+/// are exact and require a full scan of each table.
+pub fn table_stats(db_path: PathBuf) -> anyhow::Result<Vec<TableStats>> {
+    let tables = list_tables(db_path.clone())?;
+    let db = rocksdb::DBWithThreadMode::<MultiThreaded>::open_cf_for_read_only(
+        &default_db_options(None, None).0.options,
+        &db_path,
+        &tables,
+        false,
+    )?;
+
+    tables
+        .into_iter()
+        .map(|name| {
+            let cf = db
+                .cf_handle(&name)
+                .ok_or_else(|| anyhow!("Column family {name} not found"))?;
+            let sst_bytes = db
+                .property_int_value_cf(&cf, "rocksdb.total-sst-files-size")?
+                .unwrap_or_default();
+
+            let mut iter = db.raw_iterator_cf(&cf);
+            iter.seek_to_first();
+            let first_key = iter.key().map(|k| k.to_vec());
+            let mut rows = 0;
+            while iter.valid() {
+                rows += 1;
+                iter.next();
+            }
+            iter.seek_to_last();
+            let last_key = iter.key().map(|k| k.to_vec());
+
+            Ok(TableStats {
+                name,
+                rows,
+                sst_bytes,
+                first_key,
+                last_key,
+            })
+        })
+        .collect()
+}
+
+/// Cross-check the invariants of the authority perpetual tables at `db_path`.
+pub fn verify_tables(
+    store_name: StoreName,
+    db_path: PathBuf,
+) -> anyhow::Result<Vec<PerpetualTablesInconsistency>> {
+    match store_name {
+        StoreName::Validator => Ok(AuthorityPerpetualTables::<AuthoritySignInfo>::open(
+            &db_path, None,
+        )
+        .check_consistency()?),
+        StoreName::Gateway => Ok(
+            AuthorityPerpetualTables::<EmptySignInfo>::open(&db_path, None).check_consistency()?,
+        ),
+        _ => bail!("Consistency checks are only supported for the Validator and Gateway stores"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sui_core::authority::AuthorityStore;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::object::Object;
+
+    use crate::db_tool::db_dump::StoreName;
+    use crate::db_tool::db_inspect::get_entry;
+
+    #[tokio::test]
+    async fn test_get_entry() -> Result<(), anyhow::Error> {
+        let path = tempfile::tempdir()?.into_path();
+        let store = AuthorityStore::open(&path, None)?;
+        let object = Object::with_id_owner_for_testing(ObjectID::random(), SuiAddress::default());
+        store.insert_genesis_object(object.clone()).await?;
+        drop(store);
+
+        let id = object.id();
+        let version = object.version().value();
+        let expected = Some(format!("{:?}", object));
+        let get =
+            |table: &str, key: &str| get_entry(StoreName::Validator, path.clone(), table, key);
+        assert_eq!(get("objects", &id.to_string())?, expected);
+        assert_eq!(get("objects", &format!("{id}:{version}"))?, expected);
+        assert_eq!(get("objects", &format!("{id}:{}", version + 1))?, None);
+        assert_eq!(get("objects", &ObjectID::random().to_string())?, None);
+        assert!(get("parent_sync", &format!("{id}:{version}"))?.is_some());
+
+        assert!(get("objects", "not an object id").is_err());
+        assert!(get("owner_index", &id.to_string()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use sui_core::authority::AuthorityStore;
+use sui_core::checkpoints::CheckpointStoreTables;
+use sui_storage::IndexStore;
+use sui_types::base_types::TransactionDigest;
+
+/// Roll back the execution of `digest` in the validator store at `db_path`, and drop it from
+/// the checkpoint store at `checkpoints_path` and the index store at `index_path` if given.
+/// The node owning the DBs must be stopped.
+pub async fn revert_transaction(
+    db_path: PathBuf,
+    checkpoints_path: Option<PathBuf>,
+    index_path: Option<PathBuf>,
+    digest: TransactionDigest,
+) -> anyhow::Result<()> {
+    let store = AuthorityStore::open(&db_path, None)?;
+    let checkpoints = checkpoints_path
+        .map(|path| CheckpointStoreTables::open_tables_read_write(path, None, None));
+    store
+        .revert_transaction(&digest, checkpoints.as_ref())
+        .await?;
+
+    if let Some(index_path) = index_path {
+        let indexes = IndexStore::open_tables_read_write(index_path, None, None);
+        if !indexes.remove_tx(&digest)? {
+            println!("Transaction {:?} was not indexed", digest);
+        }
+    }
+    Ok(())
+}
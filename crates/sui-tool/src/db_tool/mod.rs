@@ -2,17 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use self::db_dump::{dump_table, list_tables, StoreName};
+use self::db_inspect::{get_entry, table_stats, verify_tables};
+use self::db_repair::revert_transaction;
 use clap::Parser;
 use std::path::PathBuf;
-use sui_types::base_types::EpochId;
+use sui_types::base_types::{EpochId, TransactionDigest};
 
 pub mod db_dump;
+pub mod db_inspect;
+pub mod db_repair;
 
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
 pub enum DbToolCommand {
     ListTables,
     Dump(Dump),
+    Get(Get),
+    Stats,
+    Verify(Verify),
+    RevertTx(RevertTx),
 }
 
 #[derive(Parser)]
@@ -39,7 +47,48 @@ pub struct Dump {
     epoch: Option<EpochId>,
 }
 
-pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
+/// Look up a single entry of a table. The node owning the DB must be stopped.
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct Get {
+    /// The type of store to read
+    #[clap(long = "store")]
+    store_name: StoreName,
+    /// The name of the table to read
+    #[clap(long = "table-name")]
+    table_name: String,
+    /// The key to look up: an object ID (optionally `<id>:<version>`), a transaction digest,
+    /// a checkpoint sequence number or an epoch, depending on the table
+    #[clap(long = "key")]
+    key: String,
+}
+
+/// Cross-check the invariants between tables. The node owning the DB must be stopped.
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct Verify {
+    /// The type of store to verify
+    #[clap(long = "store")]
+    store_name: StoreName,
+}
+
+/// Roll back an executed transaction in a validator store. Fails if a later transaction
+/// depends on its outputs. The node owning the DB must be stopped.
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct RevertTx {
+    /// The digest of the transaction to revert
+    digest: TransactionDigest,
+    /// Path of the node's checkpoint store, whose entries for the transaction are removed as
+    /// well
+    #[clap(long = "checkpoints-path")]
+    checkpoints_path: Option<PathBuf>,
+    /// Path of the node's index store, whose entries for the transaction are removed as well
+    #[clap(long = "index-path")]
+    index_path: Option<PathBuf>,
+}
+
+pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
     match cmd {
         DbToolCommand::ListTables => print_db_all_tables(db_path),
         DbToolCommand::Dump(d) => print_all_entries(
@@ -50,6 +99,14 @@ pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::
             d.page_size,
             d.page_number,
         ),
+        DbToolCommand::Get(g) => print_entry(g.store_name, db_path, &g.table_name, &g.key),
+        DbToolCommand::Stats => print_table_stats(db_path),
+        DbToolCommand::Verify(v) => print_inconsistencies(v.store_name, db_path),
+        DbToolCommand::RevertTx(r) => {
+            revert_transaction(db_path, r.checkpoints_path, r.index_path, r.digest).await?;
+            println!("Reverted transaction {:?}", r.digest);
+            Ok(())
+        }
     }
 }
 
@@ -71,3 +128,46 @@ pub fn print_all_entries(
     }
     Ok(())
 }
+
+pub fn print_entry(
+    store: StoreName,
+    path: PathBuf,
+    table_name: &str,
+    key: &str,
+) -> anyhow::Result<()> {
+    match get_entry(store, path, table_name, key)? {
+        Some(value) => println!("{}", value),
+        None => println!("Key {} not found in {}", key, table_name),
+    }
+    Ok(())
+}
+
+pub fn print_table_stats(path: PathBuf) -> anyhow::Result<()> {
+    println!(
+        "{:<48} {:>12} {:>14}  key range",
+        "table", "rows", "sst bytes"
+    );
+    for stats in table_stats(path)? {
+        println!(
+            "{:<48} {:>12} {:>14}  {} .. {}",
+            stats.name,
+            stats.rows,
+            stats.sst_bytes,
+            stats.first_key.map(hex::encode).unwrap_or_default(),
+            stats.last_key.map(hex::encode).unwrap_or_default(),
+        );
+    }
+    Ok(())
+}
+
+pub fn print_inconsistencies(store: StoreName, path: PathBuf) -> anyhow::Result<()> {
+    let issues = verify_tables(store, path)?;
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    if !issues.is_empty() {
+        anyhow::bail!("Found {} inconsistencies", issues.len());
+    }
+    println!("No inconsistencies found");
+    Ok(())
+}
@@ -0,0 +1,381 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-execute a historical transaction against the exact object versions it originally read,
+//! and compare the resulting effects with the ones recorded by the network. A mismatch points
+//! at nondeterministic execution or at a behaviour change of the framework or the adapter.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::CompiledModule;
+use sui_adapter::adapter::new_move_vm;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::execution_engine;
+use sui_json_rpc::api::{RpcBcsApiClient, RpcFullNodeReadApiClient};
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::committee::EpochId;
+use sui_types::crypto::AuthoritySignInfo;
+use sui_types::error::ExecutionError;
+use sui_types::gas::{self, SuiGasStatus};
+use sui_types::in_memory_storage::InMemoryStorage;
+use sui_types::messages::{
+    CertifiedTransaction, InputObjectKind, InputObjects, TransactionEffects,
+};
+use sui_types::object::Object;
+use sui_types::sui_system_state::SuiSystemState;
+use sui_types::temporary_store::TemporaryStore;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID};
+
+/// Where the certificate, the recorded effects and the historical objects are read from.
+pub enum ReplaySource {
+    /// The authority store of a stopped node.
+    Db(AuthorityPerpetualTables<AuthoritySignInfo>),
+    /// A full node, through its JSON-RPC API. The node must not have pruned the object versions
+    /// read by the transaction.
+    Rpc(HttpClient),
+}
+
+impl ReplaySource {
+    pub fn open_db(db_path: &Path) -> Self {
+        Self::Db(AuthorityPerpetualTables::open(db_path, None))
+    }
+
+    pub fn connect(rpc_url: &str) -> anyhow::Result<Self> {
+        Ok(Self::Rpc(HttpClientBuilder::default().build(rpc_url)?))
+    }
+
+    async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<(CertifiedTransaction, TransactionEffects)> {
+        match self {
+            Self::Db(tables) => {
+                let certificate = tables
+                    .get_certificate(&digest)?
+                    .ok_or_else(|| anyhow!("Certificate of {:?} not found", digest))?;
+                let effects = tables
+                    .get_effects(&digest)?
+                    .ok_or_else(|| anyhow!("Effects of {:?} not found", digest))?;
+                Ok((certificate.into_inner(), effects.effects))
+            }
+            Self::Rpc(client) => {
+                let raw = client.get_raw_transaction(digest).await?;
+                Ok((raw.certificate()?, raw.effects()?))
+            }
+        }
+    }
+
+    /// Fetch `id` at `version`, or at its latest version if none is given.
+    async fn get_object(
+        &self,
+        id: ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> anyhow::Result<Object> {
+        match self {
+            Self::Db(tables) => match version {
+                Some(version) => tables.get_object_by_key(&id, version)?,
+                None => tables.get_object(&id)?,
+            }
+            .ok_or_else(|| anyhow!("Object {:?} at version {:?} not found", id, version)),
+            Self::Rpc(client) => match version {
+                Some(version) => client
+                    .get_raw_past_object(id, version)
+                    .await?
+                    .into_object()?
+                    .try_into(),
+                None => client.get_raw_object(id).await?.into_object()?.try_into(),
+            },
+        }
+    }
+
+    /// The versions of `id` known to the source, in increasing order.
+    async fn get_object_versions(&self, id: ObjectID) -> anyhow::Result<Vec<SequenceNumber>> {
+        match self {
+            Self::Db(tables) => Ok(tables.get_object_versions(&id)?),
+            Self::Rpc(client) => {
+                let mut versions = Vec::new();
+                let mut cursor = None;
+                loop {
+                    let page = client.get_object_history(id, cursor, None).await?;
+                    versions.extend(page.data.iter().map(|entry| entry.object_ref.version));
+                    match page.next_cursor {
+                        Some(next) => cursor = Some(next),
+                        None => return Ok(versions),
+                    }
+                }
+            }
+        }
+    }
+
+    /// The system state in effect during `epoch`: the last version of the system state object
+    /// written before the epoch ended.
+    async fn get_system_state(&self, epoch: EpochId) -> anyhow::Result<SuiSystemState> {
+        for version in self
+            .get_object_versions(SUI_SYSTEM_STATE_OBJECT_ID)
+            .await?
+            .into_iter()
+            .rev()
+        {
+            let object = self
+                .get_object(SUI_SYSTEM_STATE_OBJECT_ID, Some(version))
+                .await?;
+            let system_state: SuiSystemState = bcs::from_bytes(
+                object
+                    .data
+                    .try_as_move()
+                    .ok_or_else(|| anyhow!("Sui System State object must be a Move object"))?
+                    .contents(),
+            )?;
+            if system_state.epoch <= epoch {
+                return Ok(system_state);
+            }
+        }
+        bail!("System state of epoch {epoch} not found")
+    }
+}
+
+/// The outcome of replaying a transaction.
+pub struct ReplayResult {
+    pub certificate: CertifiedTransaction,
+    /// The effects recorded when the transaction was originally executed.
+    pub expected: TransactionEffects,
+    /// The effects of the local re-execution.
+    pub actual: TransactionEffects,
+    pub execution_error: Option<ExecutionError>,
+}
+
+impl ReplayResult {
+    /// A description of every field of the effects that differs between the original and the
+    /// replayed execution. Empty if the replay reproduced the recorded effects.
+    pub fn diff(&self) -> Vec<String> {
+        let (expected, actual) = (&self.expected, &self.actual);
+        let mut diffs = Vec::new();
+        let mut compare = |field: &str, expected: String, actual: String| {
+            if expected != actual {
+                diffs.push(format!(
+                    "{field}:\n  expected: {expected}\n  actual:   {actual}"
+                ));
+            }
+        };
+        compare(
+            "status",
+            format!("{:?}", expected.status),
+            format!("{:?}", actual.status),
+        );
+        compare(
+            "gas_used",
+            format!("{:?}", expected.gas_used),
+            format!("{:?}", actual.gas_used),
+        );
+        compare(
+            "shared_objects",
+            format!("{:?}", expected.shared_objects),
+            format!("{:?}", actual.shared_objects),
+        );
+        compare(
+            "created",
+            format!("{:?}", sorted(&expected.created)),
+            format!("{:?}", sorted(&actual.created)),
+        );
+        compare(
+            "mutated",
+            format!("{:?}", sorted(&expected.mutated)),
+            format!("{:?}", sorted(&actual.mutated)),
+        );
+        compare(
+            "unwrapped",
+            format!("{:?}", sorted(&expected.unwrapped)),
+            format!("{:?}", sorted(&actual.unwrapped)),
+        );
+        compare(
+            "deleted",
+            format!("{:?}", sorted(&expected.deleted)),
+            format!("{:?}", sorted(&actual.deleted)),
+        );
+        compare(
+            "wrapped",
+            format!("{:?}", sorted(&expected.wrapped)),
+            format!("{:?}", sorted(&actual.wrapped)),
+        );
+        compare(
+            "gas_object",
+            format!("{:?}", expected.gas_object),
+            format!("{:?}", actual.gas_object),
+        );
+        compare(
+            "events",
+            format!("{:?}", expected.events),
+            format!("{:?}", actual.events),
+        );
+        compare(
+            "dependencies",
+            format!("{:?}", sorted(&expected.dependencies)),
+            format!("{:?}", sorted(&actual.dependencies)),
+        );
+        diffs
+    }
+}
+
+fn sorted<T: Ord + Clone>(items: &[T]) -> Vec<T> {
+    let mut items = items.to_vec();
+    items.sort();
+    items
+}
+
+/// Re-execute transaction `digest` in memory, with the object versions it originally read.
+///
+/// Input versions are derived from the recorded effects, so objects the transaction only read
+/// dynamically (e.g. dynamic fields it did not modify) are not available to the replay. The
+/// storage gas price is taken from the system state of the epoch the transaction was certified
+/// in.
+pub async fn replay_transaction(
+    source: &ReplaySource,
+    digest: TransactionDigest,
+) -> anyhow::Result<ReplayResult> {
+    let (certificate, expected) = source.get_transaction(digest).await?;
+    let data = certificate.signed_data.data.clone();
+
+    // The version of every object the transaction wrote, as of before the transaction.
+    let mut input_versions: BTreeMap<ObjectID, SequenceNumber> = expected
+        .mutated
+        .iter()
+        .map(|(r, _)| r)
+        .chain(expected.deleted.iter())
+        .chain(expected.wrapped.iter())
+        .map(|(id, version, _)| (*id, version.decrement().unwrap()))
+        .collect();
+    input_versions.extend(expected.shared_objects.iter().map(|(id, v, _)| (*id, *v)));
+
+    let mut objects_by_kind = Vec::new();
+    let mut packages = BTreeSet::from([
+        ObjectID::from(MOVE_STDLIB_ADDRESS),
+        ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+    ]);
+    for kind in data.input_objects()? {
+        let object = match kind {
+            InputObjectKind::MovePackage(id) => {
+                packages.insert(id);
+                source.get_object(id, None).await?
+            }
+            InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                source.get_object(id, Some(version)).await?
+            }
+            InputObjectKind::SharedMoveObject { id, .. } => {
+                let version = input_versions
+                    .get(&id)
+                    .ok_or_else(|| anyhow!("Version of shared object {:?} not recorded", id))?;
+                source.get_object(id, Some(*version)).await?
+            }
+        };
+        objects_by_kind.push((kind, object));
+    }
+
+    // Objects written but not passed as inputs were loaded dynamically, e.g. dynamic fields.
+    let input_ids: BTreeSet<_> = objects_by_kind.iter().map(|(k, _)| k.object_id()).collect();
+    let mut storage_objects = Vec::new();
+    for (id, version) in &input_versions {
+        if !input_ids.contains(id) {
+            storage_objects.push(source.get_object(*id, Some(*version)).await?);
+        }
+    }
+
+    // Packages are immutable, so their latest version is the one the transaction used.
+    let mut pending: Vec<_> = packages.iter().copied().collect();
+    while let Some(id) = pending.pop() {
+        let package = source.get_object(id, None).await?;
+        let modules = package
+            .data
+            .try_as_package()
+            .ok_or_else(|| anyhow!("Object {:?} is not a package", id))?
+            .serialized_module_map();
+        for bytes in modules.values() {
+            let module = CompiledModule::deserialize(bytes)?;
+            for dependency in module.immediate_dependencies() {
+                let dependency = ObjectID::from(*dependency.address());
+                if packages.insert(dependency) {
+                    pending.push(dependency);
+                }
+            }
+        }
+        storage_objects.push(package);
+    }
+    storage_objects.extend(objects_by_kind.iter().map(|(_, o)| o.clone()));
+
+    let gas_status = if data.kind.is_system_tx() {
+        SuiGasStatus::new_unmetered()
+    } else {
+        let system_state = source
+            .get_system_state(certificate.auth_sign_info.epoch)
+            .await?;
+        let mut gas_status = gas::start_gas_metering(
+            data.gas_budget,
+            data.gas_price,
+            system_state.parameters.storage_gas_price,
+        )?;
+        if data.contains_shared_object() {
+            gas_status.charge_consensus()?;
+        }
+        gas_status
+    };
+
+    let input_objects = InputObjects::new(objects_by_kind);
+    let transaction_dependencies = input_objects.transaction_dependencies();
+    let shared_object_refs = input_objects.filter_shared_objects();
+    let temporary_store = TemporaryStore::new(
+        Arc::new(InMemoryStorage::new(storage_objects)),
+        input_objects,
+        digest,
+    );
+    let native_functions =
+        sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+    let move_vm = Arc::new(new_move_vm(native_functions.clone())?);
+    let (_, actual, execution_error) = execution_engine::execute_transaction_to_effects(
+        shared_object_refs,
+        temporary_store,
+        data,
+        digest,
+        transaction_dependencies,
+        &move_vm,
+        &native_functions,
+        gas_status,
+        certificate.auth_sign_info.epoch,
+    );
+
+    Ok(ReplayResult {
+        certificate,
+        expected,
+        actual,
+        execution_error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utils::network::TestClusterBuilder;
+    use test_utils::transaction::transfer_sui;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replay_transaction_over_rpc() -> anyhow::Result<()> {
+        let mut test_cluster = TestClusterBuilder::new().build().await?;
+        let (_, _, _, digest) = transfer_sui(test_cluster.wallet_mut(), None, None).await?;
+
+        let source = ReplaySource::connect(test_cluster.rpc_url().unwrap())?;
+        let mut result = replay_transaction(&source, digest).await?;
+        assert!(result.execution_error.is_none());
+        assert!(result.diff().is_empty(), "{:?}", result.diff());
+
+        // A replay that doesn't reproduce the recorded effects reports the differing fields.
+        result.expected.gas_used.computation_cost += 1;
+        let diffs = result.diff();
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].starts_with("gas_used"));
+        Ok(())
+    }
+}
@@ -77,6 +77,23 @@ pub fn generate_proof_of_possession<K: KeypairTraits>(
     keypair.sign(&domain_with_pk[..])
 }
 
+// Verifies a proof created by generate_proof_of_possession for the keypair of public_key and address.
+pub fn verify_proof_of_possession<P: VerifyingKey>(
+    proof_of_possession: &P::Sig,
+    public_key: &P,
+    address: SuiAddress,
+) -> SuiResult<()> {
+    let mut domain_with_pk: Vec<u8> = Vec::new();
+    domain_with_pk.extend_from_slice(PROOF_OF_POSSESSION_DOMAIN);
+    domain_with_pk.extend_from_slice(public_key.as_bytes());
+    domain_with_pk.extend_from_slice(address.as_ref());
+    public_key
+        .verify(&domain_with_pk[..], proof_of_possession)
+        .map_err(|e| SuiError::InvalidSignature {
+            error: format!("Invalid proof of possession: {e}"),
+        })
+}
+
 ///////////////////////////////////////////////
 /// Account Keys
 ///
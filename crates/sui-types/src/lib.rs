@@ -37,6 +37,7 @@ pub mod messages;
 pub mod messages_checkpoint;
 pub mod move_package;
 pub mod object;
+pub mod object_proof;
 pub mod query;
 pub mod signature_seed;
 pub mod storage;
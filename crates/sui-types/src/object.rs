@@ -662,6 +662,21 @@ impl Display for ObjectRead {
     }
 }
 
+/// A change of ownership of an object, recorded when a transaction creates, transfers, wraps,
+/// unwraps or deletes it.
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerChange {
+    pub object_id: ObjectID,
+    /// The version of the object written (or deleted) by the transaction.
+    pub version: SequenceNumber,
+    /// The owner before the transaction, None if the object was created or unwrapped.
+    pub from: Option<Owner>,
+    /// The owner after the transaction, None if the object was deleted or wrapped.
+    pub to: Option<Owner>,
+    pub transaction_digest: TransactionDigest,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "status", content = "details")]
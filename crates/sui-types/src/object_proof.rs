@@ -0,0 +1,83 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::{ExecutionDigests, ObjectRef};
+use crate::committee::Committee;
+use crate::error::{SuiError, SuiResult};
+use crate::messages::{CertifiedTransaction, TransactionEffects};
+use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use crate::object::Object;
+
+#[cfg(test)]
+#[path = "unit_tests/object_proof_tests.rs"]
+mod object_proof_tests;
+
+/// A portable proof that an object had a given state: the object was written by a certified
+/// transaction, whose effects are committed to by a certified checkpoint.
+///
+/// Only the committee of the checkpoint's epoch needs to be trusted to check the proof.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectProof {
+    /// The object, as written by the transaction.
+    pub object: Object,
+    /// The certificate of the transaction that wrote the object.
+    pub certificate: CertifiedTransaction,
+    pub effects: TransactionEffects,
+    /// The certified checkpoint including the transaction.
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+}
+
+impl ObjectProof {
+    /// Verify the proof against `committee`, the committee of the epoch of the checkpoint, and
+    /// return the reference of the proven object.
+    pub fn verify(&self, committee: &Committee) -> SuiResult<ObjectRef> {
+        self.checkpoint.verify(committee, Some(&self.contents))?;
+        fp_ensure!(
+            self.certificate.epoch() == self.checkpoint.summary.epoch,
+            invalid_proof(format!(
+                "certificate of epoch {} included in checkpoint of epoch {}",
+                self.certificate.epoch(),
+                self.checkpoint.summary.epoch
+            ))
+        );
+        self.certificate.verify_signatures(committee)?;
+
+        let digest = *self.certificate.digest();
+        fp_ensure!(
+            self.effects.transaction_digest == digest,
+            invalid_proof(format!("effects are not the effects of {digest:?}"))
+        );
+        let execution_digests = ExecutionDigests::new(digest, self.effects.digest());
+        fp_ensure!(
+            self.contents.iter().any(|d| *d == execution_digests),
+            invalid_proof(format!(
+                "transaction {digest:?} is not included in checkpoint {}",
+                self.checkpoint.summary.sequence_number
+            ))
+        );
+
+        let object_ref = self.object.compute_object_reference();
+        fp_ensure!(
+            self.object.previous_transaction == digest,
+            invalid_proof(format!(
+                "object {object_ref:?} was not written by {digest:?}"
+            ))
+        );
+        fp_ensure!(
+            self.effects
+                .all_mutated()
+                .any(|(r, owner, _)| *r == object_ref && *owner == self.object.owner),
+            invalid_proof(format!(
+                "object {object_ref:?} is not in the effects of {digest:?}"
+            ))
+        );
+        Ok(object_ref)
+    }
+}
+
+fn invalid_proof(error: String) -> SuiError {
+    SuiError::InvalidObjectProof { error }
+}
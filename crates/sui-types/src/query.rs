@@ -7,6 +7,7 @@ use serde::Serialize;
 
 use crate::base_types::{SuiAddress, TransactionDigest};
 use crate::event::EventType;
+use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::object::Owner;
 use crate::ObjectID;
 
@@ -28,6 +29,30 @@ pub enum TransactionQuery {
     FromAddress(SuiAddress),
     /// Query by recipient address.
     ToAddress(SuiAddress),
+    /// Query by emitted move event struct type, e.g. `0x2::devnet_nft::MintNFTEvent` or
+    /// `0x2::SUI::test_foo<address, vector<u8>>` with type params.
+    MoveEvent(String),
+    /// Query by package: transactions that publish it, call it, or call into it from other
+    /// packages, as seen from the Move events its modules emit.
+    Package(ObjectID),
+    /// Query by the time the transaction was first seen by the node, in
+    /// [start_time, end_time) interval (UTC timestamps in milliseconds).
+    TimeRange {
+        /// left endpoint of time interval, inclusive
+        start_time: u64,
+        /// right endpoint of time interval, exclusive
+        end_time: u64,
+    },
+    /// Query by checkpoint sequence number.
+    Checkpoint(CheckpointSequenceNumber),
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+pub enum OwnerChangeQuery {
+    /// Query the ownership changes of an object.
+    Object(ObjectID),
+    /// Query the ownership changes from or to an address.
+    Address(SuiAddress),
 }
 
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
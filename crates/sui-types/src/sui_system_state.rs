@@ -11,8 +11,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::base_types::AuthorityName;
 use crate::collection_types::{VecMap, VecSet};
-use crate::committee::{Committee, CommitteeWithNetAddresses, StakeUnit};
+use crate::committee::{Committee, CommitteeWithNetAddresses, EpochId, StakeUnit};
 use crate::crypto::AuthorityPublicKeyBytes;
+use crate::messages::ChangeEpoch;
 use crate::{
     balance::{Balance, Supply},
     id::UID,
@@ -22,6 +23,13 @@ use crate::{
 const SUI_SYSTEM_STATE_STRUCT_NAME: &IdentStr = ident_str!("SuiSystemState");
 pub const SUI_SYSTEM_MODULE_NAME: &IdentStr = ident_str!("sui_system");
 pub const ADVANCE_EPOCH_FUNCTION_NAME: &IdentStr = ident_str!("advance_epoch");
+pub const ADD_DELEGATION_FUNCTION_NAME: &IdentStr = ident_str!("request_add_delegation");
+pub const WITHDRAW_DELEGATION_FUNCTION_NAME: &IdentStr = ident_str!("request_withdraw_delegation");
+pub const SWITCH_DELEGATION_FUNCTION_NAME: &IdentStr = ident_str!("request_switch_delegation");
+
+pub const STAKING_POOL_MODULE_NAME: &IdentStr = ident_str!("staking_pool");
+const DELEGATION_STRUCT_NAME: &IdentStr = ident_str!("Delegation");
+const STAKED_SUI_STRUCT_NAME: &IdentStr = ident_str!("StakedSui");
 
 /// Rust version of the Move sui::sui_system::SystemParameters type
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
@@ -46,9 +54,13 @@ pub struct ValidatorMetadata {
     pub proof_of_possession_bytes: Vec<u8>,
     pub name: Vec<u8>,
     pub net_address: Vec<u8>,
+    pub description: Vec<u8>,
+    pub image_url: Vec<u8>,
+    pub project_url: Vec<u8>,
     pub next_epoch_stake: u64,
     pub next_epoch_delegation: u64,
     pub next_epoch_gas_price: u64,
+    pub commission_rate: u64,
 }
 
 impl ValidatorMetadata {
@@ -107,6 +119,74 @@ pub struct StakingPool {
     pub pending_delegations: Vec<PendingDelegationEntry>,
 }
 
+impl StakingPool {
+    /// The amount of SUI `token_amount` delegation tokens are worth at the exchange rate of the
+    /// start of the epoch, as computed by `sui::staking_pool::get_sui_amount`.
+    pub fn sui_amount(&self, token_amount: u64) -> u64 {
+        if self.epoch_starting_delegation_token_supply == 0 {
+            return token_amount;
+        }
+        (self.epoch_starting_sui_balance as u128 * token_amount as u128
+            / self.epoch_starting_delegation_token_supply as u128) as u64
+    }
+
+    /// The amount of delegation tokens `sui_amount` SUI are worth at the exchange rate of the
+    /// start of the epoch, as computed by `sui::staking_pool::get_token_amount`.
+    pub fn token_amount(&self, sui_amount: u64) -> u64 {
+        if self.epoch_starting_sui_balance == 0 {
+            return sui_amount;
+        }
+        (self.epoch_starting_delegation_token_supply as u128 * sui_amount as u128
+            / self.epoch_starting_sui_balance as u128) as u64
+    }
+}
+
+/// Rust version of the Move sui::epoch_time_lock::EpochTimeLock type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct EpochTimeLock {
+    pub epoch: EpochId,
+}
+
+/// Rust version of the Move sui::staking_pool::Delegation type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Delegation {
+    pub id: UID,
+    pub validator_address: AccountAddress,
+    pub pool_starting_epoch: u64,
+    pub pool_tokens: Balance,
+    pub principal_sui_amount: u64,
+}
+
+impl Delegation {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            name: DELEGATION_STRUCT_NAME.to_owned(),
+            module: STAKING_POOL_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+}
+
+/// Rust version of the Move sui::staking_pool::StakedSui type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct StakedSui {
+    pub id: UID,
+    pub principal: Balance,
+    pub sui_token_lock: MoveOption<EpochTimeLock>,
+}
+
+impl StakedSui {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            name: STAKED_SUI_STRUCT_NAME.to_owned(),
+            module: STAKING_POOL_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+}
+
 /// Rust version of the Move sui::validator_set::ValidatorSet type
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct ValidatorSet {
@@ -157,6 +237,68 @@ impl SuiSystemState {
         .unwrap()
     }
 
+    /// The statistics of the epoch ended by `change`, with `self` the system state before the
+    /// epoch change and `next` the system state after it.
+    ///
+    /// The rewards mirror the distribution done by `sui::sui_system::advance_epoch`.
+    pub fn epoch_stats(&self, next: &SuiSystemState, change: &ChangeEpoch) -> EpochStats {
+        let validators = &self.validators;
+        let computation_charge = change.computation_charge as u128;
+        let validator_stake = validators.validator_stake as u128;
+        let delegation_stake = validators.delegation_stake as u128;
+        let total_stake = validator_stake + delegation_stake + self.storage_fund.value() as u128;
+
+        let delegator_reward = if total_stake == 0 {
+            0
+        } else {
+            delegation_stake * computation_charge / total_stake
+        };
+        let validator_reward = computation_charge - delegator_reward;
+
+        let validator_rewards = validators
+            .active_validators
+            .iter()
+            .map(|validator| {
+                let stake_reward = if validator_stake == 0 {
+                    0
+                } else {
+                    validator.stake_amount as u128 * validator_reward / validator_stake
+                };
+                let pool = &validator.delegation_staking_pool;
+                let delegation_reward = if delegation_stake == 0 {
+                    0
+                } else {
+                    pool.epoch_starting_sui_balance as u128 * delegator_reward / delegation_stake
+                };
+                // Validators leaving the set keep the exchange rate of the ended epoch.
+                let next_pool = next
+                    .validators
+                    .active_validators
+                    .iter()
+                    .map(|v| &v.delegation_staking_pool)
+                    .find(|p| p.validator_address == pool.validator_address)
+                    .unwrap_or(pool);
+                ValidatorEpochRewards {
+                    sui_address: validator.metadata.sui_address,
+                    stake_reward: stake_reward as u64,
+                    delegation_reward: delegation_reward as u64,
+                    pool_sui_balance: next_pool.epoch_starting_sui_balance,
+                    pool_token_supply: next_pool.epoch_starting_delegation_token_supply,
+                }
+            })
+            .collect();
+
+        EpochStats {
+            epoch: self.epoch,
+            transaction_count: None,
+            storage_charge: change.storage_charge,
+            computation_charge: change.computation_charge,
+            storage_rebate: change.storage_rebate,
+            reference_gas_price: self.reference_gas_price,
+            validator_rewards,
+        }
+    }
+
     pub fn get_current_epoch_committee(&self) -> CommitteeWithNetAddresses {
         let mut voting_rights = BTreeMap::new();
         let mut net_addresses = BTreeMap::new();
@@ -175,3 +317,34 @@ impl SuiSystemState {
         }
     }
 }
+
+/// Statistics of an ended epoch, recorded when the epoch change transaction is executed.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct EpochStats {
+    pub epoch: EpochId,
+    /// The number of transactions checkpointed during the epoch, None if the node does not have
+    /// all the checkpoints of the epoch. Not stored: it is counted from the checkpoints when the
+    /// statistics are read.
+    #[serde(skip)]
+    pub transaction_count: Option<u64>,
+    pub storage_charge: u64,
+    pub computation_charge: u64,
+    pub storage_rebate: u64,
+    pub reference_gas_price: u64,
+    /// The rewards of every validator active during the epoch.
+    pub validator_rewards: Vec<ValidatorEpochRewards>,
+}
+
+/// The rewards distributed to a validator and its delegators at the end of an epoch.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct ValidatorEpochRewards {
+    pub sui_address: AccountAddress,
+    /// The reward added to the validator's own stake.
+    pub stake_reward: u64,
+    /// The reward added to the validator's delegation staking pool.
+    pub delegation_reward: u64,
+    /// The exchange rate of the staking pool at the start of the next epoch: `pool_sui_balance`
+    /// SUI for `pool_token_supply` delegation tokens.
+    pub pool_sui_balance: u64,
+    pub pool_token_supply: u64,
+}
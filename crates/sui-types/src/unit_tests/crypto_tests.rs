@@ -46,3 +46,15 @@ proptest! {
 
 
 }
+
+#[test]
+fn test_proof_of_possession() {
+    let (address, keypair): (_, AuthorityKeyPair) = get_key_pair();
+    let pop = generate_proof_of_possession(&keypair, address);
+    assert!(verify_proof_of_possession(&pop, keypair.public(), address).is_ok());
+
+    // The proof is bound to the address and to the key
+    let (other_address, other_keypair): (_, AuthorityKeyPair) = get_key_pair();
+    assert!(verify_proof_of_possession(&pop, keypair.public(), other_address).is_err());
+    assert!(verify_proof_of_possession(&pop, other_keypair.public(), address).is_err());
+}
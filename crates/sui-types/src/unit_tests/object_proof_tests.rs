@@ -0,0 +1,167 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+use super::*;
+use crate::base_types::{dbg_addr, ObjectID, SuiAddress};
+use crate::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair};
+use crate::gas::GasCostSummary;
+use crate::messages::{SignedTransaction, Transaction, TransactionData};
+use crate::messages_checkpoint::SignedCheckpointSummary;
+use crate::object::Owner;
+use crate::utils::make_committee_key;
+
+const RNG_SEED: [u8; 32] = [
+    21, 23, 199, 200, 234, 250, 252, 178, 94, 15, 202, 178, 62, 186, 88, 137, 233, 192, 130, 157,
+    179, 179, 65, 9, 31, 249, 221, 123, 225, 112, 199, 247,
+];
+
+/// A certified transfer of a fresh object to a recipient, and the object it wrote.
+fn make_transfer(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+) -> (Object, CertifiedTransaction, TransactionEffects) {
+    let (sender, sender_key): (SuiAddress, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+
+    let transaction = Transaction::from_data(
+        TransactionData::new_transfer(
+            recipient,
+            object.compute_object_reference(),
+            sender,
+            gas.compute_object_reference(),
+            10000,
+        ),
+        &sender_key,
+    )
+    .verify()
+    .unwrap();
+    let signatures = keys
+        .iter()
+        .map(|k| {
+            SignedTransaction::new(committee.epoch, transaction.clone(), k.public().into(), k)
+                .auth_sign_info
+        })
+        .collect();
+    let certificate =
+        CertifiedTransaction::new_with_auth_sign_infos(transaction, signatures, committee).unwrap();
+
+    let mut written = object;
+    written.transfer_and_increment_version(recipient);
+    written.previous_transaction = *certificate.digest();
+    let effects = TransactionEffects {
+        transaction_digest: *certificate.digest(),
+        mutated: vec![(written.compute_object_reference(), written.owner)],
+        gas_object: (gas.compute_object_reference(), gas.owner),
+        ..Default::default()
+    };
+    (written, certificate, effects)
+}
+
+fn make_checkpoint(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+    effects: &TransactionEffects,
+) -> (CertifiedCheckpointSummary, CheckpointContents) {
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [
+            ExecutionDigests::random(),
+            ExecutionDigests::new(effects.transaction_digest, effects.digest()),
+        ]
+        .into_iter(),
+    );
+    let signed = keys
+        .iter()
+        .map(|k| {
+            SignedCheckpointSummary::new(
+                committee.epoch,
+                1,
+                k.public().into(),
+                k,
+                &contents,
+                None,
+                GasCostSummary::default(),
+                None,
+            )
+        })
+        .collect();
+    let checkpoint = CertifiedCheckpointSummary::aggregate(signed, committee).unwrap();
+    (checkpoint, contents)
+}
+
+fn make_proof(keys: &[AuthorityKeyPair], committee: &Committee) -> ObjectProof {
+    let (object, certificate, effects) = make_transfer(keys, committee);
+    let (checkpoint, contents) = make_checkpoint(keys, committee, &effects);
+    ObjectProof {
+        object,
+        certificate,
+        effects,
+        checkpoint,
+        contents,
+    }
+}
+
+#[test]
+fn test_object_proof() {
+    let mut rng = StdRng::from_seed(RNG_SEED);
+    let (keys, committee) = make_committee_key(&mut rng);
+    let (_, other_committee) = make_committee_key(&mut rng);
+
+    let proof = make_proof(&keys, &committee);
+    assert_eq!(
+        proof.verify(&committee).unwrap(),
+        proof.object.compute_object_reference()
+    );
+
+    // The proof survives serialization.
+    let proof: ObjectProof = bcs::from_bytes(&bcs::to_bytes(&proof).unwrap()).unwrap();
+    assert!(proof.verify(&committee).is_ok());
+
+    // It is not accepted by another committee.
+    assert!(proof.verify(&other_committee).is_err());
+}
+
+#[test]
+fn test_object_proof_rejects_tampering() {
+    let mut rng = StdRng::from_seed(RNG_SEED);
+    let (keys, committee) = make_committee_key(&mut rng);
+    let proof = make_proof(&keys, &committee);
+
+    // An object state the transaction did not write.
+    let mut tampered = proof.clone();
+    tampered.object.owner = Owner::AddressOwner(dbg_addr(3));
+    assert!(matches!(
+        tampered.verify(&committee),
+        Err(SuiError::InvalidObjectProof { .. })
+    ));
+
+    // Effects that the checkpoint does not commit to.
+    let mut tampered = proof.clone();
+    tampered.effects.gas_used.computation_cost += 1;
+    assert!(matches!(
+        tampered.verify(&committee),
+        Err(SuiError::InvalidObjectProof { .. })
+    ));
+
+    // A checkpoint that does not include the transaction.
+    let (other_object, other_certificate, other_effects) = make_transfer(&keys, &committee);
+    let mut tampered = proof.clone();
+    tampered.object = other_object;
+    tampered.certificate = other_certificate;
+    tampered.effects = other_effects;
+    assert!(matches!(
+        tampered.verify(&committee),
+        Err(SuiError::InvalidObjectProof { .. })
+    ));
+
+    // Contents that do not match the checkpoint.
+    let (_, other_contents) = make_checkpoint(&keys, &committee, &proof.effects);
+    let mut tampered = proof;
+    tampered.contents = other_contents;
+    assert!(tampered.verify(&committee).is_err());
+}
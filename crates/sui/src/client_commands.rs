@@ -3,7 +3,7 @@
 
 use core::fmt;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     path::{Path, PathBuf},
     time::Instant,
@@ -17,8 +17,11 @@ use fastcrypto::{
     encoding::{Base64, Encoding},
     traits::ToFromBytes,
 };
-use move_core_types::language_storage::TypeTag;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use move_package::BuildConfig as MoveBuildConfig;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::json;
 use tracing::info;
@@ -27,22 +30,32 @@ use sui_framework::build_move_package;
 use sui_framework_build::compiled_package::BuildConfig;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, SuiObjectInfo, SuiParsedObject, SuiTransactionResponse,
+    GetObjectDataResponse, Page, SuiEpochStats, SuiMoveStruct, SuiMoveValue, SuiObjectInfo,
+    SuiParsedData, SuiParsedMoveObject, SuiParsedObject, SuiTransactionResponse, SuiValidatorSet,
 };
 use sui_json_rpc_types::{GetRawObjectDataResponse, SuiData};
 use sui_json_rpc_types::{SuiCertifiedTransaction, SuiExecutionStatus, SuiTransactionEffects};
+use sui_keys::encrypted_seed::EncryptedSeed;
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::TransactionExecutionResult;
-use sui_types::crypto::SignableBytes;
+use sui_types::crypto::{SignableBytes, SuiSignature};
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    committee::EpochId,
     gas_coin::GasCoin,
     messages::{Transaction, VerifiedTransaction},
     object::Owner,
-    parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
+    parse_sui_type_tag,
+    query::TransactionQuery,
+    sui_framework_address_concat_string,
+    sui_system_state::{
+        Delegation, StakedSui, StakingPool, SuiSystemState, ADD_DELEGATION_FUNCTION_NAME,
+        SUI_SYSTEM_MODULE_NAME, SWITCH_DELEGATION_FUNCTION_NAME, WITHDRAW_DELEGATION_FUNCTION_NAME,
+    },
+    SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 use sui_types::{
-    crypto::{Signature, SignatureScheme},
+    crypto::{PublicKey, Signature, SignatureScheme},
     messages::TransactionData,
 };
 
@@ -51,23 +64,37 @@ use sui_sdk::embedded_gateway::SuiClient;
 #[cfg(not(msim))]
 use sui_sdk::SuiClient;
 
-use crate::config::{Config, PersistedConfig, SuiClientConfig, SuiEnv};
+use crate::config::{AddressOrAlias, Config, PersistedConfig, SuiClientConfig, SuiEnv};
+use crate::source_verifier::verify_source;
+
+/// Environment variable holding the password of the seed `new-address --index` derives from.
+pub const SEED_PASSWORD_ENV_VAR: &str = "SUI_SEED_PASSWORD";
 
 pub const EXAMPLE_NFT_NAME: &str = "Example NFT";
 pub const EXAMPLE_NFT_DESCRIPTION: &str = "An NFT created by the Sui Command Line Tool";
 pub const EXAMPLE_NFT_URL: &str =
     "ipfs://bafkreibngqhl3gaa7daob4i2vccziay2jjlp435cf66vhono7nrvww53ty";
 
+/// Options shared by the commands building a transaction.
+#[derive(Args, Debug, Default, Clone, Copy)]
+pub struct TxBuildOptions {
+    /// Print the unsigned transaction data, Base64 encoded, instead of signing and executing the
+    /// transaction. It can be signed offline with `sui keytool sign` and executed with
+    /// `sui client execute-signed-tx`.
+    #[clap(long)]
+    pub serialize_unsigned: bool,
+}
+
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
 pub enum SuiClientCommands {
     /// Switch active address and network(e.g., devnet, local rpc server)
     #[clap(name = "switch")]
     Switch {
-        /// An Sui address to be used as the active address for subsequent
+        /// An Sui address, or its alias, to be used as the active address for subsequent
         /// commands.
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
         /// The RPC server URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long)]
@@ -102,6 +129,37 @@ pub enum SuiClientCommands {
         id: ObjectID,
     },
 
+    /// List the dynamic fields of an object, with their names and values
+    #[clap(name = "dynamic-fields")]
+    DynamicFields {
+        /// Object ID of the object owning the fields
+        id: ObjectID,
+    },
+
+    /// List the transactions sent by an address, most recent first
+    #[clap(name = "tx-history")]
+    TxHistory {
+        /// Address, or alias, to list the transactions of, defaults to the active address
+        #[clap(long)]
+        address: Option<AddressOrAlias>,
+        /// List the transactions sending objects to the address instead of the ones it sent
+        #[clap(long)]
+        received: bool,
+        /// Maximum number of transactions to list
+        #[clap(long, default_value = "20")]
+        limit: usize,
+        /// Digest of the transaction to start listing from, as printed by the previous page
+        #[clap(long)]
+        cursor: Option<TransactionDigest>,
+    },
+
+    /// Show a transaction, with its effects and events
+    #[clap(name = "tx")]
+    Tx {
+        /// Digest of the transaction
+        digest: TransactionDigest,
+    },
+
     /// Publish Move modules
     #[clap(name = "publish")]
     Publish {
@@ -127,6 +185,14 @@ pub enum SuiClientCommands {
         /// Gas budget for running module initializers
         #[clap(long)]
         gas_budget: u64,
+
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
+
+        /// Check that the bytecode of the package's published dependencies matches the on-chain
+        /// packages before publishing
+        #[clap(long)]
+        verify_deps: bool,
     },
 
     /// Call Move function
@@ -161,14 +227,16 @@ pub enum SuiClientCommands {
         /// Gas budget for this call
         #[clap(long)]
         gas_budget: u64,
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
 
     /// Transfer object
     #[clap(name = "transfer")]
     Transfer {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Object to transfer, in 20 bytes Hex string
         #[clap(long)]
@@ -182,15 +250,18 @@ pub enum SuiClientCommands {
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: u64,
+
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
     /// Transfer SUI, and pay gas with the same SUI coin object.
     /// If amount is specified, only the amount is transferred; otherwise the entire object
     /// is transferred.
     #[clap(name = "transfer-sui")]
     TransferSui {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
@@ -203,6 +274,9 @@ pub enum SuiClientCommands {
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
         #[clap(long)]
         amount: Option<u64>,
+
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
     /// Pay coins to recipients following specified amounts, with input coins.
     /// Length of recipients must be the same as that of amounts.
@@ -212,9 +286,9 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient addresses, must be of same length as amounts
+        /// The recipient addresses or aliases, must be of same length as amounts
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
-        recipients: Vec<SuiAddress>,
+        recipients: Vec<AddressOrAlias>,
 
         /// The amounts to be paid, following the order of recipients.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
@@ -228,6 +302,9 @@ pub enum SuiClientCommands {
         /// Gas budget for this transaction
         #[clap(long)]
         gas_budget: u64,
+
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
 
     /// Pay SUI coins to recipients following following specified amounts, with input coins.
@@ -239,9 +316,9 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient addresses, must be of same length as amounts.
+        /// The recipient addresses or aliases, must be of same length as amounts.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
-        recipients: Vec<SuiAddress>,
+        recipients: Vec<AddressOrAlias>,
 
         /// The amounts to be paid, following the order of recipients.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
@@ -250,6 +327,9 @@ pub enum SuiClientCommands {
         /// Gas budget for this transaction
         #[clap(long)]
         gas_budget: u64,
+
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
 
     /// Pay all residual SUI coins to the recipient with input coins, after deducting the gas cost.
@@ -260,13 +340,16 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient address.
+        /// The recipient address or alias.
         #[clap(long, multiple_occurrences = false)]
-        recipient: SuiAddress,
+        recipient: AddressOrAlias,
 
         /// Gas budget for this transaction
         #[clap(long)]
         gas_budget: u64,
+
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
 
     /// Obtain the Addresses managed by the client.
@@ -275,26 +358,160 @@ pub enum SuiClientCommands {
 
     /// Generate new address and keypair with keypair scheme flag {ed25519 | secp256k1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or m/54'/784'/0'/0/0 for secp256k1.
+    /// With `--index`, the keypair is instead derived at that account index from the seed stored,
+    /// encrypted, in the client config, which is generated on first use. The seed password is read
+    /// from the SUI_SEED_PASSWORD environment variable, or prompted for.
     #[clap(name = "new-address")]
     NewAddress {
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
+        /// Account index to derive the keypair at from the stored seed
+        #[clap(long, conflicts_with = "derivation-path")]
+        index: Option<u32>,
+    },
+
+    /// Give an address an alias usable in place of the address in every command, or list the
+    /// aliases when no alias is given.
+    #[clap(name = "alias")]
+    Alias {
+        /// The alias, starting with a letter followed by letters, digits, '-' or '_'
+        alias: Option<String>,
+        /// The address to alias, defaults to the active address
+        address: Option<AddressOrAlias>,
+        /// Remove the alias instead
+        #[clap(long)]
+        remove: bool,
     },
 
     /// Obtain all objects owned by the address.
     #[clap(name = "objects")]
     Objects {
-        /// Address owning the objects
+        /// Address owning the objects, or its alias
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
     },
 
     /// Obtain all gas objects owned by the address.
     #[clap(name = "gas")]
     Gas {
-        /// Address owning the objects
+        /// Address owning the objects, or its alias
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
+    },
+
+    /// Show the gas charges and validator rewards of finished epochs.
+    #[clap(name = "epoch-stats")]
+    EpochStats {
+        /// The first epoch to show, defaults to the genesis epoch
+        #[clap(long)]
+        epoch: Option<EpochId>,
+        /// Maximum number of epochs to show
+        #[clap(long)]
+        limit: Option<usize>,
+    },
+
+    /// Delegate SUI to a validator. Coins of the active address are selected, merged and split
+    /// as needed to delegate exactly `amount`. The delegation becomes active at the next epoch.
+    #[clap(name = "stake")]
+    Stake {
+        /// Address of the validator to delegate to
+        #[clap(long)]
+        validator: SuiAddress,
+        /// Amount of SUI to delegate
+        #[clap(long)]
+        amount: u64,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for each transaction
+        #[clap(long)]
+        gas_budget: u64,
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
+    },
+
+    /// Withdraw a delegation, with its rewards.
+    #[clap(name = "unstake")]
+    Unstake {
+        /// ID of the Delegation object
+        #[clap(long)]
+        delegation: ObjectID,
+        /// ID of the StakedSui object holding the principal of the delegation, as listed by
+        /// `my-stakes`
+        #[clap(long)]
+        staked_sui: ObjectID,
+        /// Amount of delegation pool tokens to withdraw, defaults to all of them
+        #[clap(long)]
+        pool_tokens: Option<u64>,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
+    },
+
+    /// Move a delegation, with its rewards, to another validator.
+    #[clap(name = "switch-delegation")]
+    SwitchDelegation {
+        /// ID of the Delegation object
+        #[clap(long)]
+        delegation: ObjectID,
+        /// ID of the StakedSui object holding the principal of the delegation, as listed by
+        /// `my-stakes`
+        #[clap(long)]
+        staked_sui: ObjectID,
+        /// Address of the validator to delegate to
+        #[clap(long)]
+        validator: SuiAddress,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
+    },
+
+    /// Withdraw the rewards earned by a delegation: the pool tokens worth more than the principal
+    /// are withdrawn, so the principal stays delegated.
+    #[clap(name = "withdraw-rewards")]
+    WithdrawRewards {
+        /// ID of the Delegation object
+        #[clap(long)]
+        delegation: ObjectID,
+        /// ID of the StakedSui object holding the principal of the delegation, as listed by
+        /// `my-stakes`
+        #[clap(long)]
+        staked_sui: ObjectID,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
+    },
+
+    /// List the active validators with their stake, gas price and delegation pool.
+    #[clap(name = "list-validators")]
+    ListValidators,
+
+    /// List the delegations of an address, active and pending, and the StakedSui objects holding
+    /// their principals.
+    #[clap(name = "my-stakes")]
+    MyStakes {
+        /// Address owning the delegations, or its alias
+        #[clap(long)]
+        address: Option<AddressOrAlias>,
     },
 
     /// Split a coin object into multiple coins.
@@ -316,6 +533,8 @@ pub enum SuiClientCommands {
         /// Gas budget for this call
         #[clap(long)]
         gas_budget: u64,
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
 
     /// Merge two coin objects into one coin
@@ -333,6 +552,8 @@ pub enum SuiClientCommands {
         /// Gas budget for this call
         #[clap(long)]
         gas_budget: u64,
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
 
     /// Create an example NFT
@@ -358,14 +579,17 @@ pub enum SuiClientCommands {
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: Option<u64>,
+
+        #[clap(flatten)]
+        tx_options: TxBuildOptions,
     },
 
     /// Serialize a transfer that can be signed. This is useful when user prefers to take the data to sign elsewhere.
     #[clap(name = "serialize-transfer-sui")]
     SerializeTransferSui {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
@@ -381,22 +605,31 @@ pub enum SuiClientCommands {
     },
 
     /// Execute a Signed Transaction. This is useful when the user prefers to sign elsewhere and use this command to execute.
+    /// The signature is given either by its scheme, public key and signature parts, or as a set
+    /// of serialized signatures, e.g. collected from several offline signers with `sui keytool sign`,
+    /// among which the signature of the transaction sender is used.
+    #[clap(group(ArgGroup::new("sig").required(true).args(&["signature", "signatures"])))]
     ExecuteSignedTx {
         /// Base64 encoded of the transaction data.
         #[clap(long)]
         tx_data: String,
 
         /// Signature scheme used to sign the transaction.
-        #[clap(long)]
-        scheme: SignatureScheme,
+        #[clap(long, requires_all = &["pubkey", "signature"])]
+        scheme: Option<SignatureScheme>,
 
         /// Public key that the signature can be verified with.
-        #[clap(long)]
-        pubkey: String,
+        #[clap(long, requires_all = &["scheme", "signature"])]
+        pubkey: Option<String>,
 
         /// Base64 encoded signature committed to the transaction data.
-        #[clap(long)]
-        signature: String,
+        #[clap(long, requires_all = &["scheme", "pubkey"])]
+        signature: Option<String>,
+
+        /// Base64 encoded serialized signatures, each the flag, signature and public key bytes
+        /// concatenated, as printed by `sui keytool sign`.
+        #[clap(long, multiple_occurrences = false, multiple_values = true)]
+        signatures: Vec<String>,
     },
 }
 
@@ -411,24 +644,38 @@ impl SuiClientCommands {
                 gas,
                 build_config,
                 gas_budget,
+                tx_options,
+                verify_deps,
             } => {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
-                let compiled_modules = build_move_package(
+                let compiled_package = build_move_package(
                     &package_path,
                     BuildConfig {
                         config: build_config,
                         run_bytecode_verifier: true,
                         print_diags_to_stderr: true,
                     },
-                )?
-                .get_package_bytes();
+                )?;
+                if verify_deps {
+                    let verification =
+                        verify_source(&context.client, &compiled_package, None).await?;
+                    if !verification.is_verified() {
+                        return Err(anyhow!(
+                            "Dependencies do not match their on-chain packages:\n{verification}"
+                        ));
+                    }
+                }
+                let compiled_modules = compiled_package.get_package_bytes();
                 let data = context
                     .client
                     .transaction_builder()
                     .publish(sender, compiled_modules, gas, gas_budget)
                     .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
                 let signature = context.config.keystore.sign(&sender, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature).verify()?)
@@ -442,6 +689,38 @@ impl SuiClientCommands {
                 let object_read = context.client.read_api().get_parsed_object(id).await?;
                 SuiClientCommandResult::Object(object_read)
             }
+            SuiClientCommands::DynamicFields { id } => {
+                SuiClientCommandResult::DynamicFields(context.get_dynamic_fields(id).await?)
+            }
+            SuiClientCommands::TxHistory {
+                address,
+                received,
+                limit,
+                cursor,
+            } => {
+                let address = context.address_or_active(address)?;
+                let query = if received {
+                    TransactionQuery::ToAddress(address)
+                } else {
+                    TransactionQuery::FromAddress(address)
+                };
+                let read_api = context.client.read_api();
+                let page = read_api
+                    .get_transactions(query, cursor, Some(limit), Some(true))
+                    .await?;
+                let mut data = Vec::with_capacity(page.data.len());
+                for digest in page.data {
+                    data.push(read_api.get_transaction(digest).await?);
+                }
+                SuiClientCommandResult::TxHistory(Page {
+                    data,
+                    next_cursor: page.next_cursor,
+                })
+            }
+            SuiClientCommands::Tx { digest } => {
+                let response = context.client.read_api().get_transaction(digest).await?;
+                SuiClientCommandResult::Tx(response)
+            }
             SuiClientCommands::Call {
                 package,
                 module,
@@ -450,11 +729,16 @@ impl SuiClientCommands {
                 gas,
                 gas_budget,
                 args,
+                tx_options,
             } => {
-                let (cert, effects) = call_move(
+                let data = build_move_call(
                     package, &module, &function, type_args, gas, gas_budget, args, context,
                 )
                 .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
+                let (cert, effects) = execute_move_call(data, context).await?;
                 SuiClientCommandResult::Call(cert, effects)
             }
 
@@ -463,7 +747,9 @@ impl SuiClientCommands {
                 object_id,
                 gas,
                 gas_budget,
+                tx_options,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
                 let time_start = Instant::now();
 
@@ -472,6 +758,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .transfer_object(from, object_id, gas, gas_budget, to)
                     .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
                 let signature = context.config.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature).verify()?)
@@ -491,7 +780,9 @@ impl SuiClientCommands {
                 sui_coin_object_id: object_id,
                 gas_budget,
                 amount,
+                tx_options,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
 
                 let data = context
@@ -499,6 +790,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .transfer_sui(from, object_id, gas_budget, to, amount)
                     .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
                 let signature = context.config.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature).verify()?)
@@ -518,6 +812,7 @@ impl SuiClientCommands {
                 amounts,
                 gas,
                 gas_budget,
+                tx_options,
             } => {
                 ensure!(
                     !input_coins.is_empty(),
@@ -535,12 +830,19 @@ impl SuiClientCommands {
                         amounts.len()
                     ),
                 );
+                let recipients = recipients
+                    .iter()
+                    .map(|recipient| context.config.resolve_address(recipient))
+                    .collect::<Result<Vec<_>, _>>()?;
                 let from = context.get_object_owner(&input_coins[0]).await?;
                 let data = context
                     .client
                     .transaction_builder()
                     .pay(from, input_coins, recipients, amounts, gas, gas_budget)
                     .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
                 let signature = context.config.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature).verify()?)
@@ -561,6 +863,7 @@ impl SuiClientCommands {
                 recipients,
                 amounts,
                 gas_budget,
+                tx_options,
             } => {
                 ensure!(
                     !input_coins.is_empty(),
@@ -578,12 +881,19 @@ impl SuiClientCommands {
                         amounts.len()
                     ),
                 );
+                let recipients = recipients
+                    .iter()
+                    .map(|recipient| context.config.resolve_address(recipient))
+                    .collect::<Result<Vec<_>, _>>()?;
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let data = context
                     .client
                     .transaction_builder()
                     .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
                     .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
                 let signature = context.config.keystore.sign(&signer, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature).verify()?)
@@ -604,17 +914,22 @@ impl SuiClientCommands {
                 input_coins,
                 recipient,
                 gas_budget,
+                tx_options,
             } => {
                 ensure!(
                     !input_coins.is_empty(),
                     "PayAllSui transaction requires a non-empty list of input coins"
                 );
+                let recipient = context.config.resolve_address(&recipient)?;
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let data = context
                     .client
                     .transaction_builder()
                     .pay_all_sui(signer, input_coins, recipient, gas_budget)
                     .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
 
                 let signature = context.config.keystore.sign(&signer, &data.to_bytes())?;
                 let response = context
@@ -637,7 +952,7 @@ impl SuiClientCommands {
             }
 
             SuiClientCommands::Objects { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.address_or_active(address)?;
                 let mut address_object = context
                     .client
                     .read_api()
@@ -653,9 +968,23 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Objects(address_object)
             }
 
+            SuiClientCommands::NewAddress {
+                key_scheme,
+                derivation_path: _,
+                index: Some(index),
+            } => {
+                let password = match std::env::var(SEED_PASSWORD_ENV_VAR) {
+                    Ok(password) => password,
+                    Err(_) => prompt_password()?,
+                };
+                SuiClientCommandResult::DerivedAddress(
+                    context.derive_new_address(key_scheme, index, &password)?,
+                )
+            }
             SuiClientCommands::NewAddress {
                 key_scheme,
                 derivation_path,
+                index: None,
             } => {
                 let (address, phrase, scheme) = context
                     .config
@@ -663,8 +992,29 @@ impl SuiClientCommands {
                     .generate_new_key(key_scheme, derivation_path)?;
                 SuiClientCommandResult::NewAddress((address, phrase, scheme))
             }
+            SuiClientCommands::Alias {
+                alias,
+                address,
+                remove,
+            } => {
+                match (alias, remove) {
+                    (Some(alias), true) => {
+                        if context.config.aliases.remove(&alias).is_none() {
+                            return Err(anyhow!("Unknown address alias [{alias}]"));
+                        }
+                    }
+                    (Some(alias), false) => {
+                        let address = context.address_or_active(address)?;
+                        context.config.set_alias(alias, address)?;
+                    }
+                    (None, true) => return Err(anyhow!("No alias specified to remove.")),
+                    (None, false) => {}
+                }
+                context.config.save()?;
+                SuiClientCommandResult::Aliases(context.config.aliases.clone())
+            }
             SuiClientCommands::Gas { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.address_or_active(address)?;
                 let coins = context
                     .gas_objects(address)
                     .await?
@@ -674,12 +1024,175 @@ impl SuiClientCommands {
                     .collect();
                 SuiClientCommandResult::Gas(coins)
             }
+            SuiClientCommands::EpochStats { epoch, limit } => {
+                let page = context
+                    .client
+                    .read_api()
+                    .get_epoch_stats(epoch, limit)
+                    .await?;
+                SuiClientCommandResult::EpochStats(page.data)
+            }
+            SuiClientCommands::Stake {
+                validator,
+                amount,
+                gas,
+                gas_budget,
+                tx_options,
+            } => {
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let (coin, gas) = context
+                    .select_stake_coin(
+                        sender,
+                        amount,
+                        gas,
+                        gas_budget,
+                        !tx_options.serialize_unsigned,
+                    )
+                    .await?;
+                let data = build_sui_system_call(
+                    ADD_DELEGATION_FUNCTION_NAME,
+                    vec![
+                        SuiJsonValue::from_object_id(coin),
+                        SuiJsonValue::new(json!(validator))?,
+                    ],
+                    gas,
+                    gas_budget,
+                    context,
+                )
+                .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
+                let (cert, effects) = execute_move_call(data, context).await?;
+                SuiClientCommandResult::Stake(cert, effects)
+            }
+
+            SuiClientCommands::Unstake {
+                delegation,
+                staked_sui,
+                pool_tokens,
+                gas,
+                gas_budget,
+                tx_options,
+            } => {
+                let (owner, delegation_object) = context.get_delegation(delegation).await?;
+                let pool_tokens =
+                    pool_tokens.unwrap_or_else(|| delegation_object.pool_tokens.value());
+                let gas = context.gas_for_delegation(owner, gas, gas_budget).await?;
+                let data = build_sui_system_call(
+                    WITHDRAW_DELEGATION_FUNCTION_NAME,
+                    vec![
+                        SuiJsonValue::from_object_id(delegation),
+                        SuiJsonValue::from_object_id(staked_sui),
+                        SuiJsonValue::new(json!(pool_tokens))?,
+                    ],
+                    gas,
+                    gas_budget,
+                    context,
+                )
+                .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
+                let (cert, effects) = execute_move_call(data, context).await?;
+                SuiClientCommandResult::Unstake(cert, effects)
+            }
+
+            SuiClientCommands::SwitchDelegation {
+                delegation,
+                staked_sui,
+                validator,
+                gas,
+                gas_budget,
+                tx_options,
+            } => {
+                let (owner, delegation_object) = context.get_delegation(delegation).await?;
+                ensure!(
+                    SuiAddress::from(delegation_object.validator_address) != validator,
+                    "Delegation {delegation} is already delegated to validator {validator}"
+                );
+                let gas = context.gas_for_delegation(owner, gas, gas_budget).await?;
+                let data = build_sui_system_call(
+                    SWITCH_DELEGATION_FUNCTION_NAME,
+                    vec![
+                        SuiJsonValue::from_object_id(delegation),
+                        SuiJsonValue::from_object_id(staked_sui),
+                        SuiJsonValue::new(json!(validator))?,
+                    ],
+                    gas,
+                    gas_budget,
+                    context,
+                )
+                .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
+                let (cert, effects) = execute_move_call(data, context).await?;
+                SuiClientCommandResult::SwitchDelegation(cert, effects)
+            }
+
+            SuiClientCommands::WithdrawRewards {
+                delegation,
+                staked_sui,
+                gas,
+                gas_budget,
+                tx_options,
+            } => {
+                let (owner, delegation_object) = context.get_delegation(delegation).await?;
+                let system_state = context.get_sui_system_state().await?;
+                let pool = find_staking_pool(&system_state, &delegation_object)?;
+                let pool_tokens = reward_pool_tokens(&delegation_object, pool);
+                ensure!(
+                    pool_tokens > 0,
+                    "Delegation {delegation} has not earned any rewards yet"
+                );
+                let gas = context.gas_for_delegation(owner, gas, gas_budget).await?;
+                let data = build_sui_system_call(
+                    WITHDRAW_DELEGATION_FUNCTION_NAME,
+                    vec![
+                        SuiJsonValue::from_object_id(delegation),
+                        SuiJsonValue::from_object_id(staked_sui),
+                        SuiJsonValue::new(json!(pool_tokens))?,
+                    ],
+                    gas,
+                    gas_budget,
+                    context,
+                )
+                .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
+                let (cert, effects) = execute_move_call(data, context).await?;
+                SuiClientCommandResult::WithdrawRewards(cert, effects)
+            }
+
+            SuiClientCommands::ListValidators => {
+                SuiClientCommandResult::ListValidators(context.get_sui_system_state().await?.into())
+            }
+
+            SuiClientCommands::MyStakes { address } => {
+                let address = context.address_or_active(address)?;
+                let delegations = context.get_delegated_stakes(address).await?;
+                let staked_suis = context
+                    .get_owned_move_objects::<StakedSui>(address, &StakedSui::type_())
+                    .await?
+                    .into_iter()
+                    .map(|(id, staked_sui)| OwnedStakedSui {
+                        id,
+                        principal: staked_sui.principal.value(),
+                    })
+                    .collect();
+                SuiClientCommandResult::MyStakes(delegations, staked_suis)
+            }
+
             SuiClientCommands::SplitCoin {
                 coin_id,
                 amounts,
                 count,
                 gas,
                 gas_budget,
+                tx_options,
             } => {
                 let signer = context.get_object_owner(&coin_id).await?;
                 let data = match (amounts, count) {
@@ -704,6 +1217,9 @@ impl SuiClientCommands {
                         return Err(anyhow!("Exactly one of `count` and `amounts` must be present for split-coin command."));
                     }
                 };
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
                 let signature = context.config.keystore.sign(&signer, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature).verify()?)
@@ -715,6 +1231,7 @@ impl SuiClientCommands {
                 coin_to_merge,
                 gas,
                 gas_budget,
+                tx_options,
             } => {
                 let signer = context.get_object_owner(&primary_coin).await?;
                 let data = context
@@ -722,6 +1239,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
                     .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
                 let signature = context.config.keystore.sign(&signer, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature).verify()?)
@@ -730,6 +1250,9 @@ impl SuiClientCommands {
                 SuiClientCommandResult::MergeCoin(response)
             }
             SuiClientCommands::Switch { address, env } => {
+                let address = address
+                    .map(|address| context.config.resolve_address(&address))
+                    .transpose()?;
                 match (address, &env) {
                     (None, Some(env)) => {
                         Self::switch_env(&mut context.config, env)?;
@@ -754,6 +1277,7 @@ impl SuiClientCommands {
                 url,
                 gas,
                 gas_budget,
+                tx_options,
             } => {
                 let args_json = json!([
                     unwrap_or(&name, EXAMPLE_NFT_NAME),
@@ -764,7 +1288,7 @@ impl SuiClientCommands {
                 for a in args_json.as_array().unwrap() {
                     args.push(SuiJsonValue::new(a.clone()).unwrap());
                 }
-                let (_, effects) = call_move(
+                let data = build_move_call(
                     ObjectID::from(SUI_FRAMEWORK_ADDRESS),
                     "devnet_nft",
                     "mint",
@@ -775,6 +1299,10 @@ impl SuiClientCommands {
                     context,
                 )
                 .await?;
+                if tx_options.serialize_unsigned {
+                    return Ok(SuiClientCommandResult::SerializeUnsigned(data.to_base64()));
+                }
+                let (_, effects) = execute_move_call(data, context).await?;
                 let nft_id = effects
                     .created
                     .first()
@@ -791,6 +1319,7 @@ impl SuiClientCommands {
                 gas_budget,
                 amount,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
 
                 let data = context
@@ -806,6 +1335,7 @@ impl SuiClientCommands {
                 scheme,
                 pubkey,
                 signature,
+                signatures,
             } => {
                 let data = TransactionData::from_signable_bytes(
                     &Base64::try_from(tx_data)
@@ -813,18 +1343,22 @@ impl SuiClientCommands {
                         .to_vec()
                         .map_err(|e| anyhow!(e))?,
                 )?;
-                let signed_tx = Transaction::new(
-                    data,
-                    Signature::from_bytes(
-                        &[
+                let mut serialized_signatures = signatures
+                    .iter()
+                    .map(|s| Base64::decode(s).map_err(|e| anyhow!(e)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if let (Some(scheme), Some(pubkey), Some(signature)) = (scheme, pubkey, signature) {
+                    serialized_signatures.push(
+                        [
                             vec![scheme.flag()],
                             Base64::decode(signature.as_str()).map_err(|e| anyhow!(e))?,
                             Base64::decode(pubkey.as_str()).map_err(|e| anyhow!(e))?,
                         ]
                         .concat(),
-                    )?,
-                )
-                .verify()?;
+                    );
+                }
+                let signature = select_sender_signature(&data, &serialized_signatures)?;
+                let signed_tx = Transaction::new(data, signature).verify()?;
 
                 let response = context.execute_transaction(signed_tx).await?;
                 SuiClientCommandResult::ExecuteSignedTx(response)
@@ -885,6 +1419,27 @@ impl WalletContext {
         Ok(context)
     }
 
+    /// Replace the client with one connected to the active env, after the env was switched.
+    pub async fn reconnect(&mut self) -> Result<(), anyhow::Error> {
+        // The embedded gateway of the simulator does not depend on the env.
+        #[cfg(not(msim))]
+        {
+            self.client = self.config.get_active_env()?.create_rpc_client().await?;
+        }
+        Ok(())
+    }
+
+    /// Resolve an address given on the command line, falling back to the active address.
+    pub fn address_or_active(
+        &mut self,
+        address: Option<AddressOrAlias>,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            Some(address) => self.config.resolve_address(&address),
+            None => self.active_address(),
+        }
+    }
+
     pub fn active_address(&mut self) -> Result<SuiAddress, anyhow::Error> {
         if self.config.keystore.addresses().is_empty() {
             return Err(anyhow!(
@@ -966,6 +1521,313 @@ impl WalletContext {
         }
     }
 
+    /// Read and decode the Sui system state object.
+    pub async fn get_sui_system_state(&self) -> Result<SuiSystemState, anyhow::Error> {
+        let object = self
+            .client
+            .read_api()
+            .get_object(SUI_SYSTEM_STATE_OBJECT_ID)
+            .await?
+            .into_object()?;
+        object
+            .data
+            .try_as_move()
+            .ok_or_else(|| anyhow!("Sui system state object is not a Move object"))?
+            .deserialize()
+    }
+
+    /// Get all the objects of type `type_` owned by the address, decoded.
+    async fn get_owned_move_objects<T: DeserializeOwned>(
+        &self,
+        address: SuiAddress,
+        type_: &StructTag,
+    ) -> Result<Vec<(ObjectID, T)>, anyhow::Error> {
+        let type_ = type_.to_string();
+        let mut objects = Vec::new();
+        for info in self
+            .client
+            .read_api()
+            .get_objects_owned_by_address(address)
+            .await?
+        {
+            if info.type_ != type_ {
+                continue;
+            }
+            let object = self
+                .client
+                .read_api()
+                .get_object(info.object_id)
+                .await?
+                .into_object()?;
+            if let Some(move_object) = object.data.try_as_move() {
+                objects.push((info.object_id, move_object.deserialize()?));
+            }
+        }
+        Ok(objects)
+    }
+
+    /// Derive the keypair at account `index` from the seed of the config, generating and storing
+    /// the seed, encrypted with `password`, if there is none yet. The keypair is added to the
+    /// keystore and the config saved.
+    pub fn derive_new_address(
+        &mut self,
+        key_scheme: SignatureScheme,
+        index: u32,
+        password: &str,
+    ) -> Result<DerivedAddress, anyhow::Error> {
+        let recovery_phrase = match &self.config.seed {
+            Some(_) => None,
+            None => {
+                let (seed, phrase) = EncryptedSeed::generate(password)?;
+                self.config.seed = Some(seed);
+                Some(phrase)
+            }
+        };
+        // Ok to unwrap, the seed was set above if missing.
+        let (address, keypair) =
+            self.config
+                .seed
+                .as_ref()
+                .unwrap()
+                .derive_key_pair(password, &key_scheme, index)?;
+        if !self.config.keystore.addresses().contains(&address) {
+            self.config.keystore.add_key(keypair)?;
+        }
+        self.config.save()?;
+        Ok(DerivedAddress {
+            address,
+            scheme: key_scheme,
+            index,
+            recovery_phrase,
+        })
+    }
+
+    /// Get the dynamic fields of an object, i.e. the `0x2::dynamic_field::Field` objects it owns.
+    pub async fn get_dynamic_fields(
+        &self,
+        id: ObjectID,
+    ) -> Result<Vec<DynamicFieldInfo>, anyhow::Error> {
+        let field_type = sui_framework_address_concat_string("::dynamic_field::Field<");
+        let wrapper_type = sui_framework_address_concat_string("::dynamic_object_field::Wrapper<");
+        let mut dynamic_fields = Vec::new();
+        for info in self
+            .client
+            .read_api()
+            .get_objects_owned_by_object(id)
+            .await?
+        {
+            if !info.type_.starts_with(&field_type) {
+                continue;
+            }
+            let object = self
+                .client
+                .read_api()
+                .get_parsed_object(info.object_id)
+                .await?
+                .into_object()?;
+            let mut fields = match object.data {
+                SuiParsedData::MoveObject(SuiParsedMoveObject {
+                    fields: SuiMoveStruct::WithFields(fields),
+                    ..
+                }) => fields,
+                _ => return Err(anyhow!("Object {} is not a dynamic field", info.object_id)),
+            };
+            let (name, value) = match (fields.remove("name"), fields.remove("value")) {
+                (Some(name), Some(value)) => (name, value),
+                _ => return Err(anyhow!("Object {} is not a dynamic field", info.object_id)),
+            };
+            // Dynamic object fields wrap their name, to tell them apart from dynamic fields.
+            let name = match name {
+                SuiMoveValue::Struct(SuiMoveStruct::WithTypes { type_, mut fields })
+                    if type_.starts_with(&wrapper_type) =>
+                {
+                    fields.remove("name").ok_or_else(|| {
+                        anyhow!("Malformed dynamic object field {}", info.object_id)
+                    })?
+                }
+                name => name,
+            };
+            // The value is stored in an option, which is always set.
+            let value = match value {
+                SuiMoveValue::Option(option) => match *option {
+                    Some(value) => value,
+                    None => return Err(anyhow!("Dynamic field {} has no value", info.object_id)),
+                },
+                value => value,
+            };
+            dynamic_fields.push(DynamicFieldInfo {
+                field_id: info.object_id,
+                type_: info.type_,
+                name,
+                value,
+            });
+        }
+        Ok(dynamic_fields)
+    }
+
+    /// Read a Delegation object and return it with its owner.
+    async fn get_delegation(
+        &self,
+        id: ObjectID,
+    ) -> Result<(SuiAddress, Delegation), anyhow::Error> {
+        let object = self.client.read_api().get_object(id).await?.into_object()?;
+        let owner = object.owner.get_owner_address()?;
+        let move_object = object
+            .data
+            .try_as_move()
+            .filter(|o| o.type_ == Delegation::type_().to_string())
+            .ok_or_else(|| anyhow!("Object {id} is not a Delegation"))?;
+        Ok((owner, move_object.deserialize()?))
+    }
+
+    /// Select a gas object of the delegation owner, unless one was given.
+    async fn gas_for_delegation(
+        &self,
+        owner: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> Result<Option<ObjectID>, anyhow::Error> {
+        if gas.is_some() {
+            return Ok(gas);
+        }
+        let (_, gas) = self
+            .gas_for_owner_budget(owner, gas_budget, BTreeSet::new())
+            .await?;
+        Ok(Some(gas.id()))
+    }
+
+    /// Select a coin of `sender` worth exactly `amount` to delegate. If no coin holds the exact
+    /// amount and `split` is set, the largest coins are merged and the amount split out of them
+    /// with a PaySui transaction, the remainder paying for the delegation unless `gas` is given.
+    /// Returns the coin to delegate and the gas object to pay for the delegation with.
+    async fn select_stake_coin(
+        &self,
+        sender: SuiAddress,
+        amount: u64,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+        split: bool,
+    ) -> Result<(ObjectID, Option<ObjectID>), anyhow::Error> {
+        let mut coins: Vec<_> = self
+            .gas_objects(sender)
+            .await?
+            .into_iter()
+            .map(|(value, object, _)| (value, object.id()))
+            .filter(|(_, id)| Some(*id) != gas)
+            .collect();
+        coins.sort_by(|a, b| b.0.cmp(&a.0));
+
+        if let Some((_, coin)) = coins.iter().find(|(value, _)| *value == amount) {
+            if gas.is_some()
+                || coins
+                    .iter()
+                    .any(|(value, id)| id != coin && *value >= gas_budget)
+            {
+                return Ok((*coin, gas));
+            }
+        }
+
+        ensure!(
+            split,
+            "No coin of {sender} holds exactly {amount} with another coin left to pay for gas, \
+             split one with `sui client split-coin` first"
+        );
+
+        let gas_budgets = if gas.is_some() { 1 } else { 2 };
+        let needed = gas_budget
+            .checked_mul(gas_budgets)
+            .and_then(|budget| budget.checked_add(amount))
+            .ok_or_else(|| anyhow!("Stake amount and gas budget overflow"))?;
+        let mut input_coins = Vec::new();
+        let mut total = 0u64;
+        for (value, id) in coins {
+            if total >= needed {
+                break;
+            }
+            input_coins.push(id);
+            total = total.saturating_add(value);
+        }
+        ensure!(
+            total >= needed,
+            "Address {sender} does not own enough SUI to delegate {amount} with gas budget \
+             {gas_budget}"
+        );
+
+        let data = self
+            .client
+            .transaction_builder()
+            .pay_sui(
+                sender,
+                input_coins.clone(),
+                vec![sender],
+                vec![amount],
+                gas_budget,
+            )
+            .await?;
+        let signature = self.config.keystore.sign(&sender, &data.to_bytes())?;
+        let response = self
+            .execute_transaction(Transaction::new(data, signature).verify()?)
+            .await?;
+        if matches!(response.effects.status, SuiExecutionStatus::Failure { .. }) {
+            return Err(anyhow!(
+                "Error splitting the stake amount out of coins: {:#?}",
+                response.effects.status
+            ));
+        }
+        let coin = response
+            .effects
+            .created
+            .iter()
+            .find(|o| o.owner == Owner::AddressOwner(sender))
+            .ok_or_else(|| anyhow!("PaySui transaction did not create the coin to delegate"))?
+            .reference
+            .object_id;
+        Ok((coin, gas.or(Some(input_coins[0]))))
+    }
+
+    /// List the active and pending delegations of an address.
+    pub async fn get_delegated_stakes(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Vec<DelegatedStake>, anyhow::Error> {
+        let system_state = self.get_sui_system_state().await?;
+        let delegations = self
+            .get_owned_move_objects::<Delegation>(address, &Delegation::type_())
+            .await?;
+
+        let mut stakes = Vec::new();
+        for (id, delegation) in delegations {
+            let pool_tokens = delegation.pool_tokens.value();
+            let value = find_staking_pool(&system_state, &delegation)
+                .map(|pool| pool.sui_amount(pool_tokens))
+                .unwrap_or_default();
+            stakes.push(DelegatedStake {
+                delegation_id: Some(id),
+                validator_address: delegation.validator_address.into(),
+                principal: delegation.principal_sui_amount,
+                pool_tokens,
+                value,
+            });
+        }
+
+        let delegator = AccountAddress::from(address);
+        for validator in &system_state.validators.active_validators {
+            let pool = &validator.delegation_staking_pool;
+            for pending in &pool.pending_delegations {
+                if pending.delegator == delegator {
+                    stakes.push(DelegatedStake {
+                        delegation_id: None,
+                        validator_address: pool.validator_address.into(),
+                        principal: pending.sui_amount,
+                        pool_tokens: 0,
+                        value: pending.sui_amount,
+                    });
+                }
+            }
+        }
+        Ok(stakes)
+    }
+
     /// Find a gas object which fits the budget
     pub async fn gas_for_owner_budget(
         &self,
@@ -1037,6 +1899,80 @@ impl Display for SuiClientCommandResult {
                 let object = unwrap_err_to_string(|| Ok(object_read.object()?));
                 writeln!(writer, "{}", object)?;
             }
+            SuiClientCommandResult::DynamicFields(fields) => {
+                writeln!(
+                    writer,
+                    " {0: ^42} | {1: ^30} | {2: ^30}",
+                    "Field ID", "Name", "Value"
+                )?;
+                writeln!(writer, "{}", ["-"; 110].join(""))?;
+                for field in fields {
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^30} | {2: ^30}",
+                        field.field_id,
+                        field.name.to_string(),
+                        field.value.to_string()
+                    )?;
+                }
+                writeln!(writer, "Showing {} results.", fields.len())?;
+            }
+            SuiClientCommandResult::TxHistory(page) => {
+                writeln!(
+                    writer,
+                    " {0: ^44} | {1: ^42} | {2: ^7} | {3: ^14}",
+                    "Transaction Digest", "Sender", "Status", "Timestamp (ms)"
+                )?;
+                writeln!(writer, "{}", ["-"; 116].join(""))?;
+                for response in &page.data {
+                    let status = if response.effects.status.is_ok() {
+                        "success"
+                    } else {
+                        "failure"
+                    };
+                    let timestamp = response
+                        .timestamp_ms
+                        .map_or_else(|| "unknown".to_string(), |ms| ms.to_string());
+                    writeln!(
+                        writer,
+                        " {0: ^44} | {1: ^42} | {2: ^7} | {3: ^14}",
+                        format!("{:?}", response.certificate.transaction_digest),
+                        response.certificate.data.sender,
+                        status,
+                        timestamp
+                    )?;
+                }
+                writeln!(writer, "Showing {} results.", page.data.len())?;
+                if let Some(cursor) = &page.next_cursor {
+                    writeln!(writer, "Next page with --cursor {cursor:?}")?;
+                }
+            }
+            SuiClientCommandResult::Tx(response) => {
+                write!(
+                    writer,
+                    "{}",
+                    write_cert_and_effects(&response.certificate, &response.effects)?
+                )?;
+                let gas_used = &response.effects.gas_used;
+                writeln!(
+                    writer,
+                    "Gas Used : computation {}, storage {}, storage rebate {}",
+                    gas_used.computation_cost, gas_used.storage_cost, gas_used.storage_rebate
+                )?;
+                if let Some(timestamp_ms) = response.timestamp_ms {
+                    writeln!(writer, "Timestamp (ms) : {timestamp_ms}")?;
+                }
+                if !response.effects.events.is_empty() {
+                    writeln!(writer, "{}", "----- Events ----".bold())?;
+                    for event in &response.effects.events {
+                        writeln!(
+                            writer,
+                            "{}",
+                            serde_json::to_string(event).map_err(|_| fmt::Error)?
+                        )?;
+                    }
+                }
+            }
             SuiClientCommandResult::Call(cert, effects) => {
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
@@ -1099,6 +2035,26 @@ impl Display for SuiClientCommandResult {
                 )?;
                 writeln!(writer, "Secret Recovery Phrase : [{recovery_phrase}]")?;
             }
+            SuiClientCommandResult::DerivedAddress(derived) => {
+                if let Some(recovery_phrase) = &derived.recovery_phrase {
+                    writeln!(
+                        writer,
+                        "Generated a new seed, back up its Secret Recovery Phrase : [{recovery_phrase}]"
+                    )?;
+                }
+                writeln!(
+                    writer,
+                    "Derived keypair at index {} for address with scheme {:?}: [{}]",
+                    derived.index, derived.scheme, derived.address
+                )?;
+            }
+            SuiClientCommandResult::Aliases(aliases) => {
+                writeln!(writer, " {0: ^20} | {1: ^42}", "Alias", "Address")?;
+                writeln!(writer, "{}", ["-"; 65].join(""))?;
+                for (alias, address) in aliases {
+                    writeln!(writer, " {0: <20} | {1: ^42}", alias, address)?;
+                }
+            }
             SuiClientCommandResult::Gas(gases) => {
                 // TODO: generalize formatting of CLI
                 writeln!(writer, " {0: ^42} | {1: ^11}", "Object ID", "Gas Value")?;
@@ -1110,6 +2066,107 @@ impl Display for SuiClientCommandResult {
                     writeln!(writer, " {0: ^42} | {1: ^11}", gas.id(), gas.value())?;
                 }
             }
+            SuiClientCommandResult::Stake(cert, effects)
+            | SuiClientCommandResult::Unstake(cert, effects)
+            | SuiClientCommandResult::SwitchDelegation(cert, effects)
+            | SuiClientCommandResult::WithdrawRewards(cert, effects) => {
+                write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
+            }
+            SuiClientCommandResult::ListValidators(validator_set) => {
+                writeln!(
+                    writer,
+                    "Epoch {}, reference gas price {}",
+                    validator_set.epoch, validator_set.reference_gas_price
+                )?;
+                writeln!(
+                    writer,
+                    " {0: ^20} | {1: ^42} | {2: ^15} | {3: ^15} | {4: ^9} | {5: ^20}",
+                    "Name", "Address", "Stake", "Pool Balance", "Gas Price", "Pool Exchange Rate"
+                )?;
+                writeln!(writer, "{}", ["-"; 135].join(""))?;
+                for validator in &validator_set.active_validators {
+                    let pool = &validator.delegation_staking_pool;
+                    writeln!(
+                        writer,
+                        " {0: ^20} | {1: ^42} | {2: ^15} | {3: ^15} | {4: ^9} | {5: ^20}",
+                        validator.metadata.name,
+                        validator.metadata.sui_address,
+                        validator.stake_amount,
+                        pool.sui_balance,
+                        validator.gas_price,
+                        format!(
+                            "{}/{}",
+                            pool.epoch_starting_sui_balance,
+                            pool.epoch_starting_delegation_token_supply
+                        )
+                    )?;
+                }
+            }
+            SuiClientCommandResult::MyStakes(stakes, staked_suis) => {
+                writeln!(
+                    writer,
+                    " {0: ^42} | {1: ^42} | {2: ^12} | {3: ^12}",
+                    "Delegation ID", "Validator", "Principal", "Value"
+                )?;
+                writeln!(writer, "{}", ["-"; 120].join(""))?;
+                for stake in stakes {
+                    let delegation = match stake.delegation_id {
+                        Some(id) => id.to_string(),
+                        None => "pending".to_string(),
+                    };
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^42} | {2: ^12} | {3: ^12}",
+                        delegation, stake.validator_address, stake.principal, stake.value
+                    )?;
+                }
+                writeln!(writer)?;
+                writeln!(writer, " {0: ^42} | {1: ^12}", "StakedSui ID", "Principal")?;
+                writeln!(writer, "{}", ["-"; 60].join(""))?;
+                for staked_sui in staked_suis {
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^12}",
+                        staked_sui.id, staked_sui.principal
+                    )?;
+                }
+            }
+            SuiClientCommandResult::EpochStats(stats) => {
+                for epoch in stats {
+                    let transaction_count = epoch
+                        .transaction_count
+                        .map_or_else(|| "unknown".to_string(), |count| count.to_string());
+                    writeln!(writer, "{}", format!("Epoch {}", epoch.epoch).bold())?;
+                    writeln!(writer, "Transactions : {transaction_count}")?;
+                    writeln!(
+                        writer,
+                        "Reference gas price : {}",
+                        epoch.reference_gas_price
+                    )?;
+                    writeln!(
+                        writer,
+                        "Computation charge : {}, storage charge : {}, storage rebate : {}",
+                        epoch.computation_charge, epoch.storage_charge, epoch.storage_rebate
+                    )?;
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^12} | {2: ^17} | {3: ^20}",
+                        "Validator", "Stake Reward", "Delegation Reward", "Pool Exchange Rate"
+                    )?;
+                    writeln!(writer, "{}", ["-"; 100].join(""))?;
+                    for rewards in epoch.validator_rewards {
+                        writeln!(
+                            writer,
+                            " {0: ^42} | {1: ^12} | {2: ^17} | {3: ^20}",
+                            rewards.sui_address,
+                            rewards.stake_reward,
+                            rewards.delegation_reward,
+                            format!("{}/{}", rewards.pool_sui_balance, rewards.pool_token_supply)
+                        )?;
+                    }
+                    writeln!(writer)?;
+                }
+            }
             SuiClientCommandResult::SplitCoin(response) => {
                 write!(
                     writer,
@@ -1155,7 +2212,8 @@ impl Display for SuiClientCommandResult {
                     writeln!(writer, "{}", parsed_resp)?;
                 }
             }
-            SuiClientCommandResult::SerializeTransferSui(res) => {
+            SuiClientCommandResult::SerializeTransferSui(res)
+            | SuiClientCommandResult::SerializeUnsigned(res) => {
                 write!(writer, "{}", res)?;
             }
             SuiClientCommandResult::ActiveEnv(env) => {
@@ -1188,10 +2246,29 @@ pub async fn call_move(
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
 ) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let data = build_move_call(
+        package, module, function, type_args, gas, gas_budget, args, context,
+    )
+    .await?;
+    execute_move_call(data, context).await
+}
+
+/// Build a Move call transaction, sent by the owner of the gas object if any, by the active
+/// address otherwise.
+pub async fn build_move_call(
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    args: Vec<SuiJsonValue>,
+    context: &mut WalletContext,
+) -> Result<TransactionData, anyhow::Error> {
     let gas_owner = context.try_get_object_owner(&gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
-    let data = context
+    context
         .client
         .transaction_builder()
         .move_call(
@@ -1207,7 +2284,14 @@ pub async fn call_move(
             gas,
             gas_budget,
         )
-        .await?;
+        .await
+}
+
+async fn execute_move_call(
+    data: TransactionData,
+    context: &mut WalletContext,
+) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let sender = data.signer();
     let signature = context.config.keystore.sign(&sender, &data.to_bytes())?;
     let transaction = Transaction::new(data, signature).verify()?;
 
@@ -1221,6 +2305,94 @@ pub async fn call_move(
     Ok((cert, effects))
 }
 
+/// Select the signature of the sender of `data` among serialized signatures, by the address of
+/// their public keys, and check that it signs `data`.
+fn select_sender_signature(
+    data: &TransactionData,
+    serialized_signatures: &[Vec<u8>],
+) -> Result<Signature, anyhow::Error> {
+    let sender = data.signer();
+    let mut sender_signature = None;
+    for bytes in serialized_signatures {
+        let signature = Signature::from_bytes(bytes)?;
+        let public_key =
+            PublicKey::try_from_bytes(signature.scheme(), signature.public_key_bytes())
+                .map_err(|e| anyhow!("Invalid public key in signature: {e}"))?;
+        if SuiAddress::from(&public_key) == sender {
+            sender_signature = Some(signature);
+            break;
+        }
+    }
+    let signature = sender_signature.ok_or_else(|| {
+        anyhow!(
+            "None of the {} signatures is by the transaction sender {sender}",
+            serialized_signatures.len()
+        )
+    })?;
+    signature.verify(data, sender).map_err(|e| {
+        anyhow!("The signature of the sender {sender} does not sign the transaction: {e}")
+    })?;
+    Ok(signature)
+}
+
+/// Build a call to an entry function of the `sui_system` module on the Sui system state object.
+async fn build_sui_system_call(
+    function: &IdentStr,
+    mut args: Vec<SuiJsonValue>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    context: &mut WalletContext,
+) -> Result<TransactionData, anyhow::Error> {
+    args.insert(0, SuiJsonValue::from_object_id(SUI_SYSTEM_STATE_OBJECT_ID));
+    build_move_call(
+        ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        SUI_SYSTEM_MODULE_NAME.as_str(),
+        function.as_str(),
+        vec![],
+        gas,
+        gas_budget,
+        args,
+        context,
+    )
+    .await
+}
+
+/// The staking pool `delegation` delegates to.
+fn find_staking_pool<'a>(
+    system_state: &'a SuiSystemState,
+    delegation: &Delegation,
+) -> Result<&'a StakingPool, anyhow::Error> {
+    system_state
+        .validators
+        .active_validators
+        .iter()
+        .map(|validator| &validator.delegation_staking_pool)
+        .find(|pool| {
+            pool.validator_address == delegation.validator_address
+                && pool.starting_epoch == delegation.pool_starting_epoch
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "Validator {} of the delegation is not active",
+                SuiAddress::from(delegation.validator_address)
+            )
+        })
+}
+
+/// The pool tokens of `delegation` earned as rewards: the pool tokens needed to stay worth the
+/// principal are kept, the others can be withdrawn.
+pub fn reward_pool_tokens(delegation: &Delegation, pool: &StakingPool) -> u64 {
+    delegation
+        .pool_tokens
+        .value()
+        .saturating_sub(pool.token_amount(delegation.principal_sui_amount))
+}
+
+/// Read the seed password from stdin.
+fn prompt_password() -> Result<String, anyhow::Error> {
+    Ok(rpassword::prompt_password("Enter the seed password: ")?)
+}
+
 fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
     match val {
         Some(v) => v,
@@ -1281,6 +2453,9 @@ impl SuiClientCommandResult {
 pub enum SuiClientCommandResult {
     Publish(SuiTransactionResponse),
     Object(GetObjectDataResponse),
+    DynamicFields(Vec<DynamicFieldInfo>),
+    TxHistory(Page<SuiTransactionResponse, TransactionDigest>),
+    Tx(SuiTransactionResponse),
     Call(SuiCertifiedTransaction, SuiTransactionEffects),
     Transfer(
         // Skipping serialisation for elapsed time.
@@ -1296,7 +2471,16 @@ pub enum SuiClientCommandResult {
     Objects(Vec<SuiObjectInfo>),
     SyncClientState,
     NewAddress((SuiAddress, String, SignatureScheme)),
+    DerivedAddress(DerivedAddress),
+    Aliases(BTreeMap<String, SuiAddress>),
     Gas(Vec<GasCoin>),
+    EpochStats(Vec<SuiEpochStats>),
+    Stake(SuiCertifiedTransaction, SuiTransactionEffects),
+    Unstake(SuiCertifiedTransaction, SuiTransactionEffects),
+    SwitchDelegation(SuiCertifiedTransaction, SuiTransactionEffects),
+    WithdrawRewards(SuiCertifiedTransaction, SuiTransactionEffects),
+    ListValidators(SuiValidatorSet),
+    MyStakes(Vec<DelegatedStake>, Vec<OwnedStakedSui>),
     SplitCoin(SuiTransactionResponse),
     MergeCoin(SuiTransactionResponse),
     Switch(SwitchResponse),
@@ -1305,10 +2489,56 @@ pub enum SuiClientCommandResult {
     Envs(Vec<SuiEnv>, Option<String>),
     CreateExampleNFT(GetObjectDataResponse),
     SerializeTransferSui(String),
+    SerializeUnsigned(String),
     ExecuteSignedTx(SuiTransactionResponse),
     NewEnv(SuiEnv),
 }
 
+/// A dynamic field of an object, as listed by `dynamic-fields`.
+#[derive(Serialize, Clone, Debug)]
+pub struct DynamicFieldInfo {
+    /// The `0x2::dynamic_field::Field` object storing the field.
+    pub field_id: ObjectID,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub name: SuiMoveValue,
+    /// The value of the field, the ID of the object for dynamic object fields.
+    pub value: SuiMoveValue,
+}
+
+/// A delegation of an address, as listed by `my-stakes`.
+#[derive(Serialize, Clone, Debug)]
+pub struct DelegatedStake {
+    /// The Delegation object, None while the delegation is pending until the next epoch.
+    pub delegation_id: Option<ObjectID>,
+    pub validator_address: SuiAddress,
+    pub principal: u64,
+    pub pool_tokens: u64,
+    /// The SUI value of the delegation at the exchange rate of the current epoch, i.e. the
+    /// principal plus the rewards earned so far.
+    pub value: u64,
+}
+
+/// A StakedSui object of an address, as listed by `my-stakes`. It holds the principal of one of
+/// the delegations of the address, and must be passed along with the Delegation object to
+/// unstake, switch the delegation or withdraw its rewards.
+#[derive(Serialize, Clone, Debug)]
+pub struct OwnedStakedSui {
+    pub id: ObjectID,
+    pub principal: u64,
+}
+
+/// An address derived from the seed of the config by `new-address --index`.
+#[derive(Serialize, Debug)]
+pub struct DerivedAddress {
+    pub address: SuiAddress,
+    pub scheme: SignatureScheme,
+    pub index: u32,
+    /// The mnemonic phrase of the seed, only set when the seed was generated for this address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_phrase: Option<String>,
+}
+
 #[derive(Serialize, Clone, Debug)]
 pub struct SwitchResponse {
     /// Active address
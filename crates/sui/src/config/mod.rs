@@ -2,7 +2,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
 
 use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,7 @@ pub use sui_config::utils;
 pub use sui_config::Config;
 pub use sui_config::PersistedConfig;
 use sui_config::SUI_DEV_NET_URL;
+use sui_keys::encrypted_seed::EncryptedSeed;
 use sui_keys::keystore::AccountKeystore;
 use sui_keys::keystore::Keystore;
 use sui_sdk::SuiClient;
@@ -24,6 +27,13 @@ pub struct SuiClientConfig {
     pub envs: Vec<SuiEnv>,
     pub active_env: Option<String>,
     pub active_address: Option<SuiAddress>,
+    /// Human readable aliases of addresses, usable in place of the addresses in commands.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, SuiAddress>,
+    /// The seed new addresses are derived from by index, created by the first
+    /// `new-address --index`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<EncryptedSeed>,
 }
 
 impl SuiClientConfig {
@@ -33,9 +43,47 @@ impl SuiClientConfig {
             envs: vec![],
             active_env: None,
             active_address: None,
+            aliases: BTreeMap::new(),
+            seed: None,
         }
     }
 
+    /// The address an alias stands for, or the address itself.
+    pub fn resolve_address(&self, address: &AddressOrAlias) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            AddressOrAlias::Address(address) => Ok(*address),
+            AddressOrAlias::Alias(alias) => self
+                .aliases
+                .get(alias)
+                .copied()
+                .ok_or_else(|| anyhow!("Unknown address alias [{alias}]")),
+        }
+    }
+
+    pub fn alias_of(&self, address: &SuiAddress) -> Option<&str> {
+        self.aliases
+            .iter()
+            .find(|(_, aliased)| *aliased == address)
+            .map(|(alias, _)| alias.as_str())
+    }
+
+    /// Give `address` the alias `alias`, replacing the previous alias of the address.
+    pub fn set_alias(&mut self, alias: String, address: SuiAddress) -> Result<(), anyhow::Error> {
+        if !is_valid_alias(&alias) {
+            return Err(anyhow!(
+                "Invalid alias [{alias}], aliases start with a letter followed by letters, digits, '-' or '_', and are not addresses"
+            ));
+        }
+        if let Some(aliased) = self.aliases.get(&alias) {
+            if *aliased != address {
+                return Err(anyhow!("Alias [{alias}] is already used by {aliased}"));
+            }
+        }
+        self.aliases.retain(|_, aliased| *aliased != address);
+        self.aliases.insert(alias, address);
+        Ok(())
+    }
+
     pub fn get_env(&self, alias: &Option<String>) -> Option<&SuiEnv> {
         if let Some(alias) = alias {
             self.envs.iter().find(|env| &env.alias == alias)
@@ -64,6 +112,49 @@ impl SuiClientConfig {
     }
 }
 
+/// An address, or its alias in the client config, as accepted by client commands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressOrAlias {
+    Address(SuiAddress),
+    Alias(String),
+}
+
+impl FromStr for AddressOrAlias {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(address) = decode_bytes_hex(s) {
+            Ok(AddressOrAlias::Address(address))
+        } else if is_valid_alias(s) {
+            Ok(AddressOrAlias::Alias(s.to_string()))
+        } else {
+            Err(anyhow!("[{s}] is neither an address nor an alias"))
+        }
+    }
+}
+
+impl From<SuiAddress> for AddressOrAlias {
+    fn from(address: SuiAddress) -> Self {
+        AddressOrAlias::Address(address)
+    }
+}
+
+impl Display for AddressOrAlias {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressOrAlias::Address(address) => write!(f, "{address}"),
+            AddressOrAlias::Alias(alias) => write!(f, "{alias}"),
+        }
+    }
+}
+
+fn is_valid_alias(alias: &str) -> bool {
+    let mut chars = alias.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && decode_bytes_hex::<SuiAddress>(alias).is_err()
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SuiEnv {
     pub alias: String,
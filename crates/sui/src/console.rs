@@ -1,15 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::io::{stderr, Write};
+use std::io::Write;
 use std::ops::Deref;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use clap::Command;
 use clap::CommandFactory;
 use clap::FromArgMatches;
 use clap::Parser;
 use colored::Colorize;
+use serde_json::Value;
 
 use crate::client_commands::SwitchResponse;
 use crate::client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext};
@@ -38,7 +40,6 @@ pub async fn start_console(
     out: &mut (dyn Write + Send),
     err: &mut (dyn Write + Send),
 ) -> Result<(), anyhow::Error> {
-    let app: Command = SuiClientCommands::command();
     writeln!(out, "{}", SUI.cyan().bold())?;
     let mut version = env!("CARGO_PKG_VERSION").to_owned();
     if let Some(git_rev) = std::option_env!("GIT_REVISION") {
@@ -81,14 +82,25 @@ pub async fn start_console(
         writeln!(out)?;
     };
 
-    let mut shell = Shell::new(
+    new_shell(context).run_async(out, err).await
+}
+
+/// Run the console commands in `script`, stopping at the first command that fails.
+pub async fn run_console_script(
+    context: WalletContext,
+    script: &str,
+    out: &mut (dyn Write + Send),
+) -> Result<(), anyhow::Error> {
+    new_shell(context).run_script(script, out).await
+}
+
+fn new_shell(context: WalletContext) -> Shell<&'static str, WalletContext, ClientCommandHandler> {
+    Shell::new(
         "sui>-$ ",
         context,
         ClientCommandHandler,
-        CommandStructure::from_clap(&install_shell_plugins(app)),
-    );
-
-    shell.run_async(out, err).await
+        CommandStructure::from_clap(&install_shell_plugins(SuiClientCommands::command())),
+    )
 }
 
 struct ClientCommandHandler;
@@ -100,14 +112,8 @@ impl AsyncHandler<WalletContext> for ClientCommandHandler {
         args: Vec<String>,
         context: &mut WalletContext,
         completion_cache: CompletionCache,
-    ) -> bool {
-        match handle_command(get_command(args), context, completion_cache).await {
-            Err(e) => {
-                let _err = writeln!(stderr(), "{}", e.to_string().red());
-                false
-            }
-            Ok(return_value) => return_value,
-        }
+    ) -> Result<Option<Value>, anyhow::Error> {
+        handle_command(get_command(args), context, completion_cache).await
     }
 }
 
@@ -122,7 +128,7 @@ async fn handle_command(
     wallet_opts: Result<ConsoleOpts, anyhow::Error>,
     context: &mut WalletContext,
     completion_cache: CompletionCache,
-) -> Result<bool, anyhow::Error> {
+) -> Result<Option<Value>, anyhow::Error> {
     let wallet_opts = wallet_opts?;
     let result = wallet_opts.command.execute(context).await?;
 
@@ -152,13 +158,12 @@ async fn handle_command(
     }
     result.print(!wallet_opts.json);
 
-    // Quit shell after RPC switch
-    if matches!(
-        result,
-        SuiClientCommandResult::Switch(SwitchResponse { env: Some(_), .. })
-    ) {
-        println!("Sui environment switch completed, please restart Sui console.");
-        return Ok(true);
+    // Connect to the new env's full node after an env switch
+    if let SuiClientCommandResult::Switch(SwitchResponse { env: Some(env), .. }) = &result {
+        context
+            .reconnect()
+            .await
+            .map_err(|e| anyhow!("Cannot connect to environment {env}: {e}"))?;
     }
-    Ok(false)
+    Ok(Some(serde_json::to_value(&result)?))
 }
@@ -1,23 +1,26 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use camino::Utf8PathBuf;
 use clap::Parser;
 use multiaddr::Multiaddr;
 use signature::{Signer, Verifier};
+use std::collections::BTreeMap;
 use std::{fs, path::PathBuf};
 use sui_config::{
     genesis::{Builder, Genesis},
-    SUI_GENESIS_FILENAME,
+    DEFAULT_COMMISSION_RATE, DEFAULT_GAS_PRICE, DEFAULT_STAKE, SUI_GENESIS_FILENAME,
 };
 use sui_types::{
     base_types::{decode_bytes_hex, encode_bytes_hex, ObjectID, SuiAddress},
+    committee::StakeUnit,
     crypto::{
         generate_proof_of_possession, AuthorityKeyPair, AuthorityPublicKey,
         AuthorityPublicKeyBytes, AuthoritySignature, KeypairTraits, NetworkKeyPair, SuiKeyPair,
         ToFromBytes,
     },
+    gas_coin::GasCoin,
     object::Object,
 };
 
@@ -65,6 +68,24 @@ pub enum CeremonyCommand {
         narwhal_worker_address: Multiaddr,
         #[clap(long)]
         narwhal_consensus_address: Multiaddr,
+        /// Stake of the validator at genesis
+        #[clap(long, default_value_t = DEFAULT_STAKE)]
+        stake: StakeUnit,
+        /// Gas price quote of the validator
+        #[clap(long, default_value_t = DEFAULT_GAS_PRICE)]
+        gas_price: u64,
+        /// Commission charged on the rewards of delegators, in basis points
+        #[clap(long, default_value_t = DEFAULT_COMMISSION_RATE)]
+        commission_rate: u64,
+        /// Description of the validator, for display
+        #[clap(long, default_value = "")]
+        description: String,
+        /// URL of an image representing the validator
+        #[clap(long, default_value = "")]
+        image_url: String,
+        /// URL of the validator's project or operator
+        #[clap(long, default_value = "")]
+        project_url: String,
     },
 
     AddGasObject {
@@ -76,6 +97,10 @@ pub enum CeremonyCommand {
         value: u64,
     },
 
+    /// Check the validator set for duplicate keys or addresses, invalid proofs of possession of
+    /// the protocol keys and unsound stake.
+    Validate,
+
     Build,
 
     VerifyAndSign {
@@ -83,6 +108,9 @@ pub enum CeremonyCommand {
         key_file: PathBuf,
     },
 
+    /// Print the validators and gas objects of the built genesis, and which validators signed it.
+    Summary,
+
     Finalize,
 }
 
@@ -110,6 +138,12 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             narwhal_primary_address,
             narwhal_worker_address,
             narwhal_consensus_address,
+            stake,
+            gas_price,
+            commission_rate,
+            description,
+            image_url,
+            project_url,
         } => {
             let mut builder = Builder::load(&dir)?;
             let keypair: AuthorityKeyPair = read_authority_keypair_from_file(validator_key_file)?;
@@ -124,13 +158,17 @@ pub fn run(cmd: Ceremony) -> Result<()> {
                     worker_key: worker_keypair.public().clone(),
                     account_key: account_keypair.public(),
                     network_key: network_keypair.public().clone(),
-                    stake: 1,
+                    stake,
                     delegation: 0,
-                    gas_price: 1,
+                    gas_price,
+                    commission_rate,
                     network_address,
                     narwhal_primary_address,
                     narwhal_worker_address,
                     narwhal_consensus_address,
+                    description,
+                    image_url,
+                    project_url,
                 },
                 pop,
             );
@@ -151,10 +189,27 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             builder.save(dir)?;
         }
 
-        CeremonyCommand::Build => {
+        CeremonyCommand::Validate => {
             let builder = Builder::load(&dir)?;
+            if builder.validators().is_empty() {
+                return Err(anyhow::anyhow!("no validators in the validator set"));
+            }
+            builder.validate()?;
+
+            let total_stake: StakeUnit = builder
+                .validators()
+                .values()
+                .map(|validator| validator.info.stake() + validator.info.delegation())
+                .sum();
+            println!(
+                "Successfully validated {} validators with a total stake of {total_stake}",
+                builder.validators().len()
+            );
+        }
 
-            let genesis = builder.build();
+        CeremonyCommand::Build => {
+            let builder = Builder::load(&dir)?;
+            let genesis = builder.build()?;
 
             genesis.save(dir.join(SUI_GENESIS_FILENAME))?;
 
@@ -171,8 +226,7 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             let loaded_genesis_bytes = loaded_genesis.to_bytes();
 
             let builder = Builder::load(&dir)?;
-
-            let built_genesis = builder.build();
+            let built_genesis = builder.build()?;
             let built_genesis_bytes = built_genesis.to_bytes();
 
             if built_genesis != loaded_genesis || built_genesis_bytes != loaded_genesis_bytes {
@@ -181,13 +235,17 @@ pub fn run(cmd: Ceremony) -> Result<()> {
                 ));
             }
 
-            if !built_genesis.validator_set().iter().any(|validator| {
-                validator.protocol_key() == AuthorityPublicKeyBytes::from(keypair.public())
-            }) {
-                return Err(anyhow::anyhow!(
-                    "provided keypair does not correspond to a validator in the validator set"
-                ));
-            }
+            let validator = built_genesis
+                .validator_set()
+                .iter()
+                .find(|validator| {
+                    validator.protocol_key() == AuthorityPublicKeyBytes::from(keypair.public())
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "provided keypair does not correspond to a validator in the validator set"
+                    )
+                })?;
 
             // Sign the genesis bytes
             let signature: AuthoritySignature = keypair.try_sign(&built_genesis_bytes)?;
@@ -198,61 +256,137 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             let hex_name = encode_bytes_hex(AuthorityPublicKeyBytes::from(keypair.public()));
             fs::write(signature_dir.join(hex_name), signature)?;
 
-            println!("Successfully verified {SUI_GENESIS_FILENAME}");
+            println!(
+                "Successfully verified {SUI_GENESIS_FILENAME}, signed as validator {}",
+                validator.name()
+            );
             println!(
                 "{SUI_GENESIS_FILENAME} sha3-256: {}",
                 hex::encode(built_genesis.sha3())
             );
         }
 
-        CeremonyCommand::Finalize => {
+        CeremonyCommand::Summary => {
             let genesis = Genesis::load(dir.join(SUI_GENESIS_FILENAME))?;
             let genesis_bytes = genesis.to_bytes();
+            let mut signatures = load_signatures(&dir)?;
 
-            let mut signatures = std::collections::BTreeMap::new();
+            println!(
+                "{SUI_GENESIS_FILENAME} sha3-256: {}",
+                hex::encode(genesis.sha3())
+            );
 
-            for entry in dir.join(GENESIS_BUILDER_SIGNATURE_DIR).read_dir_utf8()? {
-                let entry = entry?;
-                if entry.file_name().starts_with('.') {
-                    continue;
-                }
+            let total_stake: StakeUnit = genesis
+                .validator_set()
+                .iter()
+                .map(|validator| validator.stake() + validator.delegation())
+                .sum();
+            println!(
+                "\n{} validators, total stake {total_stake}:",
+                genesis.validator_set().len()
+            );
+            println!(
+                " {0: ^20} | {1: ^42} | {2: ^16} | {3: ^10} | {4: ^10} | {5: ^10}",
+                "Name", "Address", "Stake", "Gas Price", "Commission", "Signed"
+            );
+            println!("{}", ["-"; 125].join(""));
+            for validator in genesis.validator_set() {
+                let signed = match signatures.remove(&validator.protocol_key()) {
+                    None => "missing",
+                    Some(signature) => {
+                        let pk: AuthorityPublicKey = validator.protocol_key().try_into()?;
+                        if pk.verify(&genesis_bytes, &signature).is_ok() {
+                            "yes"
+                        } else {
+                            "invalid"
+                        }
+                    }
+                };
+                println!(
+                    " {0: <20} | {1: ^42} | {2: >16} | {3: >10} | {4: >9}% | {5: ^10}",
+                    validator.name(),
+                    validator.sui_address(),
+                    validator.stake() + validator.delegation(),
+                    validator.gas_price(),
+                    validator.commission_rate() as f64 / 100.0,
+                    signed
+                );
+            }
+            for public_key in signatures.keys() {
+                println!(
+                    "Signature from {} which is not in the validator set",
+                    encode_bytes_hex(public_key)
+                );
+            }
 
-                let path = entry.path();
-                let signature_bytes = fs::read(path)?;
-                let signature: AuthoritySignature =
-                    AuthoritySignature::from_bytes(&signature_bytes)?;
-                let name = path
-                    .file_name()
-                    .ok_or_else(|| anyhow::anyhow!("Invalid signature file"))?;
-                let public_key =
-                    AuthorityPublicKeyBytes::from_bytes(&decode_bytes_hex::<Vec<u8>>(name)?[..])?;
-                signatures.insert(public_key, signature);
+            let mut gas: BTreeMap<SuiAddress, (usize, u64)> = BTreeMap::new();
+            for object in genesis.objects() {
+                if let (Ok(coin), Some(owner)) =
+                    (GasCoin::try_from(object), object.get_single_owner())
+                {
+                    let (count, value) = gas.entry(owner).or_default();
+                    *count += 1;
+                    *value += coin.value();
+                }
             }
+            println!("\n{} accounts own gas objects:", gas.len());
+            println!(
+                " {0: ^42} | {1: ^7} | {2: ^20}",
+                "Owner", "Objects", "Total Value"
+            );
+            println!("{}", ["-"; 76].join(""));
+            for (owner, (count, value)) in gas {
+                println!(" {0: ^42} | {1: >7} | {2: >20}", owner, count, value);
+            }
+        }
+
+        CeremonyCommand::Finalize => {
+            let genesis = Genesis::load(dir.join(SUI_GENESIS_FILENAME))?;
+            let genesis_bytes = genesis.to_bytes();
 
+            let mut signatures = load_signatures(&dir)?;
+
+            let mut missing = Vec::new();
+            let mut invalid = Vec::new();
             for validator in genesis.validator_set() {
-                let signature = signatures
-                    .remove(&validator.protocol_key())
-                    .ok_or_else(|| {
-                        anyhow::anyhow!("missing signature for validator {}", validator.name())
-                    })?;
+                let signature = match signatures.remove(&validator.protocol_key()) {
+                    Some(signature) => signature,
+                    None => {
+                        missing.push(validator.name());
+                        continue;
+                    }
+                };
 
                 let pk: AuthorityPublicKey = validator.protocol_key().try_into()?;
 
-                pk.verify(&genesis_bytes, &signature).with_context(|| {
-                    format!(
-                        "failed to validate signature for validator {}",
-                        validator.name()
-                    )
-                })?;
+                if pk.verify(&genesis_bytes, &signature).is_err() {
+                    invalid.push(validator.name());
+                }
+            }
+
+            if !missing.is_empty() || !invalid.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "missing signatures from validators [{}], invalid signatures from validators [{}]",
+                    missing.join(", "),
+                    invalid.join(", ")
+                ));
             }
 
             if !signatures.is_empty() {
                 return Err(anyhow::anyhow!(
-                    "found extra signatures from entities not in the validator set"
+                    "found extra signatures from entities not in the validator set: {}",
+                    signatures
+                        .keys()
+                        .map(encode_bytes_hex)
+                        .collect::<Vec<_>>()
+                        .join(", ")
                 ));
             }
 
-            println!("Successfully finalized Genesis!");
+            println!(
+                "Successfully finalized Genesis, signed by all {} validators!",
+                genesis.validator_set().len()
+            );
             println!(
                 "{SUI_GENESIS_FILENAME} sha3-256: {}",
                 hex::encode(genesis.sha3())
@@ -263,6 +397,35 @@ pub fn run(cmd: Ceremony) -> Result<()> {
     Ok(())
 }
 
+/// Load the signatures of the genesis blob, keyed by the protocol key of their signer.
+fn load_signatures(
+    dir: &Utf8PathBuf,
+) -> Result<BTreeMap<AuthorityPublicKeyBytes, AuthoritySignature>> {
+    let mut signatures = BTreeMap::new();
+
+    let signature_dir = dir.join(GENESIS_BUILDER_SIGNATURE_DIR);
+    if !signature_dir.exists() {
+        return Ok(signatures);
+    }
+    for entry in signature_dir.read_dir_utf8()? {
+        let entry = entry?;
+        if entry.file_name().starts_with('.') {
+            continue;
+        }
+
+        let path = entry.path();
+        let signature_bytes = fs::read(path)?;
+        let signature: AuthoritySignature = AuthoritySignature::from_bytes(&signature_bytes)?;
+        let name = path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Invalid signature file"))?;
+        let public_key =
+            AuthorityPublicKeyBytes::from_bytes(&decode_bytes_hex::<Vec<u8>>(name)?[..])?;
+        signatures.insert(public_key, signature);
+    }
+    Ok(signatures)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -290,13 +453,17 @@ mod test {
                     worker_key: worker_keypair.public().clone(),
                     account_key: account_keypair.public().clone().into(),
                     network_key: network_keypair.public().clone(),
-                    stake: 1,
+                    stake: DEFAULT_STAKE * (i + 1),
                     delegation: 0,
-                    gas_price: 1,
+                    gas_price: i + 1,
+                    commission_rate: 100 * i,
                     network_address: utils::new_network_address(),
                     narwhal_primary_address: utils::new_network_address(),
                     narwhal_worker_address: utils::new_network_address(),
                     narwhal_consensus_address: utils::new_network_address(),
+                    description: format!("Validator number {i}"),
+                    image_url: String::new(),
+                    project_url: format!("https://validator-{i}.example.com"),
                 };
                 let key_file = dir.path().join(format!("{}-0.key", info.name));
                 write_authority_keypair_to_file(&keypair, &key_file).unwrap();
@@ -355,11 +522,24 @@ mod test {
                     narwhal_primary_address: validator.narwhal_primary_address.clone(),
                     narwhal_worker_address: validator.narwhal_worker_address.clone(),
                     narwhal_consensus_address: validator.narwhal_consensus_address.clone(),
+                    stake: validator.stake(),
+                    gas_price: validator.gas_price(),
+                    commission_rate: validator.commission_rate(),
+                    description: validator.description().to_owned(),
+                    image_url: validator.image_url().to_owned(),
+                    project_url: validator.project_url().to_owned(),
                 },
             };
             command.run()?;
         }
 
+        // Validate the validator set
+        let command = Ceremony {
+            path: Some(dir.path().into()),
+            command: CeremonyCommand::Validate,
+        };
+        command.run()?;
+
         // Build the Genesis object
         let command = Ceremony {
             path: Some(dir.path().into()),
@@ -367,6 +547,13 @@ mod test {
         };
         command.run()?;
 
+        // Genesis can't be finalized before the validators signed it
+        let command = Ceremony {
+            path: Some(dir.path().into()),
+            command: CeremonyCommand::Finalize,
+        };
+        assert!(command.run().is_err());
+
         // Have all the validators verify and sign genesis
         for (key, _worker_key, _network_key, _account_key, _validator) in &validators {
             let command = Ceremony {
@@ -378,6 +565,29 @@ mod test {
             command.run()?;
         }
 
+        let command = Ceremony {
+            path: Some(dir.path().into()),
+            command: CeremonyCommand::Summary,
+        };
+        command.run()?;
+
+        // The stakes and validator metadata made it into genesis and its system object
+        let genesis = Genesis::load(dir.path().join(SUI_GENESIS_FILENAME))?;
+        let system_object = genesis.sui_system_object();
+        for (_key, _worker_key, _network_key, _account_key, validator) in &validators {
+            assert!(genesis.validator_set().contains(validator));
+            let metadata = &system_object
+                .validators
+                .active_validators
+                .iter()
+                .find(|onchain| onchain.metadata.name == validator.name().as_bytes())
+                .unwrap()
+                .metadata;
+            assert_eq!(metadata.commission_rate, validator.commission_rate());
+            assert_eq!(metadata.description, validator.description().as_bytes());
+            assert_eq!(metadata.project_url, validator.project_url().as_bytes());
+        }
+
         // Finalize the Ceremony
         let command = Ceremony {
             path: Some(dir.path().into()),
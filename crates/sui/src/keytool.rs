@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use core::fmt;
+use std::fmt::{Debug, Display, Formatter, Write};
 use std::fs;
+use std::io::{self, Write as _};
 use std::path::{Path, PathBuf};
 
 use anyhow::anyhow;
@@ -9,6 +12,7 @@ use bip32::{DerivationPath, Mnemonic};
 use clap::*;
 use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+use serde::Serialize;
 use signature::rand_core::OsRng;
 use sui_keys::key_derive::derive_key_pair_from_path;
 use tracing::info;
@@ -18,9 +22,13 @@ use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_types::base_types::SuiAddress;
 use sui_types::base_types::{decode_bytes_hex, encode_bytes_hex};
 use sui_types::crypto::{
-    get_key_pair, AuthorityKeyPair, Ed25519SuiSignature, EncodeDecodeBase64, NetworkKeyPair,
-    SignatureScheme, SuiKeyPair, SuiSignatureInner,
+    generate_proof_of_possession, get_key_pair, AuthorityKeyPair, Ed25519SuiSignature,
+    EncodeDecodeBase64, KeypairTraits, NetworkKeyPair, SignatureScheme, SuiKeyPair,
+    SuiSignatureInner,
 };
+
+use crate::config::{AddressOrAlias, SuiClientConfig};
+
 #[cfg(test)]
 #[path = "unit_tests/keytool_tests.rs"]
 mod keytool_tests;
@@ -45,7 +53,9 @@ pub enum KeyToolCommand {
     },
     /// List all keys by its address, public key, key scheme in the keystore
     List,
-    /// Create signature using the sui keystore and provided data.
+    /// Create signature using the sui keystore and provided data, e.g. the Base64 transaction data
+    /// printed by `sui client` commands with `--serialize-unsigned`. The serialized signature can
+    /// be passed to `sui client execute-signed-tx --signatures`.
     Sign {
         #[clap(long, parse(try_from_str = decode_bytes_hex))]
         address: SuiAddress,
@@ -59,6 +69,33 @@ pub enum KeyToolCommand {
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
     },
+    /// Export the keypair of an address in the keystore, Base64 encoded with its key scheme flag.
+    /// Anyone holding the exported keypair controls the address.
+    Export {
+        /// The address, or its alias in the client config
+        address: AddressOrAlias,
+        /// Skip the confirmation prompt
+        #[clap(long)]
+        yes: bool,
+    },
+    /// Remove the keypair of an address from the keystore, along with its alias in the client
+    /// config. If the address was the active address, another address of the keystore becomes
+    /// the active address.
+    Remove {
+        /// The address, or its alias in the client config
+        address: AddressOrAlias,
+    },
+    /// Generate the proof of possession of a validator protocol key, i.e. the signature by the
+    /// protocol key binding it to the account address of the validator, which the validator
+    /// registers with its protocol key.
+    ProofOfPossession {
+        /// File holding the protocol keypair, as written by `generate bls12381`
+        #[clap(long)]
+        protocol_key_file: PathBuf,
+        /// Account address of the validator
+        #[clap(long, parse(try_from_str = decode_bytes_hex))]
+        address: SuiAddress,
+    },
     /// Read keypair from path and show its base64 encoded value with flag. This is useful
     /// to generate protocol, account, worker, network keys in NodeConfig with its expected encoding.
     LoadKeypair {
@@ -67,66 +104,72 @@ pub enum KeyToolCommand {
 }
 
 impl KeyToolCommand {
-    pub fn execute(self, keystore: &mut Keystore) -> Result<(), anyhow::Error> {
-        match self {
+    /// Run the command against `keystore`. `client_config` is the client config using the same
+    /// keystore, if any, whose aliases are resolved and kept in sync with the keystore.
+    pub fn execute(
+        self,
+        keystore: &mut Keystore,
+        client_config: Option<&mut SuiClientConfig>,
+    ) -> Result<KeyToolCommandResult, anyhow::Error> {
+        Ok(match self {
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
             } => {
-                let k = key_scheme.to_string();
                 if "bls12381" == key_scheme.to_string() {
                     let (address, keypair): (_, AuthorityKeyPair) = get_key_pair();
                     let file_name = format!("bls-{address}.key");
                     write_authority_keypair_to_file(&keypair, &file_name)?;
+                    KeyToolCommandResult::Generate(GeneratedKey {
+                        address,
+                        key_scheme: key_scheme.to_string(),
+                        file: file_name,
+                    })
                 } else {
                     let mnemonic = Mnemonic::random(OsRng, Default::default());
                     let seed = mnemonic.to_seed("");
-                    match derive_key_pair_from_path(seed.as_bytes(), derivation_path, &key_scheme) {
-                        Ok((address, kp)) => {
-                            let file_name = format!("{address}.key");
-                            write_keypair_to_file(&kp, &file_name)?;
-                            println!("{:?} key generated and saved to '{file_name}'", k);
-                        }
-                        Err(e) => println!("Failed to generate keypair: {:?}", e),
-                    }
+                    let (address, kp) =
+                        derive_key_pair_from_path(seed.as_bytes(), derivation_path, &key_scheme)
+                            .map_err(|e| anyhow!("Failed to generate keypair: {:?}", e))?;
+                    let file_name = format!("{address}.key");
+                    write_keypair_to_file(&kp, &file_name)?;
+                    KeyToolCommandResult::Generate(GeneratedKey {
+                        address,
+                        key_scheme: key_scheme.to_string(),
+                        file: file_name,
+                    })
                 }
             }
             KeyToolCommand::Show { file } => {
-                let res: Result<SuiKeyPair, anyhow::Error> = read_keypair_from_file(&file);
-                match res {
-                    Ok(keypair) => {
-                        println!("Public Key: {}", encode_bytes_hex(keypair.public()));
-                        println!("Flag: {}", keypair.public().flag());
-                    }
-                    Err(e) => {
-                        println!("Failed to read keypair at path {:?} err: {:?}", file, e)
-                    }
-                }
+                let keypair = read_keypair_from_file(&file).map_err(|e| {
+                    anyhow!("Failed to read keypair at path {:?} err: {:?}", file, e)
+                })?;
+                KeyToolCommandResult::Show(KeyInfo {
+                    public_key: encode_bytes_hex(keypair.public()),
+                    flag: keypair.public().flag(),
+                })
             }
 
-            KeyToolCommand::Unpack { keypair } => {
-                store_and_print_keypair((&keypair.public()).into(), keypair)
-            }
-            KeyToolCommand::List => {
-                println!(
-                    " {0: ^42} | {1: ^45} | {2: ^6}",
-                    "Sui Address", "Public Key (Base64)", "Scheme"
-                );
-                println!("{}", ["-"; 100].join(""));
-                for pub_key in keystore.keys() {
-                    println!(
-                        " {0: ^42} | {1: ^45} | {2: ^6}",
-                        Into::<SuiAddress>::into(&pub_key),
-                        Base64::encode(&pub_key),
-                        pub_key.scheme().to_string()
-                    );
-                }
-            }
+            KeyToolCommand::Unpack { keypair } => KeyToolCommandResult::Unpack(
+                store_and_print_keypair((&keypair.public()).into(), keypair)?,
+            ),
+            KeyToolCommand::List => KeyToolCommandResult::List(
+                keystore
+                    .keys()
+                    .into_iter()
+                    .map(|pub_key| KeyEntry {
+                        sui_address: (&pub_key).into(),
+                        public_key_base64: Base64::encode(&pub_key),
+                        scheme: pub_key.scheme().to_string(),
+                    })
+                    .collect(),
+            ),
             KeyToolCommand::Sign { address, data } => {
                 info!("Data to sign : {}", data);
                 info!("Address : {}", address);
                 let message = Base64::decode(&data).map_err(|e| anyhow!(e))?;
                 let signature = keystore.sign(&address, &message)?;
+                let serialized_signature = Base64::encode(signature.as_ref());
                 // Separate pub key and signature string, signature and pub key are concatenated with an '@' symbol.
                 let signature_string = format!("{:?}", signature);
                 let sig_split = signature_string.split('@').collect::<Vec<_>>();
@@ -139,9 +182,12 @@ impl KeyToolCommand {
                 let pub_key = sig_split
                     .last()
                     .ok_or_else(|| anyhow!("Error creating signature."))?;
-                info!("Flag Base64: {}", flag);
-                info!("Public Key Base64: {}", pub_key);
-                info!("Signature : {}", signature);
+                KeyToolCommandResult::Sign(SignatureParts {
+                    flag: flag.to_string(),
+                    public_key: pub_key.to_string(),
+                    signature: signature.to_string(),
+                    serialized_signature,
+                })
             }
             KeyToolCommand::Import {
                 mnemonic_phrase,
@@ -150,7 +196,53 @@ impl KeyToolCommand {
             } => {
                 let address =
                     keystore.import_from_mnemonic(&mnemonic_phrase, key_scheme, derivation_path)?;
-                info!("Key imported for address [{address}]");
+                KeyToolCommandResult::Import(address)
+            }
+
+            KeyToolCommand::Export { address, yes } => {
+                let address = resolve_address(&address, client_config.as_deref())?;
+                let keypair = keystore.get_key(&address)?;
+                if !yes
+                    && !confirm(&format!(
+                    "Export the private key of {address}? Anyone holding it controls the address."
+                ))? {
+                    return Err(anyhow!("Export of the key of {address} cancelled"));
+                }
+                KeyToolCommandResult::Export(ExportedKey {
+                    address,
+                    keypair: keypair.encode_base64(),
+                })
+            }
+            KeyToolCommand::Remove { address } => {
+                let address = resolve_address(&address, client_config.as_deref())?;
+                keystore.remove_key(&address)?;
+                if let Some(config) = client_config {
+                    config.aliases.retain(|_, aliased| *aliased != address);
+                    if config.active_address == Some(address) {
+                        config.active_address = keystore.addresses().first().copied();
+                    }
+                }
+                KeyToolCommandResult::Remove(address)
+            }
+
+            KeyToolCommand::ProofOfPossession {
+                protocol_key_file,
+                address,
+            } => {
+                let keypair =
+                    read_authority_keypair_from_file(&protocol_key_file).map_err(|e| {
+                        anyhow!(
+                            "Failed to read protocol keypair at path {:?} err: {:?}",
+                            protocol_key_file,
+                            e
+                        )
+                    })?;
+                let proof_of_possession = generate_proof_of_possession(&keypair, address);
+                KeyToolCommandResult::ProofOfPossession(ProofOfPossession {
+                    protocol_key: Base64::encode(keypair.public().as_bytes()),
+                    address,
+                    proof_of_possession: Base64::encode(proof_of_possession.as_ref()),
+                })
             }
 
             KeyToolCommand::LoadKeypair { file } => {
@@ -158,41 +250,251 @@ impl KeyToolCommand {
                     Ok(keypair) => {
                         // Account keypair is encoded with the key scheme flag {},
                         // and network and worker keypair are not.
-                        println!("Account Keypair: {}", keypair.encode_base64());
+                        let mut keypairs = LoadedKeypairs {
+                            account_keypair: Some(keypair.encode_base64()),
+                            ..Default::default()
+                        };
                         if let SuiKeyPair::Ed25519SuiKeyPair(kp) = keypair {
-                            println!("Network Keypair: {}", kp.encode_base64());
-                            println!("Worker Keypair: {}", kp.encode_base64());
+                            keypairs.network_keypair = Some(kp.encode_base64());
+                            keypairs.worker_keypair = Some(kp.encode_base64());
                         };
+                        KeyToolCommandResult::LoadKeypair(keypairs)
                     }
                     Err(_) => {
                         // Authority keypair file is not stored with the flag, it will try read as BLS keypair..
-                        match read_authority_keypair_from_file(&file) {
-                            Ok(kp) => println!("Protocol Keypair: {}", kp.encode_base64()),
-                            Err(e) => {
-                                println!("Failed to read keypair at path {:?} err: {:?}", file, e)
-                            }
-                        }
+                        let kp = read_authority_keypair_from_file(&file).map_err(|e| {
+                            anyhow!("Failed to read keypair at path {:?} err: {:?}", file, e)
+                        })?;
+                        KeyToolCommandResult::LoadKeypair(LoadedKeypairs {
+                            protocol_keypair: Some(kp.encode_base64()),
+                            ..Default::default()
+                        })
                     }
                 }
             }
+        })
+    }
+}
+
+/// The output of a keytool command, printed as JSON with `--json`: an object with the name of the
+/// command in `command` and its output in `output`, e.g.
+/// `{"command": "import", "output": "<address>"}`.
+#[derive(Serialize)]
+#[serde(tag = "command", content = "output", rename_all = "kebab-case")]
+pub enum KeyToolCommandResult {
+    Generate(GeneratedKey),
+    Show(KeyInfo),
+    Unpack(String),
+    List(Vec<KeyEntry>),
+    Sign(SignatureParts),
+    Import(SuiAddress),
+    Export(ExportedKey),
+    Remove(SuiAddress),
+    ProofOfPossession(ProofOfPossession),
+    LoadKeypair(LoadedKeypairs),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofOfPossession {
+    /// Base64 encoded protocol public key.
+    pub protocol_key: String,
+    pub address: SuiAddress,
+    /// Base64 encoded signature of the protocol key over itself and the address.
+    pub proof_of_possession: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedKey {
+    pub address: SuiAddress,
+    /// Base64 encoded keypair with its key scheme flag, as stored in the keystore.
+    pub keypair: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedKey {
+    pub address: SuiAddress,
+    pub key_scheme: String,
+    /// The file the keypair is written to.
+    pub file: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyInfo {
+    /// Hex encoded public key.
+    pub public_key: String,
+    pub flag: u8,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyEntry {
+    pub sui_address: SuiAddress,
+    pub public_key_base64: String,
+    pub scheme: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureParts {
+    pub flag: String,
+    pub public_key: String,
+    pub signature: String,
+    /// The flag, signature and public key bytes concatenated, Base64 encoded.
+    pub serialized_signature: String,
+}
+
+/// The keypairs a key file holds, as encoded in the NodeConfig.
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedKeypairs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_keypair: Option<String>,
+}
+
+impl Display for KeyToolCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = String::new();
+        match self {
+            KeyToolCommandResult::Generate(key) => {
+                writeln!(
+                    writer,
+                    "{:?} key generated and saved to '{}'",
+                    key.key_scheme, key.file
+                )?;
+            }
+            KeyToolCommandResult::Show(key) => {
+                writeln!(writer, "Public Key: {}", key.public_key)?;
+                writeln!(writer, "Flag: {}", key.flag)?;
+            }
+            KeyToolCommandResult::Unpack(path) => {
+                writeln!(writer, "Address, keypair and key scheme written to {path}")?;
+            }
+            KeyToolCommandResult::List(keys) => {
+                writeln!(
+                    writer,
+                    " {0: ^42} | {1: ^45} | {2: ^6}",
+                    "Sui Address", "Public Key (Base64)", "Scheme"
+                )?;
+                writeln!(writer, "{}", ["-"; 100].join(""))?;
+                for key in keys {
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^45} | {2: ^6}",
+                        key.sui_address, key.public_key_base64, key.scheme
+                    )?;
+                }
+            }
+            KeyToolCommandResult::Sign(signature) => {
+                writeln!(writer, "Flag Base64: {}", signature.flag)?;
+                writeln!(writer, "Public Key Base64: {}", signature.public_key)?;
+                writeln!(writer, "Signature : {}", signature.signature)?;
+                writeln!(
+                    writer,
+                    "Serialized signature : {}",
+                    signature.serialized_signature
+                )?;
+            }
+            KeyToolCommandResult::Import(address) => {
+                writeln!(writer, "Key imported for address [{address}]")?;
+            }
+            KeyToolCommandResult::Export(key) => {
+                writeln!(writer, "Keypair of {}: {}", key.address, key.keypair)?;
+            }
+            KeyToolCommandResult::Remove(address) => {
+                writeln!(writer, "Key removed for address [{address}]")?;
+            }
+            KeyToolCommandResult::ProofOfPossession(proof) => {
+                writeln!(writer, "Protocol Public Key Base64: {}", proof.protocol_key)?;
+                writeln!(writer, "Address: {}", proof.address)?;
+                writeln!(
+                    writer,
+                    "Proof of Possession Base64: {}",
+                    proof.proof_of_possession
+                )?;
+            }
+            KeyToolCommandResult::LoadKeypair(keypairs) => {
+                if let Some(kp) = &keypairs.account_keypair {
+                    writeln!(writer, "Account Keypair: {kp}")?;
+                }
+                if let Some(kp) = &keypairs.network_keypair {
+                    writeln!(writer, "Network Keypair: {kp}")?;
+                }
+                if let Some(kp) = &keypairs.worker_keypair {
+                    writeln!(writer, "Worker Keypair: {kp}")?;
+                }
+                if let Some(kp) = &keypairs.protocol_keypair {
+                    writeln!(writer, "Protocol Keypair: {kp}")?;
+                }
+            }
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+impl Debug for KeyToolCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", s)
+    }
+}
+
+impl KeyToolCommandResult {
+    pub fn print(&self, pretty: bool) {
+        let line = if pretty {
+            format!("{self}")
+        } else {
+            format!("{:?}", self)
+        };
+        // Not mirrored to the logs, since outputs hold private keys.
+        for line in line.lines() {
+            println!("{line}");
         }
+    }
+}
 
-        Ok(())
+/// The address an alias of the client config stands for, or the address itself.
+fn resolve_address(
+    address: &AddressOrAlias,
+    client_config: Option<&SuiClientConfig>,
+) -> Result<SuiAddress, anyhow::Error> {
+    match (address, client_config) {
+        (AddressOrAlias::Address(address), _) => Ok(*address),
+        (_, Some(config)) => config.resolve_address(address),
+        (AddressOrAlias::Alias(alias), None) => Err(anyhow!(
+            "Cannot resolve the alias [{alias}] without a client config"
+        )),
     }
 }
 
-fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
+/// Ask a yes/no question on the terminal, defaulting to no. The question goes to stderr so
+/// stdout only holds the output of the command.
+fn confirm(question: &str) -> Result<bool, anyhow::Error> {
+    eprint!("{question} [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().eq_ignore_ascii_case("y"))
+}
+
+fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) -> anyhow::Result<String> {
     let path_str = format!("{}.key", address).to_lowercase();
     let path = Path::new(&path_str);
     let address = format!("{}", address);
     let kp = keypair.encode_base64();
     let flag = keypair.public().flag();
     let out_str = format!("address: {}\nkeypair: {}\nflag: {}", address, kp, flag);
-    fs::write(path, out_str).unwrap();
-    println!(
-        "Address, keypair and key scheme written to {}",
-        path.to_str().unwrap()
-    );
+    fs::write(path, out_str)?;
+    Ok(path_str)
 }
 
 pub fn write_keypair_to_file<P: AsRef<std::path::Path>>(
@@ -7,6 +7,7 @@ pub mod config;
 pub mod console;
 pub mod keytool;
 pub mod shell;
+pub mod source_verifier;
 pub mod sui_commands;
 pub mod sui_move;
 
@@ -4,7 +4,7 @@ extern crate core;
 
 use clap::*;
 use colored::Colorize;
-use sui::sui_commands::SuiCommand;
+use sui::sui_commands::{CommandError, SuiCli, SuiCommand};
 use sui_types::exit_main;
 use tracing::debug;
 #[cfg(test)]
@@ -17,8 +17,8 @@ async fn main() {
     colored::control::set_virtual_terminal(true).unwrap();
 
     let bin_name = env!("CARGO_BIN_NAME");
-    let cmd: SuiCommand = SuiCommand::parse();
-    let _guard = match cmd {
+    let cli: SuiCli = SuiCli::parse();
+    let _guard = match cli.command {
         SuiCommand::Console { .. } | SuiCommand::Client { .. } => {
             telemetry_subscribers::TelemetryConfig::new(bin_name)
                 .with_log_file(&format!("{bin_name}.log"))
@@ -33,5 +33,13 @@ async fn main() {
     if let Some(git_rev) = option_env!("GIT_REVISION") {
         debug!("Sui CLI built at git revision {git_rev}");
     }
-    exit_main!(cmd.execute().await);
+    if cli.json {
+        if let Err(err) = cli.command.execute(true).await {
+            let error = CommandError::from(&err);
+            println!("{}", serde_json::to_string_pretty(&error).unwrap());
+            std::process::exit(1);
+        }
+    } else {
+        exit_main!(cli.command.execute(false).await);
+    }
 }
@@ -18,9 +18,11 @@ use rustyline::completion::{Completer, Pair};
 use rustyline::error::ReadlineError;
 use rustyline::highlight::Highlighter;
 use rustyline::hint::Hinter;
+use rustyline::history::History;
 use rustyline::validate::Validator;
 use rustyline::{Config, Context, Editor};
 use rustyline_derive::Helper;
+use serde_json::Value;
 use unescape::unescape;
 
 #[path = "unit_tests/shell_tests.rs"]
@@ -33,6 +35,8 @@ pub struct Shell<P: Display, S, H> {
     state: S,
     handler: H,
     command: CommandStructure,
+    /// Results of commands stored with `let`.
+    variables: BTreeMap<String, Value>,
 }
 
 impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
@@ -51,6 +55,7 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
             state,
             handler,
             command,
+            variables: BTreeMap::new(),
         }
     }
 
@@ -83,59 +88,240 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
                 Err(err) => return Err(err.into()),
             };
 
-            let line = substitute_env_variables(line);
-
-            // Runs the line
-            match split_and_unescape(line.trim()) {
-                Ok(line) => {
-                    if let Some(s) = line.first() {
-                        // These are shell only commands.
-                        match s.as_str() {
-                            "quit" | "exit" => {
-                                writeln!(out, "Bye!")?;
-                                break;
-                            }
-                            "clear" => {
-                                // Clear screen and move cursor to top left
-                                write!(out, "\x1B[2J\x1B[1;1H")?;
-                                continue;
-                            }
-                            "echo" => {
-                                let line = line.as_slice()[1..line.len()].join(" ");
-                                writeln!(out, "{}", line)?;
-                                continue;
-                            }
-                            "env" => {
-                                for (key, var) in env::vars() {
-                                    writeln!(out, "{}={}", key, var)?;
-                                }
-                                continue;
-                            }
-                            "history" => {
-                                for (pos, history) in rl.history().iter().enumerate() {
-                                    println!(" {} {}", pos + 1, history);
-                                }
-                                continue;
-                            }
-                            _ => {}
+            match self
+                .execute_line(&line, Some(rl.history()), out, completion_cache.clone())
+                .await
+            {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(e) => writeln!(err, "{}", e.to_string().red())?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the lines of `script` as if they were typed in the shell, stopping at the first line
+    /// that fails. Empty lines and lines starting with `#` are skipped.
+    pub async fn run_script(
+        &mut self,
+        script: &str,
+        out: &mut (dyn Write + Send),
+    ) -> Result<(), anyhow::Error> {
+        let completion_cache = Arc::new(RwLock::new(BTreeMap::new()));
+        for (number, line) in script.lines().enumerate() {
+            if is_blank_or_comment(line) {
+                continue;
+            }
+            writeln!(out, "{}{}", self.prompt, line.trim())?;
+            match self
+                .execute_line(line, None, out, completion_cache.clone())
+                .await
+            {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(e) => return Err(anyhow!("Script failed at line {}: {e}", number + 1)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a line of input, returning whether the shell should exit.
+    async fn execute_line(
+        &mut self,
+        line: &str,
+        history: Option<&History>,
+        out: &mut (dyn Write + Send),
+        completion_cache: CompletionCache,
+    ) -> Result<bool, anyhow::Error> {
+        if is_blank_or_comment(line) {
+            return Ok(false);
+        }
+        let mut line = substitute_and_split(line, &self.variables)?;
+
+        // `let <name> = <command>` stores the result of the command in a variable.
+        let variable = if line.first().map(String::as_str) == Some("let") {
+            match line.as_slice() {
+                [_, name, eq, _, ..] if eq == "=" && is_variable_name(name) => {
+                    let name = name.clone();
+                    line.drain(..3);
+                    Some(name)
+                }
+                _ => return Err(anyhow!("Usage: let <name> = <command>")),
+            }
+        } else {
+            None
+        };
+
+        let command = match line.first() {
+            Some(command) => command.as_str(),
+            // do nothing if line is empty
+            None => return Ok(false),
+        };
+        if variable.is_none() {
+            // These are shell only commands.
+            match command {
+                "quit" | "exit" => {
+                    writeln!(out, "Bye!")?;
+                    return Ok(true);
+                }
+                "clear" => {
+                    // Clear screen and move cursor to top left
+                    write!(out, "\x1B[2J\x1B[1;1H")?;
+                    return Ok(false);
+                }
+                "echo" => {
+                    let line = line.as_slice()[1..line.len()].join(" ");
+                    writeln!(out, "{}", line)?;
+                    return Ok(false);
+                }
+                "env" => {
+                    for (key, var) in env::vars() {
+                        writeln!(out, "{}={}", key, var)?;
+                    }
+                    return Ok(false);
+                }
+                "history" => {
+                    for (pos, history) in history.into_iter().flat_map(|h| h.iter()).enumerate() {
+                        writeln!(out, " {} {}", pos + 1, history)?;
+                    }
+                    return Ok(false);
+                }
+                "vars" => {
+                    for (name, value) in &self.variables {
+                        if line.len() == 1 || line[1..].contains(name) {
+                            writeln!(out, "{name} = {}", serde_json::to_string_pretty(value)?)?;
                         }
-                    } else {
-                        // do nothing if line is empty
-                        continue;
                     }
+                    return Ok(false);
+                }
+                _ => {}
+            }
+        }
+
+        let value = self
+            .handler
+            .handle_async(line, &mut self.state, completion_cache)
+            .await?;
+        if let Some(name) = variable {
+            let value =
+                value.ok_or_else(|| anyhow!("The command has no result to store in {name}"))?;
+            self.variables.insert(name, value);
+        }
+        Ok(false)
+    }
+}
+
+fn is_blank_or_comment(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+fn is_variable_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
 
-                    if self
-                        .handler
-                        .handle_async(line, &mut self.state, completion_cache.clone())
-                        .await
-                    {
-                        break;
-                    };
+/// Substitute the variables of `line` and split it into arguments. Environment variables are
+/// substituted in the whole line before it is split, so one can hold several arguments. The value
+/// of a shell variable stays a single argument, even if it contains spaces or quotes. Substituted
+/// text is not substituted again.
+fn substitute_and_split(
+    line: &str,
+    variables: &BTreeMap<String, Value>,
+) -> Result<Vec<String>, anyhow::Error> {
+    // Shell variables are held out of the line as placeholders until it is split.
+    let mut values = Vec::new();
+    let line = substitute_variables(line, variables, |value| {
+        values.push(value);
+        format!("{PLACEHOLDER}{}{PLACEHOLDER}", values.len() - 1)
+    })?;
+    Ok(split_and_unescape(substitute_env_variables(line).trim())?
+        .iter()
+        .map(|arg| fill_placeholders(arg, &values))
+        .collect())
+}
+
+/// Delimits the placeholders of shell variables. Environment variables cannot contain it.
+const PLACEHOLDER: char = '\0';
+
+fn fill_placeholders(arg: &str, values: &[String]) -> String {
+    arg.split(PLACEHOLDER)
+        .enumerate()
+        .map(|(i, part)| match i % 2 {
+            0 => part,
+            _ => part
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| values.get(index))
+                .map_or("", String::as_str),
+        })
+        .collect()
+}
+
+/// Replace `$name` with the value of the shell variable `name`, and `${name.field.0}` with the
+/// value found by following the path of object fields and array indices into it, as returned by
+/// `insert` for the value. String values are given to `insert` without quotes, other values as
+/// JSON. `$NAME` is left for environment variable substitution if there is no such shell variable.
+fn substitute_variables(
+    line: &str,
+    variables: &BTreeMap<String, Value>,
+    mut insert: impl FnMut(String) -> String,
+) -> Result<String, anyhow::Error> {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| anyhow!("Missing closing brace in ${{{braced}"))?;
+            let reference = &braced[..end];
+            let mut path = reference.split('.');
+            let name = path.next().unwrap_or_default();
+            let mut value = match variables.get(name) {
+                Some(value) => value,
+                None => match env::var(name) {
+                    Ok(var) if !reference.contains('.') => {
+                        result.push_str(&insert(var));
+                        rest = &braced[end + 1..];
+                        continue;
+                    }
+                    _ => return Err(anyhow!("Unknown variable {name}")),
+                },
+            };
+            for field in path {
+                value = match value {
+                    Value::Array(values) => field.parse::<usize>().ok().and_then(|i| values.get(i)),
+                    Value::Object(fields) => fields.get(field),
+                    _ => None,
                 }
-                Err(e) => writeln!(err, "{}", e.to_string().red())?,
+                .ok_or_else(|| anyhow!("No field {field} in ${{{reference}}}"))?;
             }
+            result.push_str(&insert(value_to_string(value)));
+            rest = &braced[end + 1..];
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            match variables.get(&rest[..end]) {
+                Some(value) => result.push_str(&insert(value_to_string(value))),
+                None => {
+                    result.push('$');
+                    result.push_str(&rest[..end]);
+                }
+            }
+            rest = &rest[end..];
         }
-        Ok(())
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        _ => value.to_string(),
     }
 }
 
@@ -183,6 +369,11 @@ pub fn install_shell_plugins(clap: Command) -> Command {
     .subcommand(Command::new("echo").about("Write arguments to the console output"))
     .subcommand(Command::new("env").about("Print environment"))
     .subcommand(Command::new("history").about("Print history"))
+    .subcommand(
+        Command::new("let")
+            .about("Store the result of a command in a variable: let <name> = <command>"),
+    )
+    .subcommand(Command::new("vars").about("Print the variables stored with let"))
 }
 
 #[derive(Helper)]
@@ -314,12 +505,13 @@ impl CommandStructure {
 
 #[async_trait]
 pub trait AsyncHandler<T: Send> {
+    /// Run the command in `args`, returning its result as JSON if it has one.
     async fn handle_async(
         &self,
         args: Vec<String>,
         state: &mut T,
         completion_cache: CompletionCache,
-    ) -> bool;
+    ) -> Result<Option<Value>, anyhow::Error>;
 }
 
 pub type CompletionCache = Arc<RwLock<BTreeMap<CacheKey, Vec<String>>>>;
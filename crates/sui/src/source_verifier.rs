@@ -0,0 +1,223 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+
+use anyhow::anyhow;
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::ModuleId;
+use serde::Serialize;
+
+use sui_adapter::bytecode_rewriter::ModuleHandleRewriter;
+use sui_framework_build::compiled_package::CompiledPackage;
+use sui_json_rpc_types::SuiRawData;
+use sui_types::base_types::ObjectID;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+#[cfg(msim)]
+use sui_sdk::embedded_gateway::SuiClient;
+#[cfg(not(msim))]
+use sui_sdk::SuiClient;
+
+/// A difference between a locally built module and the on-chain package it should be part of.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ModuleMismatch {
+    /// The module is built locally but is not part of the on-chain package.
+    MissingOnChain(String),
+    /// The on-chain package contains a module that is not built locally.
+    MissingLocally(String),
+    /// The module's local bytecode differs from its on-chain bytecode.
+    Bytecode(String),
+}
+
+/// The result of comparing the modules of an on-chain package with the ones built locally.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageVerification {
+    pub package_id: ObjectID,
+    pub modules: Vec<String>,
+    pub mismatches: Vec<ModuleMismatch>,
+}
+
+/// The result of verifying a package's sources against the network, one entry per on-chain package.
+#[derive(Serialize, Debug)]
+pub struct SourceVerification {
+    pub packages: Vec<PackageVerification>,
+}
+
+impl SourceVerification {
+    /// Whether every module of every package matched its on-chain bytecode.
+    pub fn is_verified(&self) -> bool {
+        self.packages.iter().all(|p| p.mismatches.is_empty())
+    }
+}
+
+/// Rebuild the dependencies of `package` that are already published, including the Move stdlib
+/// and Sui framework, and compare their bytecode module by module with the on-chain packages. If
+/// `package_id` is given, the package itself is compared with the package published at that ID.
+pub async fn verify_source(
+    client: &SuiClient,
+    package: &CompiledPackage,
+    package_id: Option<ObjectID>,
+) -> Result<SourceVerification, anyhow::Error> {
+    let mut published_deps: BTreeMap<ObjectID, Vec<CompiledModule>> = BTreeMap::new();
+    for module in package.get_dependent_modules() {
+        let address = *module.self_id().address();
+        // Dependencies at address zero are not published yet, they are published with the package.
+        if address == AccountAddress::ZERO {
+            continue;
+        }
+        published_deps
+            .entry(ObjectID::from(address))
+            .or_default()
+            .push(module.clone());
+    }
+    if let Some(package_id) = package_id {
+        published_deps.insert(package_id, package.get_dependency_sorted_modules());
+    }
+
+    let mut packages = vec![];
+    for (package_id, modules) in published_deps {
+        // The Move stdlib and Sui framework are stored at genesis as they are built, without the
+        // rewriting done when publishing.
+        let address = AccountAddress::from(package_id);
+        let local = if address == MOVE_STDLIB_ADDRESS || address == SUI_FRAMEWORK_ADDRESS {
+            serialize_modules(modules)?
+        } else {
+            published_bytecode(modules, package_id)?
+        };
+        let on_chain = match client
+            .read_api()
+            .get_object(package_id)
+            .await?
+            .into_object()?
+            .data
+        {
+            SuiRawData::Package(package) => package.module_map,
+            SuiRawData::MoveObject(_) => {
+                return Err(anyhow!("Object {package_id} is not a package"))
+            }
+        };
+        packages.push(PackageVerification {
+            package_id,
+            modules: local.keys().cloned().collect(),
+            mismatches: compare_modules(&local, &on_chain),
+        });
+    }
+    Ok(SourceVerification { packages })
+}
+
+/// Return the serialized modules, keyed by name, that publishing `modules` stores on-chain at
+/// `package_id`. This repeats the module handle rewriting done by the adapter on publish, because
+/// the on-chain bytecode is not the bytecode that was submitted.
+fn published_bytecode(
+    mut modules: Vec<CompiledModule>,
+    package_id: ObjectID,
+) -> Result<BTreeMap<String, Vec<u8>>, anyhow::Error> {
+    let address = AccountAddress::from(package_id);
+    // Published dependencies are built at their on-chain address, move them back to address zero
+    // so they are rewritten the same way they were when published.
+    for module in modules.iter_mut() {
+        let self_address = module
+            .module_handle_at(module.self_module_handle_idx)
+            .address;
+        if module.address_identifier_at(self_address) == &address {
+            module.address_identifiers[self_address.0 as usize] = AccountAddress::ZERO;
+        }
+    }
+    let sub_map = modules
+        .iter()
+        .map(|module| {
+            let id = module.self_id();
+            let new_id = ModuleId::new(address, id.name().to_owned());
+            (id, new_id)
+        })
+        .collect();
+    let rewriter = ModuleHandleRewriter::new(sub_map)?;
+    for module in modules.iter_mut() {
+        rewriter.sub_module_ids(module);
+    }
+    serialize_modules(modules)
+}
+
+/// Return the serialized modules, keyed by name.
+fn serialize_modules(
+    modules: Vec<CompiledModule>,
+) -> Result<BTreeMap<String, Vec<u8>>, anyhow::Error> {
+    let mut bytecode = BTreeMap::new();
+    for module in modules {
+        let mut bytes = vec![];
+        module.serialize(&mut bytes)?;
+        bytecode.insert(module.self_id().name().to_string(), bytes);
+    }
+    Ok(bytecode)
+}
+
+fn compare_modules(
+    local: &BTreeMap<String, Vec<u8>>,
+    on_chain: &BTreeMap<String, Vec<u8>>,
+) -> Vec<ModuleMismatch> {
+    let mut mismatches = vec![];
+    for (name, bytes) in local {
+        match on_chain.get(name) {
+            None => mismatches.push(ModuleMismatch::MissingOnChain(name.clone())),
+            Some(on_chain_bytes) if on_chain_bytes != bytes => {
+                mismatches.push(ModuleMismatch::Bytecode(name.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for name in on_chain.keys() {
+        if !local.contains_key(name) {
+            mismatches.push(ModuleMismatch::MissingLocally(name.clone()))
+        }
+    }
+    mismatches
+}
+
+impl Display for ModuleMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModuleMismatch::MissingOnChain(name) => {
+                write!(f, "module {name} is not part of the on-chain package")
+            }
+            ModuleMismatch::MissingLocally(name) => {
+                write!(
+                    f,
+                    "on-chain module {name} is not built from the local sources"
+                )
+            }
+            ModuleMismatch::Bytecode(name) => {
+                write!(f, "module {name} does not match its on-chain bytecode")
+            }
+        }
+    }
+}
+
+impl Display for SourceVerification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        if self.packages.is_empty() {
+            writeln!(writer, "No published packages to verify.")?;
+        }
+        for package in &self.packages {
+            if package.mismatches.is_empty() {
+                writeln!(
+                    writer,
+                    "Package {} verified: {}",
+                    package.package_id,
+                    package.modules.join(", ")
+                )?;
+            } else {
+                writeln!(writer, "Package {} does not match:", package.package_id)?;
+                for mismatch in &package.mismatches {
+                    writeln!(writer, "  {mismatch}")?;
+                }
+            }
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
@@ -11,6 +11,7 @@ use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::KeyPair;
 use move_package::BuildConfig;
+use serde::Serialize;
 use tracing::{info, warn};
 
 use sui_config::{builder::ConfigBuilder, NetworkConfig, SUI_KEYSTORE_FILENAME};
@@ -25,12 +26,12 @@ use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 
 use crate::client_commands::{SuiClientCommands, WalletContext};
 use crate::config::{SuiClientConfig, SuiEnv};
-use crate::console::start_console;
+use crate::console::{run_console_script, start_console};
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
 use crate::sui_move::{self, execute_move_command};
 
-#[allow(clippy::large_enum_variant)]
+/// The Sui command line: a command and the options shared by all commands.
 #[derive(Parser)]
 #[clap(
     name = "sui",
@@ -39,6 +40,20 @@ use crate::sui_move::{self, execute_move_command};
     author,
     version
 )]
+pub struct SuiCli {
+    /// Print the output of the command, or its error if it fails, as a single json document.
+    /// Supported by the client and keytool commands, and by the move commands except coverage,
+    /// disassemble, prove and calibrate-costs, whose reports are printed by the Move tools.
+    /// Other commands fail with this flag.
+    #[clap(long, global = true)]
+    pub json: bool,
+    #[clap(subcommand)]
+    pub command: SuiCommand,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
 pub enum SuiCommand {
     /// Start sui network.
     #[clap(name = "start")]
@@ -84,6 +99,10 @@ pub enum SuiCommand {
         /// Sets the file storing the state of our user accounts (an empty one will be created if missing)
         #[clap(long = "client.config")]
         config: Option<PathBuf>,
+        /// Run the console commands in this file instead of starting an interactive session, one
+        /// command per line. Stops at the first command that fails
+        #[clap(long)]
+        script: Option<PathBuf>,
     },
     /// Client for interacting with the Sui network.
     #[clap(name = "client")]
@@ -93,9 +112,6 @@ pub enum SuiCommand {
         config: Option<PathBuf>,
         #[clap(subcommand)]
         cmd: Option<SuiClientCommands>,
-        /// Return command outputs in json format.
-        #[clap(long, global = true)]
-        json: bool,
     },
 
     /// Tool to build and test Move applications.
@@ -113,8 +129,49 @@ pub enum SuiCommand {
     },
 }
 
+/// The error printed by a command run with `--json`, in place of its output.
+#[derive(Serialize)]
+pub struct CommandError {
+    /// The error message.
+    pub error: String,
+    /// The chain of errors causing it, outermost first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub causes: Vec<String>,
+}
+
+impl From<&anyhow::Error> for CommandError {
+    fn from(err: &anyhow::Error) -> Self {
+        Self {
+            error: err.to_string(),
+            causes: err.chain().skip(1).map(|cause| cause.to_string()).collect(),
+        }
+    }
+}
+
 impl SuiCommand {
-    pub async fn execute(self) -> Result<(), anyhow::Error> {
+    /// The name of the command if it has no json output. Move commands are checked by
+    /// `execute_move_command`.
+    fn name_without_json_output(&self) -> Option<&'static str> {
+        match self {
+            SuiCommand::Start { .. } => Some("start"),
+            SuiCommand::Network { .. } => Some("network"),
+            SuiCommand::Genesis { .. } => Some("genesis"),
+            SuiCommand::GenesisCeremony(_) => Some("genesis-ceremony"),
+            SuiCommand::Console { .. } => Some("console"),
+            // Without a subcommand, the help of the client is printed.
+            SuiCommand::Client { cmd: None, .. } => Some("client"),
+            SuiCommand::KeyTool { .. } | SuiCommand::Client { .. } | SuiCommand::Move { .. } => {
+                None
+            }
+        }
+    }
+
+    /// Run the command, printing its output in json format if `json` is set.
+    pub async fn execute(self, json: bool) -> Result<(), anyhow::Error> {
+        if let (true, Some(name)) = (json, self.name_without_json_output()) {
+            bail!("`sui {name}` does not support --json");
+        }
+
         match self {
             SuiCommand::Start { config } => {
                 // Auto genesis if path is none and sui directory doesn't exists.
@@ -179,18 +236,40 @@ impl SuiCommand {
             } => genesis(from_config, write_config, working_dir, force).await,
             SuiCommand::GenesisCeremony(cmd) => run(cmd),
             SuiCommand::KeyTool { keystore_path, cmd } => {
+                // The client config in the config dir uses the default keystore, so only
+                // resolve and update its aliases when keytool uses that keystore too.
+                let client_config_path = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+                let mut client_config = if keystore_path.is_none() && client_config_path.exists() {
+                    let config: SuiClientConfig = PersistedConfig::read(&client_config_path)?;
+                    Some(config.persisted(&client_config_path))
+                } else {
+                    None
+                };
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
                 let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
-                cmd.execute(&mut keystore)
+                let result = cmd.execute(&mut keystore, client_config.as_deref_mut())?;
+                if let Some(config) = &client_config {
+                    config.save()?;
+                }
+                result.print(!json);
+                Ok(())
             }
-            SuiCommand::Console { config } => {
+            SuiCommand::Console { config, script } => {
                 let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config).await?;
                 let context = WalletContext::new(&config).await?;
-                start_console(context, &mut stdout(), &mut stderr()).await
+                match script {
+                    Some(script) => {
+                        let script = fs::read_to_string(&script).map_err(|e| {
+                            anyhow!("Cannot read console script {}: {e}", script.display())
+                        })?;
+                        run_console_script(context, &script, &mut stdout()).await
+                    }
+                    None => start_console(context, &mut stdout(), &mut stderr()).await,
+                }
             }
-            SuiCommand::Client { config, cmd, json } => {
+            SuiCommand::Client { config, cmd } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path).await?;
 
@@ -209,12 +288,15 @@ impl SuiCommand {
                 if let Some(cmd) = cmd {
                     if let Err(e) = context.client.check_api_version() {
                         warn!("{e}");
-                        println!("{}", format!("[warn] {e}").yellow().bold());
+                        // Keep stdout a single json document.
+                        if !json {
+                            println!("{}", format!("[warn] {e}").yellow().bold());
+                        }
                     };
                     cmd.execute(&mut context).await?.print(!json);
                 } else {
                     // Print help
-                    let mut app: Command = SuiCommand::command();
+                    let mut app: Command = SuiCli::command();
                     app.build();
                     app.find_subcommand_mut("client").unwrap().print_help()?;
                 }
@@ -224,7 +306,7 @@ impl SuiCommand {
                 package_path,
                 build_config,
                 cmd,
-            } => execute_move_command(package_path, build_config, cmd),
+            } => execute_move_command(package_path, build_config, cmd, json).await,
         }
     }
 }
@@ -448,6 +530,8 @@ async fn prompt_if_no_config(wallet_conf_path: &Path) -> Result<(), anyhow::Erro
                 envs: vec![env],
                 active_address: Some(new_address),
                 active_env: Some(alias),
+                aliases: Default::default(),
+                seed: None,
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
+use fastcrypto::encoding::Base64;
 use move_cli::base::{self, build};
 use move_package::BuildConfig as MoveBuildConfig;
+use serde::Serialize;
 use serde_json::json;
 use std::path::{Path, PathBuf};
-use sui_framework_build::compiled_package::BuildConfig;
+use sui_framework_build::compiled_package::{BuildConfig, CompiledPackage};
 
 #[derive(Parser)]
 pub struct Build {
@@ -22,16 +24,32 @@ impl Build {
         &self,
         path: Option<PathBuf>,
         build_config: MoveBuildConfig,
+        json: bool,
     ) -> anyhow::Result<()> {
         let rerooted_path = base::reroot_path(path)?;
-        Self::execute_internal(&rerooted_path, build_config, self.dump_bytecode_as_base64)
+        let pkg = Self::execute_internal(
+            &rerooted_path,
+            build_config,
+            self.dump_bytecode_as_base64 && !json,
+        )?;
+        if json {
+            let output = BuildOutput {
+                modules: pkg
+                    .get_modules()
+                    .map(|module| module.self_id().name().to_string())
+                    .collect(),
+                compiled_modules: pkg.get_package_base64(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Ok(())
     }
 
     pub fn execute_internal(
         rerooted_path: &Path,
         config: MoveBuildConfig,
         dump_bytecode_as_base64: bool,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<CompiledPackage> {
         let pkg = sui_framework::build_move_package(
             rerooted_path,
             BuildConfig {
@@ -43,6 +61,16 @@ impl Build {
         if dump_bytecode_as_base64 {
             println!("{}", json!(pkg.get_package_base64()))
         }
-        Ok(())
+        Ok(pkg)
     }
 }
+
+/// The output of `sui move build --json`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildOutput {
+    /// Names of the modules of the package.
+    pub modules: Vec<String>,
+    /// The compiled modules, Base64 encoded, as published.
+    pub compiled_modules: Vec<Base64>,
+}
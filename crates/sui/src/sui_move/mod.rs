@@ -1,10 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use clap::Parser;
 use move_cli::base::test::UnitTestResult;
 use move_package::BuildConfig;
 use move_unit_test::UnitTestingConfig;
+use serde::Serialize;
+use std::io::{stderr, stdout};
 use std::path::PathBuf;
 
 pub mod build;
@@ -13,6 +16,7 @@ pub mod disassemble;
 pub mod new;
 pub mod prove;
 pub mod unit_test;
+pub mod verify_source;
 
 #[derive(Parser)]
 pub enum Command {
@@ -22,6 +26,7 @@ pub enum Command {
     New(new::New),
     Prove(prove::Prove),
     Test(unit_test::Test),
+    VerifySource(verify_source::VerifySource),
     CalibrateCosts(Calib),
 }
 #[derive(Parser)]
@@ -32,16 +37,38 @@ pub struct Calib {
     summarize: bool,
 }
 
-pub fn execute_move_command(
+/// The output of `sui move test --json`, the test report itself is written to stderr.
+#[derive(Serialize)]
+pub struct TestOutput {
+    pub passed: bool,
+}
+
+/// Run a move command. With `json`, build, new, test and verify-source print json, while
+/// coverage, disassemble, prove and calibrate-costs fail as their reports are printed by the
+/// Move tools.
+pub async fn execute_move_command(
     package_path: Option<PathBuf>,
     build_config: BuildConfig,
     command: Command,
+    json: bool,
 ) -> anyhow::Result<()> {
+    // The reports of these commands are printed by the Move tools as they run.
+    let unsupported = match &command {
+        Command::Coverage(_) => Some("coverage"),
+        Command::Disassemble(_) => Some("disassemble"),
+        Command::Prove(_) => Some("prove"),
+        Command::CalibrateCosts(_) => Some("calibrate-costs"),
+        _ => None,
+    };
+    if let (true, Some(name)) = (json, unsupported) {
+        return Err(anyhow!("`sui move {name}` does not support --json"));
+    }
+
     match command {
-        Command::Build(c) => c.execute(package_path, build_config),
+        Command::Build(c) => c.execute(package_path, build_config, json),
         Command::Coverage(c) => c.execute(package_path, build_config),
         Command::Disassemble(c) => c.execute(package_path, build_config),
-        Command::New(c) => c.execute(package_path),
+        Command::New(c) => c.execute(package_path, json),
         Command::Prove(c) => c.execute(package_path, build_config),
         Command::Test(c) => {
             let unit_test_config = UnitTestingConfig {
@@ -56,7 +83,19 @@ pub fn execute_move_command(
 
                 ..UnitTestingConfig::default_with_bound(None)
             };
-            let result = c.execute(package_path, build_config, unit_test_config)?;
+            let result = if json {
+                c.execute(package_path, build_config, unit_test_config, &mut stderr())
+                    .await?
+            } else {
+                c.execute(package_path, build_config, unit_test_config, &mut stdout())
+                    .await?
+            };
+            if json {
+                let output = TestOutput {
+                    passed: result == UnitTestResult::Success,
+                };
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            }
 
             // Return a non-zero exit code if any test failed
             if let UnitTestResult::Failure = result {
@@ -65,6 +104,7 @@ pub fn execute_move_command(
 
             Ok(())
         }
+        Command::VerifySource(c) => c.execute(package_path, build_config, json).await,
         Command::CalibrateCosts(c) => {
             sui_framework::cost_calib::run_calibration(c.runs, c.summarize);
             Ok(())
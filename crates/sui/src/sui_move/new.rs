@@ -3,6 +3,7 @@
 
 use clap::Parser;
 use move_cli::base::new;
+use serde::Serialize;
 use std::path::PathBuf;
 use sui_types::SUI_FRAMEWORK_ADDRESS;
 
@@ -17,9 +18,23 @@ pub struct New {
     pub new: new::New,
 }
 
+/// The output of `sui move new --json`.
+#[derive(Serialize)]
+pub struct NewOutput {
+    pub name: String,
+    /// The directory the package is created in.
+    pub path: PathBuf,
+}
+
 impl New {
-    pub fn execute(self, path: Option<PathBuf>) -> anyhow::Result<()> {
+    pub fn execute(self, path: Option<PathBuf>, json: bool) -> anyhow::Result<()> {
         let name = &self.new.name.to_lowercase();
+        let output = NewOutput {
+            name: self.new.name.clone(),
+            path: path
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(&self.new.name)),
+        };
         self.new.execute(
             path,
             "0.0.1",
@@ -33,6 +48,9 @@ impl New {
             ],
             "",
         )?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
         Ok(())
     }
 }
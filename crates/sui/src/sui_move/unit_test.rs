@@ -9,20 +9,43 @@ use move_cli::base::{
 };
 use move_package::BuildConfig;
 use move_unit_test::UnitTestingConfig;
+use std::io::Write;
 use std::path::PathBuf;
+use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
+use sui_types::base_types::ObjectID;
+use sui_types::object::Object;
+
+use crate::client_commands::WalletContext;
 
 #[derive(Parser)]
 pub struct Test {
     #[clap(flatten)]
     pub test: test::Test,
+    /// Report the gas each test is charged with the Sui cost table: computation and storage
+    /// costs per test, and the computation cost of each function it calls. Storage is reported
+    /// per test only. Cannot be combined with --coverage
+    #[clap(long)]
+    pub gas_report: bool,
+    /// Fetch these objects from the network and make them available to the tests, as if they
+    /// were already in the test scenario's storage
+    #[clap(long, multiple_occurrences = false, multiple_values = true)]
+    pub preload_objects: Vec<ObjectID>,
+    /// Sets the file storing the state of our user accounts, used to connect to the network when
+    /// preloading objects
+    #[clap(long = "client.config")]
+    pub config: Option<PathBuf>,
 }
 impl Test {
-    pub fn execute(
+    pub async fn execute<W: Write + Send>(
         &self,
         path: Option<PathBuf>,
         build_config: BuildConfig,
         unit_test_config: UnitTestingConfig,
+        writer: &mut W,
     ) -> anyhow::Result<UnitTestResult> {
+        if self.gas_report && self.test.compute_coverage {
+            anyhow::bail!("--gas-report cannot be combined with --coverage");
+        }
         // find manifest file directory from a given path or (if missing) from current dir
         let rerooted_path = base::reroot_path(path)?;
         // pre build for Sui-specific verifications
@@ -35,11 +58,46 @@ impl Test {
             },
             dump_bytecode_as_base64,
         )?;
+        let preloaded_objects = self.fetch_preloaded_objects().await?;
+        if self.gas_report {
+            let (result, _) = sui_framework::gas_report::run_move_unit_tests_with_gas_report(
+                &rerooted_path,
+                build_config,
+                Some(unit_test_config),
+                preloaded_objects,
+                writer,
+            )?;
+            return Ok(result);
+        }
         sui_framework::run_move_unit_tests(
             &rerooted_path,
             build_config,
             Some(unit_test_config),
             self.test.compute_coverage,
+            preloaded_objects,
+            writer,
         )
     }
+
+    async fn fetch_preloaded_objects(&self) -> anyhow::Result<Vec<Object>> {
+        if self.preload_objects.is_empty() {
+            return Ok(vec![]);
+        }
+        let config = self
+            .config
+            .clone()
+            .unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+        let context = WalletContext::new(&config).await?;
+        let mut objects = vec![];
+        for id in &self.preload_objects {
+            let object = context
+                .client
+                .read_api()
+                .get_object(*id)
+                .await?
+                .into_object()?;
+            objects.push(object.try_into()?);
+        }
+        Ok(objects)
+    }
 }
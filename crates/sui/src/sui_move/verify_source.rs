@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use move_cli::base;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::path::PathBuf;
+use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
+use sui_types::base_types::ObjectID;
+
+use crate::client_commands::WalletContext;
+use crate::source_verifier::verify_source;
+use crate::sui_move::build::Build;
+
+/// Rebuild the package's published dependencies, and the package itself if `--package-id` is
+/// given, and check that their bytecode matches the packages on-chain.
+#[derive(Parser)]
+pub struct VerifySource {
+    /// ID of the on-chain package built from these sources. If not given, only the package's
+    /// dependencies are verified
+    #[clap(long)]
+    pub package_id: Option<ObjectID>,
+    /// Sets the file storing the state of our user accounts, used to connect to the network
+    #[clap(long = "client.config")]
+    pub config: Option<PathBuf>,
+}
+
+impl VerifySource {
+    pub async fn execute(
+        self,
+        path: Option<PathBuf>,
+        build_config: MoveBuildConfig,
+        json: bool,
+    ) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let pkg = Build::execute_internal(&rerooted_path, build_config, false)?;
+
+        let config = self
+            .config
+            .unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+        let context = WalletContext::new(&config).await?;
+        let verification = verify_source(&context.client, &pkg, self.package_id).await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&verification)?);
+        } else {
+            println!("{verification}");
+        }
+
+        // Return a non-zero exit code if any module does not match
+        if !verification.is_verified() {
+            std::process::exit(1)
+        }
+        Ok(())
+    }
+}
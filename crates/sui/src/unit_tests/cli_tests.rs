@@ -1,36 +1,52 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, time::Duration};
+use std::{collections::BTreeMap, fmt::Write, fs::read_dir, path::PathBuf, str, time::Duration};
 
 use anyhow::anyhow;
+use fastcrypto::encoding::{Base64, Encoding};
+use move_cli::base::test::UnitTestResult;
 use move_package::BuildConfig;
 use serde_json::json;
 
-use sui::client_commands::SwitchResponse;
+use move_core_types::account_address::AccountAddress;
+use sui::client_commands::{reward_pool_tokens, SwitchResponse, TxBuildOptions};
 use sui::{
     client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
-    config::SuiClientConfig,
+    config::{SuiClientConfig, SuiEnv},
+    console::run_console_script,
+    source_verifier::{verify_source, ModuleMismatch},
     sui_commands::SuiCommand,
 };
 use sui_config::genesis_config::{AccountConfig, GenesisConfig, ObjectConfig};
 use sui_config::{
-    NetworkConfig, PersistedConfig, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG, SUI_GENESIS_FILENAME,
-    SUI_KEYSTORE_FILENAME, SUI_NETWORK_CONFIG,
+    Config, NetworkConfig, PersistedConfig, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG,
+    SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME, SUI_NETWORK_CONFIG,
 };
+use sui_framework::{build_move_package, gas_report::run_move_unit_tests_with_gas_report};
+use sui_framework_build::compiled_package::BuildConfig as SuiBuildConfig;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, SuiData, SuiObject, SuiParsedData, SuiParsedObject,
+    GetObjectDataResponse, SuiData, SuiMoveValue, SuiObject, SuiParsedData, SuiParsedObject,
     SuiTransactionEffects,
 };
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::key_derive::derivation_path_for_index;
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
 use sui_macros::sim_test;
+use sui_types::balance::{Balance, Supply};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
     Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
 };
-use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
-use sui_types::{sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS};
+use sui_types::id::UID;
+use sui_types::sui_system_state::{Delegation, StakingPool};
+use sui_types::{
+    base_types::ObjectID,
+    crypto::get_key_pair,
+    gas_coin::GasCoin,
+    object::{Object, Owner},
+};
+use sui_types::{sui_framework_address_concat_string, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 use test_utils::messages::make_transactions_with_wallet_context;
 use test_utils::network::init_cluster_builder_env_aware;
 
@@ -46,7 +62,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
     let start = SuiCommand::Start {
         config: Some(config),
     }
-    .execute()
+    .execute(false)
     .await;
     assert!(matches!(start, Err(..)));
     // Genesis
@@ -56,7 +72,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
         force: false,
         from_config: None,
     }
-    .execute()
+    .execute(false)
     .await?;
 
     // Get all the new file names
@@ -92,7 +108,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
         force: false,
         from_config: None,
     }
-    .execute()
+    .execute(false)
     .await;
     assert!(matches!(result, Err(..)));
 
@@ -100,6 +116,51 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_json_rejected_without_json_output() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let working_dir = temp_dir.path();
+
+    let result = SuiCommand::Genesis {
+        working_dir: Some(working_dir.to_path_buf()),
+        write_config: None,
+        force: false,
+        from_config: None,
+    }
+    .execute(true)
+    .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("`sui genesis` does not support --json"));
+    // The command is rejected before it runs.
+    assert_eq!(read_dir(working_dir)?.count(), 0);
+
+    let result = SuiCommand::Start {
+        config: Some(working_dir.join(SUI_NETWORK_CONFIG)),
+    }
+    .execute(true)
+    .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("`sui start` does not support --json"));
+
+    let result = SuiCommand::Console {
+        config: Some(working_dir.join(SUI_CLIENT_CONFIG)),
+        script: None,
+    }
+    .execute(true)
+    .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("`sui console` does not support --json"));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_addresses_command() -> Result<(), anyhow::Error> {
     let test_cluster = init_cluster_builder_env_aware().build().await?;
@@ -131,7 +192,7 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -158,6 +219,7 @@ async fn test_create_example_nft_command() {
         url: None,
         gas: None,
         gas_budget: None,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await
@@ -206,7 +268,7 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -255,7 +317,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
     let object_to_send = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -265,17 +327,18 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
 
     // Send an object
     SuiClientCommands::Transfer {
-        to: SuiAddress::random_for_testing_only(),
+        to: SuiAddress::random_for_testing_only().into(),
         object_id: object_to_send,
         gas: Some(object_id),
         gas_budget: 50000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
 
     // Fetch gas again
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -308,6 +371,8 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         build_config,
         gas: Some(gas_obj_id),
         gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
+        verify_deps: false,
     }
     .execute(context)
     .await?;
@@ -320,7 +385,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address1`
     SuiClientCommands::Objects {
-        address: Some(address1),
+        address: Some(address1.into()),
     }
     .execute(context)
     .await?
@@ -355,6 +420,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args,
         gas: None,
         gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -395,6 +461,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await;
@@ -419,6 +486,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await;
@@ -444,6 +512,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -476,6 +545,8 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
         build_config,
         gas: Some(gas_obj_id),
         gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
+        verify_deps: false,
     }
     .execute(context)
     .await?;
@@ -502,6 +573,127 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+async fn publish_package(
+    context: &mut WalletContext,
+    package_path: PathBuf,
+) -> Result<ObjectID, anyhow::Error> {
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config: BuildConfig::default(),
+        gas: None,
+        gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
+        verify_deps: true,
+    }
+    .execute(context)
+    .await?;
+    if let SuiClientCommandResult::Publish(response) = resp {
+        Ok(response
+            .effects
+            .created
+            .iter()
+            .find(|obj| obj.owner == Owner::Immutable)
+            .unwrap()
+            .reference
+            .object_id)
+    } else {
+        unreachable!("Invalid response");
+    }
+}
+
+#[sim_test]
+async fn test_verify_source() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let context = &mut test_cluster.wallet;
+
+    // Copy the packages out of the tree, so the address of the dependency can be set once it is
+    // published.
+    let dir = tempfile::tempdir()?;
+    let framework_path = std::fs::canonicalize("../sui-framework")?;
+    for package in ["verify_source_dep", "verify_source_app"] {
+        let package_path = dir.path().join(package);
+        std::fs::create_dir_all(package_path.join("sources"))?;
+        for entry in read_dir(PathBuf::from(TEST_DATA_DIR).join(package).join("sources"))? {
+            let entry = entry?;
+            std::fs::copy(
+                entry.path(),
+                package_path.join("sources").join(entry.file_name()),
+            )?;
+        }
+        let manifest =
+            std::fs::read_to_string(PathBuf::from(TEST_DATA_DIR).join(package).join("Move.toml"))?
+                .replace(
+                    "../../../../../sui-framework",
+                    framework_path.to_str().unwrap(),
+                );
+        std::fs::write(package_path.join("Move.toml"), manifest)?;
+    }
+    let dep_path = dir.path().join("verify_source_dep");
+    let app_path = dir.path().join("verify_source_app");
+
+    // Publish the dependency, then the package depending on it at its published address.
+    let dep_id = publish_package(context, dep_path.clone()).await?;
+    let dep_manifest = std::fs::read_to_string(dep_path.join("Move.toml"))?
+        .replace("dep = \"0x0\"", &format!("dep = \"{dep_id}\""));
+    std::fs::write(dep_path.join("Move.toml"), dep_manifest)?;
+    let app_id = publish_package(context, app_path.clone()).await?;
+
+    // The sources match the package, its dependency and the framework packages on-chain.
+    let pkg = build_move_package(&app_path, SuiBuildConfig::default())?;
+    let verification = verify_source(&context.client, &pkg, Some(app_id)).await?;
+    assert!(verification.is_verified());
+    let package_ids = verification
+        .packages
+        .iter()
+        .map(|package| package.package_id)
+        .collect::<Vec<_>>();
+    for package_id in [
+        ObjectID::from(MOVE_STDLIB_ADDRESS),
+        ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        dep_id,
+        app_id,
+    ] {
+        assert!(package_ids.contains(&package_id));
+    }
+
+    // The modules of another package do not.
+    let verification = verify_source(&context.client, &pkg, Some(dep_id)).await?;
+    let dep_verification = verification
+        .packages
+        .iter()
+        .find(|package| package.package_id == dep_id)
+        .unwrap();
+    assert_eq!(
+        dep_verification.mismatches,
+        vec![
+            ModuleMismatch::MissingOnChain("app".to_string()),
+            ModuleMismatch::MissingLocally("counter".to_string()),
+        ]
+    );
+
+    // Nor does a dependency whose sources changed since it was published.
+    let counter_path = dep_path.join("sources").join("counter.move");
+    let counter = std::fs::read_to_string(&counter_path)?
+        .replace("const START: u64 = 0;", "const START: u64 = 1;");
+    std::fs::write(&counter_path, counter)?;
+    let pkg = build_move_package(&app_path, SuiBuildConfig::default())?;
+    let verification = verify_source(&context.client, &pkg, None).await?;
+    assert!(!verification.is_verified());
+    for package in &verification.packages {
+        if package.package_id == dep_id {
+            assert_eq!(
+                package.mismatches,
+                vec![ModuleMismatch::Bytecode("counter".to_string())]
+            );
+        } else {
+            assert!(package.mismatches.is_empty());
+        }
+    }
+    assert!(publish_package(context, app_path).await.is_err());
+
+    Ok(())
+}
+
 #[allow(clippy::assertions_on_constants)]
 #[sim_test]
 async fn test_native_transfer() -> Result<(), anyhow::Error> {
@@ -523,9 +715,10 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: Some(gas_obj_id),
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -591,9 +784,10 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: None,
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -661,7 +855,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
 
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         env: None,
     }
     .execute(context)
@@ -686,6 +880,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     let os = SuiClientCommands::NewAddress {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: None,
+        index: None,
     }
     .execute(context)
     .await?;
@@ -698,7 +893,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     // Check that we can switch to this address
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(new_addr),
+        address: Some(new_addr.into()),
         env: None,
     }
     .execute(context)
@@ -737,6 +932,7 @@ async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
     SuiClientCommands::NewAddress {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: None,
+        index: None,
     }
     .execute(context)
     .await?;
@@ -756,6 +952,105 @@ async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_alias_command() -> Result<(), anyhow::Error> {
+    let mut cluster = init_cluster_builder_env_aware().build().await?;
+    let context = cluster.wallet_mut();
+    let addresses = context.config.keystore.addresses();
+    let (addr1, addr2) = (addresses[0], addresses[1]);
+
+    // Alias the second address and use the alias in place of the address
+    SuiClientCommands::Alias {
+        alias: Some("bob".to_string()),
+        address: Some(addr2.into()),
+        remove: false,
+    }
+    .execute(context)
+    .await?;
+    SuiClientCommands::Switch {
+        address: Some("bob".parse()?),
+        env: None,
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(addr2, context.active_address()?);
+
+    // Aliases are unique, and an address has a single alias
+    assert!(SuiClientCommands::Alias {
+        alias: Some("bob".to_string()),
+        address: Some(addr1.into()),
+        remove: false,
+    }
+    .execute(context)
+    .await
+    .is_err());
+    let aliases = match (SuiClientCommands::Alias {
+        alias: Some("robert".to_string()),
+        address: None,
+        remove: false,
+    })
+    .execute(context)
+    .await?
+    {
+        SuiClientCommandResult::Aliases(aliases) => aliases,
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases.get("robert"), Some(&addr2));
+
+    // Unknown aliases are rejected
+    assert!(SuiClientCommands::Gas {
+        address: Some("bob".parse()?),
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    SuiClientCommands::Alias {
+        alias: Some("robert".to_string()),
+        address: None,
+        remove: true,
+    }
+    .execute(context)
+    .await?;
+    assert!(context.config.aliases.is_empty());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_derive_address_from_seed() -> Result<(), anyhow::Error> {
+    let mut cluster = init_cluster_builder_env_aware().build().await?;
+    let context = cluster.wallet_mut();
+    assert!(context.config.seed.is_none());
+
+    // The seed is generated on first use, and its recovery phrase shown once
+    let first = context.derive_new_address(SignatureScheme::ED25519, 0, "password")?;
+    assert!(first.recovery_phrase.is_some());
+    assert!(context.config.seed.is_some());
+    assert!(context.config.keystore.addresses().contains(&first.address));
+
+    let second = context.derive_new_address(SignatureScheme::ED25519, 1, "password")?;
+    assert!(second.recovery_phrase.is_none());
+    assert_ne!(first.address, second.address);
+
+    // Derivation is deterministic, and needs the seed password
+    let again = context.derive_new_address(SignatureScheme::ED25519, 0, "password")?;
+    assert_eq!(first.address, again.address);
+    assert!(context
+        .derive_new_address(SignatureScheme::ED25519, 2, "wrong password")
+        .is_err());
+
+    // The phrase recovers the derived addresses
+    let mut keystore = Keystore::from(InMemKeystore::new(0));
+    let recovered = keystore.import_from_mnemonic(
+        &first.recovery_phrase.unwrap(),
+        SignatureScheme::ED25519,
+        Some(derivation_path_for_index(&SignatureScheme::ED25519, 1)?),
+    )?;
+    assert_eq!(second.address, recovered);
+    Ok(())
+}
+
 #[allow(clippy::assertions_on_constants)]
 #[sim_test]
 async fn test_active_address_command() -> Result<(), anyhow::Error> {
@@ -777,7 +1072,7 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
 
     let addr2 = context.config.keystore.addresses().get(1).cloned().unwrap();
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         env: None,
     }
     .execute(context)
@@ -849,6 +1144,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         coin_to_merge,
         gas: Some(gas),
         gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -889,6 +1185,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         coin_to_merge,
         gas: None,
         gas_budget: 10_000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -941,6 +1238,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -992,6 +1290,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
         coin_id: coin,
         amounts: None,
         count: Some(3),
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -1046,6 +1345,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -1106,15 +1406,96 @@ async fn test_execute_signed_tx() -> Result<(), anyhow::Error> {
     let (tx_data, scheme, signature, pubkey) = txn.to_network_data_for_execution();
     SuiClientCommands::ExecuteSignedTx {
         tx_data: tx_data.encoded(),
-        scheme,
-        pubkey: pubkey.encoded(),
-        signature: signature.encoded(),
+        scheme: Some(scheme),
+        pubkey: Some(pubkey.encoded()),
+        signature: Some(signature.encoded()),
+        signatures: vec![],
     }
     .execute(context)
     .await?;
     Ok(())
 }
 
+#[sim_test]
+async fn test_offline_signing() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let address = test_cluster.get_address_0();
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let coin = object_refs.get(1).unwrap().object_id;
+
+    let tx_data = match (SuiClientCommands::TransferSui {
+        to: address1.into(),
+        sui_coin_object_id: coin,
+        gas_budget: 1000,
+        amount: Some(1),
+        tx_options: TxBuildOptions {
+            serialize_unsigned: true,
+        },
+    })
+    .execute(context)
+    .await?
+    {
+        SuiClientCommandResult::SerializeUnsigned(tx_data) => tx_data,
+        _ => panic!("Command failed"),
+    };
+
+    // Sign offline, with the sender's key and an unrelated one.
+    let message = Base64::decode(&tx_data).map_err(|e| anyhow!(e))?;
+    let sender_signature = context.config.keystore.sign(&address, &message)?;
+    let other_signature = context.config.keystore.sign(&address1, &message)?;
+    let wrong_data_signature = context.config.keystore.sign(&address, b"other data")?;
+
+    // None of the signatures is by the sender.
+    let result = SuiClientCommands::ExecuteSignedTx {
+        tx_data: tx_data.clone(),
+        scheme: None,
+        pubkey: None,
+        signature: None,
+        signatures: vec![Base64::encode(other_signature.as_ref())],
+    }
+    .execute(context)
+    .await;
+    assert!(result.is_err());
+
+    // The sender's signature is selected, but signs other data.
+    let result = SuiClientCommands::ExecuteSignedTx {
+        tx_data: tx_data.clone(),
+        scheme: None,
+        pubkey: None,
+        signature: None,
+        signatures: vec![
+            Base64::encode(other_signature.as_ref()),
+            Base64::encode(wrong_data_signature.as_ref()),
+        ],
+    }
+    .execute(context)
+    .await;
+    assert!(result.is_err());
+
+    // The sender's signature is picked among the signatures of several signers.
+    let result = SuiClientCommands::ExecuteSignedTx {
+        tx_data,
+        scheme: None,
+        pubkey: None,
+        signature: None,
+        signatures: vec![
+            Base64::encode(other_signature.as_ref()),
+            Base64::encode(sender_signature.as_ref()),
+        ],
+    }
+    .execute(context)
+    .await?;
+    assert!(matches!(result, SuiClientCommandResult::ExecuteSignedTx(_)));
+    Ok(())
+}
+
 #[sim_test]
 async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     let mut test_cluster = init_cluster_builder_env_aware().build().await?;
@@ -1130,12 +1511,412 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     let coin = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::SerializeTransferSui {
-        to: address1,
+        to: address1.into(),
+        sui_coin_object_id: coin,
+        gas_budget: 1000,
+        amount: Some(1),
+    }
+    .execute(context)
+    .await?;
+    Ok(())
+}
+
+#[sim_test]
+async fn test_tx_history_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let address = test_cluster.get_address_0();
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let coin = object_refs.get(1).unwrap().object_id;
+
+    let digest = match (SuiClientCommands::TransferSui {
+        to: address1.into(),
         sui_coin_object_id: coin,
         gas_budget: 1000,
         amount: Some(1),
+        tx_options: TxBuildOptions::default(),
+    })
+    .execute(context)
+    .await?
+    {
+        SuiClientCommandResult::TransferSui(cert, _) => cert.transaction_digest,
+        _ => panic!("Command failed"),
+    };
+
+    let page = match (SuiClientCommands::TxHistory {
+        address: Some(address.into()),
+        received: false,
+        limit: 1,
+        cursor: None,
+    })
+    .execute(context)
+    .await?
+    {
+        SuiClientCommandResult::TxHistory(page) => page,
+        _ => panic!("Command failed"),
+    };
+    // Most recent first.
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.data[0].certificate.transaction_digest, digest);
+
+    let page = match (SuiClientCommands::TxHistory {
+        address: Some(address1.into()),
+        received: true,
+        limit: 10,
+        cursor: None,
+    })
+    .execute(context)
+    .await?
+    {
+        SuiClientCommandResult::TxHistory(page) => page,
+        _ => panic!("Command failed"),
+    };
+    assert!(page
+        .data
+        .iter()
+        .any(|response| response.certificate.transaction_digest == digest));
+
+    let response = match (SuiClientCommands::Tx { digest }).execute(context).await? {
+        SuiClientCommandResult::Tx(response) => response,
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(response.effects.transaction_digest, digest);
+    assert!(response.effects.status.is_ok());
+
+    // Coins have no dynamic fields.
+    let fields = match (SuiClientCommands::DynamicFields { id: coin })
+        .execute(context)
+        .await?
+    {
+        SuiClientCommandResult::DynamicFields(fields) => fields,
+        _ => panic!("Command failed"),
+    };
+    assert!(fields.is_empty());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_dynamic_fields_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dynamic_fields");
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config: BuildConfig::default(),
+        gas: None,
+        gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
+        verify_deps: false,
     }
     .execute(context)
     .await?;
+    let package = match resp {
+        SuiClientCommandResult::Publish(response) => {
+            response
+                .effects
+                .created
+                .iter()
+                .find(|obj| obj.owner == Owner::Immutable)
+                .unwrap()
+                .reference
+                .object_id
+        }
+        _ => panic!("Command failed"),
+    };
+
+    let effects = match (SuiClientCommands::Call {
+        package,
+        module: "dynamic_fields".to_string(),
+        function: "create".to_string(),
+        type_args: vec![],
+        args: vec![],
+        gas: None,
+        gas_budget: 20_000,
+        tx_options: TxBuildOptions::default(),
+    })
+    .execute(context)
+    .await?
+    {
+        SuiClientCommandResult::Call(_, effects) => effects,
+        _ => panic!("Command failed"),
+    };
+    let parent = effects
+        .created
+        .iter()
+        .find(|obj| obj.owner == Owner::AddressOwner(address))
+        .unwrap()
+        .reference
+        .object_id;
+
+    let mut fields = match (SuiClientCommands::DynamicFields { id: parent })
+        .execute(context)
+        .await?
+    {
+        SuiClientCommandResult::DynamicFields(fields) => fields,
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(fields.len(), 2);
+    fields.sort_by_key(|field| field.name.to_string());
+
+    // The value of a dynamic field is listed directly.
+    assert_eq!(fields[0].name, SuiMoveValue::Number(1));
+    assert_eq!(fields[0].value, SuiMoveValue::Number(42));
+
+    // The name of a dynamic object field is unwrapped, its value is the ID of the child object.
+    assert_eq!(fields[1].name, SuiMoveValue::Number(2));
+    let child = match &fields[1].value {
+        SuiMoveValue::Address(child) => ObjectID::from(*child),
+        value => panic!("Unexpected dynamic object field value {value}"),
+    };
+    assert!(effects
+        .created
+        .iter()
+        .any(|obj| obj.reference.object_id == child));
+    for field in &fields {
+        assert!(effects
+            .created
+            .iter()
+            .any(|obj| obj.reference.object_id == field.field_id
+                && obj.owner == Owner::ObjectOwner(parent.into())));
+    }
     Ok(())
 }
+
+#[sim_test]
+async fn test_stake_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let validator_set = match SuiClientCommands::ListValidators.execute(context).await? {
+        SuiClientCommandResult::ListValidators(validator_set) => validator_set,
+        _ => panic!("Command failed"),
+    };
+    assert!(!validator_set.active_validators.is_empty());
+    let validator = validator_set.active_validators[0].metadata.sui_address;
+
+    // No coin holds exactly this amount, so it is split out of the coins of the address.
+    SuiClientCommands::Stake {
+        validator,
+        amount: 12345,
+        gas: None,
+        gas_budget: 1000,
+        tx_options: TxBuildOptions::default(),
+    }
+    .execute(context)
+    .await?
+    .print(true);
+
+    // The delegation is pending until the next epoch.
+    let (stakes, staked_suis) = match (SuiClientCommands::MyStakes {
+        address: Some(address.into()),
+    })
+    .execute(context)
+    .await?
+    {
+        SuiClientCommandResult::MyStakes(stakes, staked_suis) => (stakes, staked_suis),
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(stakes.len(), 1);
+    assert_eq!(stakes[0].delegation_id, None);
+    assert_eq!(stakes[0].validator_address, validator);
+    assert_eq!(stakes[0].principal, 12345);
+    assert_eq!(staked_suis.len(), 1);
+    assert_eq!(staked_suis[0].principal, 12345);
+    let staked_sui = staked_suis[0].id;
+
+    // The Delegation object is only created at the next epoch, the delegation commands reject
+    // any other object.
+    let result = SuiClientCommands::Unstake {
+        delegation: staked_sui,
+        staked_sui,
+        pool_tokens: None,
+        gas: None,
+        gas_budget: 1000,
+        tx_options: TxBuildOptions::default(),
+    }
+    .execute(context)
+    .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("is not a Delegation"));
+
+    let result = SuiClientCommands::SwitchDelegation {
+        delegation: staked_sui,
+        staked_sui,
+        validator,
+        gas: None,
+        gas_budget: 1000,
+        tx_options: TxBuildOptions::default(),
+    }
+    .execute(context)
+    .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("is not a Delegation"));
+
+    let result = SuiClientCommands::WithdrawRewards {
+        delegation: staked_sui,
+        staked_sui,
+        gas: None,
+        gas_budget: 1000,
+        tx_options: TxBuildOptions::default(),
+    }
+    .execute(context)
+    .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("is not a Delegation"));
+    Ok(())
+}
+
+#[test]
+fn test_reward_pool_tokens() {
+    let validator_address = AccountAddress::random();
+    let delegation = |pool_tokens, principal_sui_amount| Delegation {
+        id: UID::new(ObjectID::random()),
+        validator_address,
+        pool_starting_epoch: 0,
+        pool_tokens: Balance::new(pool_tokens),
+        principal_sui_amount,
+    };
+    let pool = |epoch_starting_sui_balance, epoch_starting_delegation_token_supply| StakingPool {
+        validator_address,
+        starting_epoch: 0,
+        epoch_starting_sui_balance,
+        epoch_starting_delegation_token_supply,
+        sui_balance: epoch_starting_sui_balance,
+        rewards_pool: Balance::new(0),
+        delegation_token_supply: Supply {
+            value: epoch_starting_delegation_token_supply,
+        },
+        pending_delegations: vec![],
+    };
+
+    // No rewards were distributed yet, the pool tokens are all needed for the principal.
+    assert_eq!(
+        reward_pool_tokens(&delegation(100, 100), &pool(1000, 1000)),
+        0
+    );
+    // The pool is worth 25% more than its tokens: 80 of the 100 tokens hold the principal.
+    assert_eq!(
+        reward_pool_tokens(&delegation(100, 100), &pool(1250, 1000)),
+        20
+    );
+    // Part of the principal was already withdrawn.
+    assert_eq!(
+        reward_pool_tokens(&delegation(60, 50), &pool(1250, 1000)),
+        20
+    );
+    // A pool without balance exchanges its tokens one to one.
+    assert_eq!(reward_pool_tokens(&delegation(100, 100), &pool(0, 0)), 0);
+}
+
+// The simulator's client does not depend on the env.
+#[tokio::test]
+#[cfg_attr(msim, ignore)]
+async fn test_console_script_reconnects_on_env_switch() -> Result<(), anyhow::Error> {
+    let cluster = init_cluster_builder_env_aware().build().await?;
+    let config_path = cluster.swarm.dir().join(SUI_CLIENT_CONFIG);
+    let mut config: SuiClientConfig = PersistedConfig::read(&config_path)?;
+    config.envs.push(SuiEnv {
+        alias: "other".to_string(),
+        rpc: cluster.rpc_url().unwrap().to_string(),
+        ws: None,
+    });
+    config.envs.push(SuiEnv {
+        alias: "unreachable".to_string(),
+        rpc: "http://127.0.0.1:1".to_string(),
+        ws: None,
+    });
+    config.persisted(&config_path).save()?;
+
+    // The commands after the switch are sent to the full node of the new env.
+    let context = WalletContext::new(&config_path).await?;
+    run_console_script(context, "switch --env other\ngas", &mut Vec::new()).await?;
+
+    let context = WalletContext::new(&config_path).await?;
+    let err = run_console_script(context, "switch --env unreachable\ngas", &mut Vec::new())
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Script failed at line 1: Cannot connect to environment unreachable"),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn test_move_unit_tests_with_preloaded_objects() {
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("preload_objects");
+    let owner = SuiAddress::from(AccountAddress::from_hex_literal("0xA11CE").unwrap());
+    let coin = Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, 100);
+
+    // The test taking the coin fails unless it is preloaded.
+    let result = sui_framework::run_move_unit_tests(
+        &package_path,
+        BuildConfig::default(),
+        None,
+        false,
+        vec![],
+        &mut Vec::new(),
+    )
+    .unwrap();
+    assert_eq!(result, UnitTestResult::Failure);
+    let result = sui_framework::run_move_unit_tests(
+        &package_path,
+        BuildConfig::default(),
+        None,
+        false,
+        vec![coin.clone()],
+        &mut Vec::new(),
+    )
+    .unwrap();
+    assert_eq!(result, UnitTestResult::Success);
+
+    let mut output = Vec::new();
+    let (result, report) = run_move_unit_tests_with_gas_report(
+        &package_path,
+        BuildConfig::default(),
+        None,
+        vec![coin],
+        &mut output,
+    )
+    .unwrap();
+    assert_eq!(
+        result,
+        UnitTestResult::Success,
+        "{}",
+        String::from_utf8_lossy(&output)
+    );
+    let tests: BTreeMap<_, _> = report
+        .tests
+        .iter()
+        .map(|test| (test.test.rsplit("::").next().unwrap(), test))
+        .collect();
+    assert_eq!(
+        tests.keys().copied().collect::<Vec<_>>(),
+        vec!["test_abort", "test_signer", "test_take_preloaded_coin"]
+    );
+    assert!(tests["test_abort"].aborted);
+    // Tests taking signers are metered like the others.
+    assert!(!tests["test_signer"].aborted);
+    assert!(tests["test_signer"].computation_cost > 0);
+    assert!(!tests["test_take_preloaded_coin"].aborted);
+    assert!(tests["test_take_preloaded_coin"].storage_cost > 0);
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// An object with a dynamic field and a dynamic object field
+module examples::dynamic_fields {
+    use sui::dynamic_field;
+    use sui::dynamic_object_field;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct Parent has key {
+        id: UID,
+    }
+
+    struct Child has key, store {
+        id: UID,
+    }
+
+    /// Create a parent object with the value 42 in its field 1 and a child object in its
+    /// object field 2, and send it to the sender.
+    public entry fun create(ctx: &mut TxContext) {
+        let parent = Parent { id: object::new(ctx) };
+        dynamic_field::add(&mut parent.id, 1u64, 42u64);
+        dynamic_object_field::add(&mut parent.id, 2u64, Child { id: object::new(ctx) });
+        transfer::transfer(parent, tx_context::sender(ctx));
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module preload_objects::preload_objects {
+    #[test_only]
+    use std::signer;
+    #[test_only]
+    use sui::coin::{Self, Coin};
+    #[test_only]
+    use sui::sui::SUI;
+    #[test_only]
+    use sui::test_scenario;
+
+    #[test]
+    fun test_take_preloaded_coin() {
+        let scenario = test_scenario::begin(@0xA11CE);
+        let coin = test_scenario::take_from_sender<Coin<SUI>>(&scenario);
+        assert!(coin::value(&coin) == 100, 0);
+        test_scenario::return_to_sender(&scenario, coin);
+        test_scenario::end(scenario);
+    }
+
+    #[test(alice = @0xA11CE)]
+    fun test_signer(alice: signer) {
+        assert!(signer::address_of(&alice) == @0xA11CE, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_abort() {
+        abort 1
+    }
+}
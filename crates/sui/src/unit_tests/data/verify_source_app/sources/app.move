@@ -0,0 +1,10 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module app::app {
+    use dep::counter;
+
+    public fun first(): u64 {
+        counter::start() + 1
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module dep::counter {
+    const START: u64 = 0;
+
+    public fun start(): u64 {
+        START
+    }
+}
@@ -4,15 +4,23 @@
 use crate::keytool::read_authority_keypair_from_file;
 use crate::keytool::read_keypair_from_file;
 
+use crate::config::SuiClientConfig;
+
+use super::write_authority_keypair_to_file;
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
+use super::KeyToolCommandResult;
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::{KeyPair, ToFromBytes};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair;
 use sui_types::crypto::get_key_pair_from_rng;
+use sui_types::crypto::verify_proof_of_possession;
 use sui_types::crypto::AuthorityKeyPair;
+use sui_types::crypto::AuthoritySignature;
 use sui_types::crypto::Ed25519SuiSignature;
 use sui_types::crypto::EncodeDecodeBase64;
 use sui_types::crypto::Secp256k1SuiSignature;
@@ -35,7 +43,224 @@ fn test_addresses_command() -> Result<(), anyhow::Error> {
     }
 
     // List all addresses with flag
-    KeyToolCommand::List.execute(&mut keystore).unwrap();
+    KeyToolCommand::List.execute(&mut keystore, None).unwrap();
+    Ok(())
+}
+
+/// The json output of a keytool command, checked against the command it is tagged with.
+fn json_output(
+    result: &KeyToolCommandResult,
+    command: &str,
+) -> Result<serde_json::Value, anyhow::Error> {
+    let mut json: serde_json::Value = serde_json::from_str(&format!("{:?}", result))?;
+    assert_eq!(json["command"], command);
+    Ok(json["output"].take())
+}
+
+#[test]
+fn test_json_output() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new(2));
+
+    let result = KeyToolCommand::List.execute(&mut keystore, None)?;
+    let output = json_output(&result, "list")?;
+    let entries = output.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    for entry in entries {
+        let address: SuiAddress = entry["suiAddress"].as_str().unwrap().parse()?;
+        assert!(keystore.addresses().contains(&address));
+        assert_eq!(entry["scheme"], "ed25519");
+    }
+
+    let address = keystore.addresses()[0];
+    let data = Base64::encode(b"hello");
+    let result = KeyToolCommand::Sign {
+        address,
+        data: data.clone(),
+    }
+    .execute(&mut keystore, None)?;
+    let output = json_output(&result, "sign")?;
+    let signature = Signature::from_bytes(
+        &Base64::decode(output["serializedSignature"].as_str().unwrap())
+            .map_err(|e| anyhow::anyhow!(e))?,
+    )?;
+    assert_eq!(signature, keystore.sign(&address, b"hello")?);
+    assert!(output["flag"].is_string());
+    assert!(output["publicKey"].is_string());
+    assert!(output["signature"].is_string());
+
+    let result = KeyToolCommand::Import {
+        mnemonic_phrase: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: None,
+    }
+    .execute(&mut keystore, None)?;
+    let output = json_output(&result, "import")?;
+    let imported: SuiAddress = output.as_str().unwrap().parse()?;
+    assert_eq!(
+        imported.to_string(),
+        "0x1a4623343cd42be47d67314fce0ad042f3c82685"
+    );
+
+    let result = KeyToolCommand::Export {
+        address: address.into(),
+        yes: true,
+    }
+    .execute(&mut keystore, None)?;
+    let output = json_output(&result, "export")?;
+    assert_eq!(
+        output["address"].as_str().unwrap().parse::<SuiAddress>()?,
+        address
+    );
+    let keypair = SuiKeyPair::decode_base64(output["keypair"].as_str().unwrap())
+        .map_err(|e| anyhow::anyhow!(e))?;
+    assert_eq!(SuiAddress::from(&keypair.public()), address);
+
+    let result = KeyToolCommand::Remove {
+        address: address.into(),
+    }
+    .execute(&mut keystore, None)?;
+    let output = json_output(&result, "remove")?;
+    assert_eq!(output.as_str().unwrap().parse::<SuiAddress>()?, address);
+
+    let dir = TempDir::new()?;
+    let protocol_keypair: AuthorityKeyPair =
+        get_key_pair_from_rng(&mut StdRng::from_seed([0; 32])).1;
+    let protocol_key_file = dir.path().join("protocol.key");
+    write_authority_keypair_to_file(&protocol_keypair, &protocol_key_file)?;
+    let result = KeyToolCommand::LoadKeypair {
+        file: protocol_key_file.clone(),
+    }
+    .execute(&mut keystore, None)?;
+    let output = json_output(&result, "load-keypair")?;
+    assert_eq!(output["protocolKeypair"], protocol_keypair.encode_base64());
+    assert!(output.get("accountKeypair").is_none());
+
+    let result = KeyToolCommand::ProofOfPossession {
+        protocol_key_file,
+        address,
+    }
+    .execute(&mut keystore, None)?;
+    let output = json_output(&result, "proof-of-possession")?;
+    assert_eq!(
+        output["address"].as_str().unwrap().parse::<SuiAddress>()?,
+        address
+    );
+    assert_eq!(
+        output["protocolKey"],
+        Base64::encode(protocol_keypair.public().as_bytes())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_export_and_remove() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new(2));
+    let address = keystore.addresses()[0];
+
+    let exported = match (KeyToolCommand::Export {
+        address: address.into(),
+        yes: true,
+    })
+    .execute(&mut keystore, None)?
+    {
+        KeyToolCommandResult::Export(exported) => exported,
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(exported.address, address);
+    let keypair = SuiKeyPair::decode_base64(&exported.keypair).map_err(|e| anyhow::anyhow!(e))?;
+    assert_eq!(SuiAddress::from(&keypair.public()), address);
+
+    KeyToolCommand::Remove {
+        address: address.into(),
+    }
+    .execute(&mut keystore, None)?;
+    assert_eq!(keystore.addresses().len(), 1);
+    assert!(!keystore.addresses().contains(&address));
+
+    // The key is gone, so neither removing nor exporting it again works.
+    assert!(KeyToolCommand::Remove {
+        address: address.into(),
+    }
+    .execute(&mut keystore, None)
+    .is_err());
+    assert!(KeyToolCommand::Export {
+        address: address.into(),
+        yes: true,
+    }
+    .execute(&mut keystore, None)
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_remove_cleans_client_config() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new(2));
+    let addresses = keystore.addresses();
+    let mut config = SuiClientConfig::new(Keystore::from(InMemKeystore::new(0)));
+    config.active_address = Some(addresses[0]);
+    config.set_alias("alice".to_string(), addresses[0])?;
+    config.set_alias("bob".to_string(), addresses[1])?;
+
+    // Aliases resolve to the addresses they stand for, but only with a client config.
+    let exported = match (KeyToolCommand::Export {
+        address: "bob".parse()?,
+        yes: true,
+    })
+    .execute(&mut keystore, Some(&mut config))?
+    {
+        KeyToolCommandResult::Export(exported) => exported,
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(exported.address, addresses[1]);
+    assert!(KeyToolCommand::Export {
+        address: "bob".parse()?,
+        yes: true,
+    }
+    .execute(&mut keystore, None)
+    .is_err());
+
+    // Removing the active address drops its alias and activates another address.
+    KeyToolCommand::Remove {
+        address: "alice".parse()?,
+    }
+    .execute(&mut keystore, Some(&mut config))?;
+    assert_eq!(keystore.addresses(), vec![addresses[1]]);
+    assert!(!config.aliases.contains_key("alice"));
+    assert_eq!(config.aliases.get("bob"), Some(&addresses[1]));
+    assert_eq!(config.active_address, Some(addresses[1]));
+
+    // With no key left there is no active address.
+    KeyToolCommand::Remove {
+        address: "bob".parse()?,
+    }
+    .execute(&mut keystore, Some(&mut config))?;
+    assert!(config.aliases.is_empty());
+    assert_eq!(config.active_address, None);
+    Ok(())
+}
+
+#[test]
+fn test_proof_of_possession() -> Result<(), anyhow::Error> {
+    let dir = TempDir::new()?;
+    let keypair: AuthorityKeyPair = get_key_pair_from_rng(&mut StdRng::from_seed([0; 32])).1;
+    let protocol_key_file = dir.path().join("protocol.key");
+    write_authority_keypair_to_file(&keypair, &protocol_key_file)?;
+    let address = SuiAddress::random_for_testing_only();
+
+    let mut keystore = Keystore::from(InMemKeystore::new(0));
+    let proof = match (KeyToolCommand::ProofOfPossession {
+        protocol_key_file,
+        address,
+    })
+    .execute(&mut keystore, None)?
+    {
+        KeyToolCommandResult::ProofOfPossession(proof) => proof,
+        _ => panic!("Command failed"),
+    };
+    let pop = AuthoritySignature::from_bytes(
+        &Base64::decode(&proof.proof_of_possession).map_err(|e| anyhow::anyhow!(e))?,
+    )?;
+    assert!(verify_proof_of_possession(&pop, keypair.public(), address).is_ok());
     Ok(())
 }
 
@@ -149,7 +374,7 @@ fn test_mnemonics_ed25519() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: None,
     }
-    .execute(&mut keystore)?;
+    .execute(&mut keystore, None)?;
     keystore.keys().iter().for_each(|pk| {
         assert_eq!(
             hex::encode(pk.as_ref()),
@@ -174,7 +399,7 @@ fn test_mnemonics_secp256k1() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: None,
     }
-    .execute(&mut keystore)?;
+    .execute(&mut keystore, None)?;
     keystore.keys().iter().for_each(|pk| {
         assert_eq!(
             hex::encode(pk.as_ref()),
@@ -198,7 +423,7 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/1'/0'/0/0".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_err());
 
     assert!(KeyToolCommand::Import {
@@ -206,7 +431,7 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/0'/784'/0'/0/0".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_err());
 
     assert!(KeyToolCommand::Import {
@@ -214,7 +439,7 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/54'/784'/0'/0/0".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_err());
 
     assert!(KeyToolCommand::Import {
@@ -222,7 +447,7 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/0'/0'/0'".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_err());
 
     assert!(KeyToolCommand::Import {
@@ -230,7 +455,7 @@ fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/44'/784'/0'/0/0".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_err());
 
     Ok(())
@@ -244,7 +469,7 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/0'/0'/0'".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_ok());
 
     assert!(KeyToolCommand::Import {
@@ -252,7 +477,7 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/0'/0'/1'".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_ok());
 
     assert!(KeyToolCommand::Import {
@@ -260,7 +485,7 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/1'/0'/1'".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_ok());
 
     assert!(KeyToolCommand::Import {
@@ -268,7 +493,7 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/0'/0/1".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_ok());
 
     assert!(KeyToolCommand::Import {
@@ -276,7 +501,7 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/1'/0/1".parse().unwrap()),
     }
-    .execute(&mut keystore)
+    .execute(&mut keystore, None)
     .is_ok());
     Ok(())
 }
@@ -5,17 +5,55 @@ use std::collections::BTreeMap;
 use std::env;
 use std::sync::Arc;
 
+use anyhow::anyhow;
+use async_trait::async_trait;
 use rustyline::completion::Completer;
 use rustyline::history::History;
 use rustyline::Context;
+use serde_json::{json, Value};
 
 use sui_types::base_types::ObjectID;
 
 use crate::shell::split_and_unescape;
 use crate::shell::{
-    substitute_env_variables, CacheKey, CommandStructure, CompletionCache, ShellHelper,
+    substitute_and_split, substitute_env_variables, substitute_variables, AsyncHandler, CacheKey,
+    CommandStructure, CompletionCache, Shell, ShellHelper,
 };
 
+/// Records the arguments of each command it runs. `create` returns an object, `fail` fails and
+/// other commands have no result.
+struct RecordingHandler;
+
+#[async_trait]
+impl AsyncHandler<Vec<Vec<String>>> for RecordingHandler {
+    async fn handle_async(
+        &self,
+        args: Vec<String>,
+        commands: &mut Vec<Vec<String>>,
+        _completion_cache: CompletionCache,
+    ) -> Result<Option<Value>, anyhow::Error> {
+        commands.push(args.clone());
+        match args[0].as_str() {
+            "create" => Ok(Some(json!({ "id": "0x1", "name": "my \"first\" object" }))),
+            "fail" => Err(anyhow!("Command failed")),
+            _ => Ok(None),
+        }
+    }
+}
+
+fn recording_shell() -> Shell<&'static str, Vec<Vec<String>>, RecordingHandler> {
+    Shell::new(
+        "> ",
+        vec![],
+        RecordingHandler,
+        CommandStructure {
+            name: "test".to_string(),
+            completions: vec![],
+            children: vec![],
+        },
+    )
+}
+
 #[test]
 fn test_completion_cache_key() {
     let mut cache = BTreeMap::new();
@@ -54,6 +92,83 @@ fn test_substitute_env_variables() {
     );
 }
 
+#[test]
+fn test_substitute_variables() {
+    let package_id = ObjectID::random().to_string();
+    let mut variables = BTreeMap::new();
+    variables.insert(
+        "pkg".to_string(),
+        json!({ "effects": { "created": [{ "reference": { "objectId": package_id } }] } }),
+    );
+    variables.insert("amount".to_string(), json!(100));
+
+    assert_eq!(
+        format!("call --package {package_id} --args 100"),
+        substitute_variables(
+            "call --package ${pkg.effects.created.0.reference.objectId} --args $amount",
+            &variables,
+            |value| value
+        )
+        .unwrap()
+    );
+    // Whole values are substituted as JSON, except for strings
+    assert_eq!(
+        json!({ "objectId": package_id }).to_string(),
+        substitute_variables("${pkg.effects.created.0.reference}", &variables, |value| {
+            value
+        })
+        .unwrap()
+    );
+
+    // Names that are not variables are left for environment variable substitution
+    assert_eq!(
+        "$OBJECT_ID/$amounts",
+        substitute_variables("$OBJECT_ID/$amounts", &variables, |value| value).unwrap()
+    );
+    env::set_var("CONSOLE_TEST_VAR", "value");
+    assert_eq!(
+        "value",
+        substitute_variables("${CONSOLE_TEST_VAR}", &variables, |value| value).unwrap()
+    );
+
+    assert!(substitute_variables("${pkg.effects.mutated}", &variables, |value| value).is_err());
+    assert!(substitute_variables("${pkg.effects.created.1}", &variables, |value| value).is_err());
+    assert!(substitute_variables("${unknown.field}", &variables, |value| value).is_err());
+    assert!(substitute_variables("${pkg", &variables, |value| value).is_err());
+}
+
+#[test]
+fn test_substitute_and_split() {
+    let mut variables = BTreeMap::new();
+    variables.insert(
+        "name".to_string(),
+        json!("my \"first\" $CONSOLE_SPLIT_ARGS"),
+    );
+    env::set_var("CONSOLE_SPLIT_ARGS", "--gas-budget 1000");
+    env::set_var("CONSOLE_SPLIT_REF", "$name");
+
+    // An environment variable can hold several arguments, a shell variable is a single one
+    assert_eq!(
+        vec![
+            "call",
+            "--gas-budget",
+            "1000",
+            "--name",
+            "my \"first\" $CONSOLE_SPLIT_ARGS"
+        ],
+        substitute_and_split("call $CONSOLE_SPLIT_ARGS --name $name", &variables).unwrap()
+    );
+    // Substituted text is not substituted again
+    assert_eq!(
+        vec!["echo", "$name"],
+        substitute_and_split("echo $CONSOLE_SPLIT_REF", &variables).unwrap()
+    );
+    assert_eq!(
+        vec!["echo", "name: my \"first\" $CONSOLE_SPLIT_ARGS!"],
+        substitute_and_split("echo \"name: ${name}!\"", &variables).unwrap()
+    );
+}
+
 #[test]
 fn test_completer() {
     let helper = ShellHelper {
@@ -233,3 +348,36 @@ fn test_split_line() {
         result
     );
 }
+
+#[tokio::test]
+async fn test_script_stops_at_failed_command() {
+    let mut shell = recording_shell();
+    let script = "# setup\nfirst --flag\n\nfail\nsecond";
+    let err = shell.run_script(script, &mut Vec::new()).await.unwrap_err();
+
+    assert_eq!("Script failed at line 4: Command failed", err.to_string());
+    assert_eq!(vec![vec!["first", "--flag"], vec!["fail"]], shell.state);
+}
+
+#[tokio::test]
+async fn test_script_let_captures_result() {
+    let mut shell = recording_shell();
+    let script =
+        "let obj = create --name x\nuse --id ${obj.id} --name ${obj.name}\nlet nothing = other";
+    let err = shell.run_script(script, &mut Vec::new()).await.unwrap_err();
+
+    assert_eq!(
+        "Script failed at line 3: The command has no result to store in nothing",
+        err.to_string()
+    );
+    // Values with spaces and quotes are substituted as a single argument.
+    assert_eq!(
+        vec!["use", "--id", "0x1", "--name", "my \"first\" object"],
+        shell.state[1]
+    );
+    assert_eq!(
+        json!({ "id": "0x1", "name": "my \"first\" object" }),
+        shell.variables["obj"]
+    );
+    assert!(!shell.variables.contains_key("nothing"));
+}
@@ -10,7 +10,7 @@ use jsonrpsee::rpc_params;
 use move_core_types::parser::parse_struct_tag;
 use move_core_types::value::MoveStructLayout;
 use prometheus::Registry;
-use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
+use sui::client_commands::{SuiClientCommandResult, SuiClientCommands, TxBuildOptions};
 use sui_json_rpc_types::{
     type_and_fields_from_move_struct, EventPage, SuiEvent, SuiEventEnvelope, SuiEventFilter,
     SuiExecuteTransactionResponse, SuiExecutionStatus, SuiMoveStruct, SuiMoveValue,
@@ -77,10 +77,42 @@ async fn test_full_node_follows_txes() -> Result<(), anyhow::Error> {
 
     assert_eq!(object.owner.get_owner_address().unwrap(), receiver);
 
+    // the object history ends with the version produced by the transfer
+    let history = node
+        .state()
+        .get_object_history(&transferred_object, None, 100)?;
+    let (_, owner, previous_transaction) = history.last().unwrap();
+    assert_eq!(*previous_transaction, digest);
+    assert_eq!(*owner, Some(Owner::AddressOwner(receiver)));
+
     // timestamp is recorded
     let ts = node.state().get_timestamp_ms(&digest).await?;
     assert!(ts.is_some());
 
+    // the transaction can be found by time range, which is exclusive of its right endpoint
+    let ts = ts.unwrap();
+    let txes = node.state().get_transactions(
+        TransactionQuery::TimeRange {
+            start_time: ts - HOUR_MS,
+            end_time: ts + HOUR_MS,
+        },
+        None,
+        None,
+        false,
+    )?;
+    assert!(txes.contains(&digest));
+
+    let txes = node.state().get_transactions(
+        TransactionQuery::TimeRange {
+            start_time: ts - HOUR_MS,
+            end_time: ts,
+        },
+        None,
+        None,
+        true,
+    )?;
+    assert!(!txes.contains(&digest));
+
     assert_eq!(node.state().metrics.num_post_processing_tasks.get(), 1);
 
     Ok(())
@@ -170,6 +202,56 @@ async fn test_full_node_move_function_index() -> Result<(), anyhow::Error> {
     assert_eq!(txes.len(), 2);
     assert_eq!(txes[1], digest);
 
+    // The package index also includes the publish transaction itself.
+    let txes = node.state().get_transactions(
+        TransactionQuery::Package(package_ref.0),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes.len(), 3);
+    assert_eq!(txes[2], digest);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_move_event_index() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let node = &test_cluster.fullnode_handle.as_ref().unwrap().sui_node;
+    let context = &mut test_cluster.wallet;
+
+    let (_, _, digest) = create_devnet_nft(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let event_type = parse_struct_tag("0x2::devnet_nft::MintNFTEvent")?.to_string();
+    let txes = node.state().get_transactions(
+        TransactionQuery::MoveEvent(event_type),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes, vec![digest]);
+
+    // The event type must match exactly, including its type params
+    let txes = node.state().get_transactions(
+        TransactionQuery::MoveEvent(parse_struct_tag("0x2::devnet_nft::DevNetNFT")?.to_string()),
+        None,
+        None,
+        false,
+    )?;
+    assert!(txes.is_empty());
+
+    // The NFT module is called directly, so the transaction is indexed under the framework
+    let txes = node.state().get_transactions(
+        TransactionQuery::Package(SUI_FRAMEWORK_OBJECT_ID),
+        None,
+        None,
+        false,
+    )?;
+    assert!(txes.contains(&digest));
+
     Ok(())
 }
 
@@ -233,6 +315,15 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     )?;
     assert_eq!(txes.len(), 0);
 
+    // Paying for gas with a framework coin does not index the transfer under the framework
+    let txes = node.state().get_transactions(
+        TransactionQuery::Package(SUI_FRAMEWORK_OBJECT_ID),
+        None,
+        None,
+        false,
+    )?;
+    assert!(!txes.contains(&digest));
+
     // timestamp is recorded
     let ts = node.state().get_timestamp_ms(&digest).await?;
     assert!(ts.is_some());
@@ -481,6 +572,7 @@ async fn test_full_node_sync_flood() -> Result<(), anyhow::Error> {
                         coin_id: object_to_split.0,
                         gas: Some(gas_object_id),
                         gas_budget: 50000,
+                        tx_options: TxBuildOptions::default(),
                     }
                     .execute(context)
                     .await
@@ -6,7 +6,7 @@ use multiaddr::Multiaddr;
 use prometheus::Registry;
 use std::sync::Arc;
 use std::time::Duration;
-use sui_config::{NetworkConfig, ValidatorInfo};
+use sui_config::{FullNodeSyncMode, NetworkConfig, ValidatorInfo};
 use sui_core::authority_active::checkpoint_driver::{
     checkpoint_process_step, CheckpointProcessControl,
 };
@@ -32,6 +32,7 @@ use test_utils::transaction::{
 };
 
 use sui_macros::sim_test;
+use tokio::time::timeout;
 
 #[sim_test]
 async fn reconfig_end_to_end_tests() {
@@ -326,6 +327,69 @@ async fn reconfig_last_checkpoint_sync_missing_tx() {
         .await;
 }
 
+#[sim_test]
+async fn reconfig_fullnode_checkpoint_sync() {
+    let mut configs = test_authority_configs();
+    for c in configs.validator_configs.iter_mut() {
+        // Turn off checkpoint process so that we can have fine control over it in the test.
+        c.enable_checkpoint = false;
+        c.enable_reconfig = true;
+    }
+    let gas_objects = test_gas_objects();
+    let validator_stake = generate_gas_object_with_balance(100000000000000);
+    let handles = init_validators(&configs, gas_objects, &validator_stake).await;
+
+    fast_forward_to_ready_for_reconfig_start(&handles).await;
+    start_epoch_change(&handles).await;
+    fast_forward_to_ready_for_reconfig_finish(&handles).await;
+    let epoch_change_checkpoint = handles[0].with(|node| {
+        node.state()
+            .checkpoints
+            .lock()
+            .latest_stored_checkpoint()
+            .unwrap()
+            .sequence_number()
+    });
+    let results: Vec<_> = handles
+        .iter()
+        .map(|handle| {
+            handle.with_async(|node| async {
+                node.active().finish_epoch_change().await.unwrap();
+            })
+        })
+        .collect();
+    join_all(results).await;
+    let new_committee = handles[0].with(|node| node.state().committee.load().clone());
+
+    // A fullnode started after the epoch change must verify the checkpoints of epoch 0 with
+    // the genesis committee, then move to the committee of epoch 1.
+    let mut fullnode_config = configs.generate_fullnode_config();
+    fullnode_config.sync_mode = FullNodeSyncMode::Checkpoint;
+    let fullnode = start_node(&fullnode_config, Registry::new()).await;
+    timeout(Duration::from_secs(60), async {
+        loop {
+            let synced = fullnode.with(|node| {
+                node.state().checkpoints.lock().next_checkpoint() > epoch_change_checkpoint
+                    && node.state().committee.load().epoch == 1
+            });
+            if synced {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("fullnode did not sync across the epoch change");
+
+    fullnode.with(|node| {
+        assert_eq!(
+            node.state().committee.load().voting_rights,
+            new_committee.voting_rights
+        );
+        assert_eq!(node.active().net.load().committee.epoch, 1);
+    });
+}
+
 async fn create_and_register_new_validator(
     framework_pkg: ObjectRef,
     gas_objects: &mut Vec<Object>,
@@ -352,8 +416,12 @@ async fn create_and_register_new_validator(
                     .unwrap(),
             ),
             CallArg::Pure(bcs::to_bytes(&new_validator.network_address).unwrap()),
+            CallArg::Pure(bcs::to_bytes(new_validator.description().as_bytes()).unwrap()),
+            CallArg::Pure(bcs::to_bytes(new_validator.image_url().as_bytes()).unwrap()),
+            CallArg::Pure(bcs::to_bytes(new_validator.project_url().as_bytes()).unwrap()),
             CallArg::Object(ObjectArg::ImmOrOwnedObject(validator_stake)),
             CallArg::Pure(bcs::to_bytes(&new_validator.gas_price()).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&new_validator.commission_rate()).unwrap()),
         ],
     );
     submit_shared_object_transaction(validator_tx, validator_info).await
@@ -375,10 +443,14 @@ pub fn get_new_validator() -> (ValidatorInfo, AuthoritySignature) {
             stake: 1,
             delegation: 0,
             gas_price: 1,
+            commission_rate: 0,
             network_address: sui_config::utils::new_network_address(),
             narwhal_primary_address: sui_config::utils::new_network_address(),
             narwhal_worker_address: sui_config::utils::new_network_address(),
             narwhal_consensus_address: sui_config::utils::new_network_address(),
+            description: String::new(),
+            image_url: String::new(),
+            project_url: String::new(),
         },
         pop,
     )
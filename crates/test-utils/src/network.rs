@@ -228,6 +228,8 @@ impl TestClusterBuilder {
             envs: Default::default(),
             active_address,
             active_env: Default::default(),
+            aliases: Default::default(),
+            seed: None,
         }
         .save(&wallet_path)?;
 
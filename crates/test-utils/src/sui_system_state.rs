@@ -29,9 +29,13 @@ pub fn test_validatdor_metadata(
         proof_of_possession_bytes: vec![],
         name: to_bytes("zero_commission").unwrap(),
         net_address,
+        description: vec![],
+        image_url: vec![],
+        project_url: vec![],
         next_epoch_stake: 1,
         next_epoch_delegation: 1,
         next_epoch_gas_price: 1,
+        commission_rate: 0,
     }
 }
 
@@ -12,7 +12,7 @@ use tracing::debug;
 use tracing::info;
 
 use sui::client_commands::WalletContext;
-use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
+use sui::client_commands::{SuiClientCommandResult, SuiClientCommands, TxBuildOptions};
 use sui_config::ValidatorInfo;
 use sui_core::authority::AuthorityState;
 use sui_core::authority_client::AuthorityAPI;
@@ -241,6 +241,7 @@ pub async fn create_devnet_nft(
         url: Some("https://sui.io/_nuxt/img/sui-logo.8d3c44e.svg".into()),
         gas: Some(*gas_object),
         gas_budget: Some(50000),
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -275,10 +276,11 @@ pub async fn transfer_sui(
         .unwrap();
 
     let res = SuiClientCommands::TransferSui {
-        to: receiver,
+        to: receiver.into(),
         amount: None,
         sui_coin_object_id: gas_ref.0,
         gas_budget: 50000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -321,10 +323,11 @@ pub async fn transfer_coin(
         object_to_send, sender, receiver
     );
     let res = SuiClientCommands::Transfer {
-        to: receiver,
+        to: receiver.into(),
         object_id: object_to_send,
         gas: None,
         gas_budget: 50000,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await?;
@@ -357,6 +360,7 @@ pub async fn split_coin_with_wallet_context(context: &mut WalletContext, coin_id
         count: Some(2),
         gas: None,
         gas_budget: MAX_GAS,
+        tx_options: TxBuildOptions::default(),
     }
     .execute(context)
     .await